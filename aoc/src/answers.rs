@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use aoc_common::ParseError;
+
+pub const DEFAULT_PATH: &str = "answers.txt";
+
+/// Known answers, keyed by day, part and the name of the input they were produced from.
+/// Each non-blank line of the file is `<day> <part> <input> <answer>`, and `#` starts a comment.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(u8, u8, String), String>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut answers = HashMap::new();
+        for (idx, line) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [day, part, input, answer] = fields[..] else {
+                return Err(ParseError::at_line(
+                    line_no,
+                    "expected <day> <part> <input> <answer>",
+                ));
+            };
+            let day = day
+                .parse()
+                .map_err(|_| ParseError::at_line(line_no, format!("invalid day '{day}'")))?;
+            let part = match part.parse() {
+                Ok(part @ 1..=2) => part,
+                _ => {
+                    return Err(ParseError::at_line(
+                        line_no,
+                        format!("invalid part '{part}'"),
+                    ));
+                }
+            };
+            let key = (day, part, input.to_string());
+            if answers.insert(key, answer.to_string()).is_some() {
+                return Err(ParseError::at_line(
+                    line_no,
+                    format!("duplicate answer for day {day} part {part} input {input}"),
+                ));
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        self.answers
+            .get(&(day, part, input.to_string()))
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            "# day part input answer
+            1 1 embedded 1970720
+
+            18 2 inputs/day18.txt 26,50 # comment",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1, "embedded"), Some("1970720"));
+        assert_eq!(answers.get(18, 2, "inputs/day18.txt"), Some("26,50"));
+        assert_eq!(answers.get(1, 2, "embedded"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Answers::parse("1 1 embedded").unwrap_err(),
+            ParseError::at_line(1, "expected <day> <part> <input> <answer>")
+        );
+        assert_eq!(
+            Answers::parse("\n1 3 embedded 5").unwrap_err(),
+            ParseError::at_line(2, "invalid part '3'")
+        );
+        assert!(Answers::parse("1 1 embedded 5\n1 1 embedded 6").is_err());
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt::Display;
+
+pub const USAGE: &str = "Usage:
+    aoc list
+    aoc run <day> [--part <1|2>] [--input <path>|-] [--spans]
+    aoc run-all
+    aoc verify [--day <day>] [--answers <path>] [--json <path>]
+    aoc bench [--day <day>] [--part <1|2>] [--warmup <n>] [--samples <n>]
+              [--save <path>] [--baseline <path>] [--threshold <percent>]
+
+Inputs are read from --input (- for stdin), then inputs/dayNN.txt (or $AOC_INPUTS/dayNN.txt),
+then the input compiled into the binary.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    List,
+    Run {
+        day: u8,
+        part: Option<u8>,
+        input: Option<String>,
+        spans: bool,
+    },
+    RunAll,
+    Verify {
+        day: Option<u8>,
+        answers: Option<String>,
+        json: Option<String>,
+    },
+    Bench(BenchOptions),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub warmup: usize,
+    pub samples: usize,
+    pub save: Option<String>,
+    pub baseline: Option<String>,
+    pub threshold: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            day: None,
+            part: None,
+            warmup: 3,
+            samples: 20,
+            save: None,
+            baseline: None,
+            threshold: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ArgsError {
+    MissingCommand,
+    UnknownCommand(String),
+    MissingValue(&'static str),
+    InvalidDay(String),
+    InvalidPart(String),
+    InvalidNumber(String),
+    UnexpectedArgument(String),
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgsError::MissingCommand => write!(f, "no command given"),
+            ArgsError::UnknownCommand(command) => write!(f, "unknown command '{command}'"),
+            ArgsError::MissingValue(name) => write!(f, "missing value for {name}"),
+            ArgsError::InvalidDay(day) => write!(f, "invalid day '{day}', expected 1 to 25"),
+            ArgsError::InvalidPart(part) => write!(f, "invalid part '{part}', expected 1 or 2"),
+            ArgsError::InvalidNumber(value) => write!(f, "invalid number '{value}'"),
+            ArgsError::UnexpectedArgument(arg) => write!(f, "unexpected argument '{arg}'"),
+        }
+    }
+}
+
+impl std::error::Error for ArgsError {}
+
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, ArgsError> {
+    let mut args = args.into_iter();
+    let command = args.next().ok_or(ArgsError::MissingCommand)?;
+    let command = match command.as_str() {
+        "list" => Command::List,
+        "run-all" => Command::RunAll,
+        "help" | "--help" | "-h" => Command::Help,
+        "run" => {
+            let day = args.next().ok_or(ArgsError::MissingValue("<day>"))?;
+            let day = parse_day(&day)?;
+            let mut part = None;
+            let mut input = None;
+            let mut spans = false;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--part" => {
+                        let value = args.next().ok_or(ArgsError::MissingValue("--part"))?;
+                        part = Some(parse_part(&value)?);
+                    }
+                    "--input" => {
+                        input = Some(args.next().ok_or(ArgsError::MissingValue("--input"))?);
+                    }
+                    "--spans" => spans = true,
+                    _ => return Err(ArgsError::UnexpectedArgument(arg)),
+                }
+            }
+            return Ok(Command::Run {
+                day,
+                part,
+                input,
+                spans,
+            });
+        }
+        "verify" => {
+            let mut day = None;
+            let mut answers = None;
+            let mut json = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--day" => {
+                        let value = args.next().ok_or(ArgsError::MissingValue("--day"))?;
+                        day = Some(parse_day(&value)?);
+                    }
+                    "--answers" => {
+                        answers = Some(args.next().ok_or(ArgsError::MissingValue("--answers"))?);
+                    }
+                    "--json" => {
+                        json = Some(args.next().ok_or(ArgsError::MissingValue("--json"))?);
+                    }
+                    _ => return Err(ArgsError::UnexpectedArgument(arg)),
+                }
+            }
+            return Ok(Command::Verify { day, answers, json });
+        }
+        "bench" => {
+            let mut options = BenchOptions::default();
+            while let Some(arg) = args.next() {
+                let mut value = |name| args.next().ok_or(ArgsError::MissingValue(name));
+                match arg.as_str() {
+                    "--day" => options.day = Some(parse_day(&value("--day")?)?),
+                    "--part" => options.part = Some(parse_part(&value("--part")?)?),
+                    "--warmup" => options.warmup = parse_number(&value("--warmup")?)?,
+                    "--samples" => options.samples = parse_number(&value("--samples")?)?,
+                    "--save" => options.save = Some(value("--save")?),
+                    "--baseline" => options.baseline = Some(value("--baseline")?),
+                    "--threshold" => options.threshold = parse_number(&value("--threshold")?)?,
+                    _ => return Err(ArgsError::UnexpectedArgument(arg)),
+                }
+            }
+            return Ok(Command::Bench(options));
+        }
+        _ => return Err(ArgsError::UnknownCommand(command)),
+    };
+    match args.next() {
+        Some(arg) => Err(ArgsError::UnexpectedArgument(arg)),
+        None => Ok(command),
+    }
+}
+
+fn parse_day(value: &str) -> Result<u8, ArgsError> {
+    match value.parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(ArgsError::InvalidDay(value.to_string())),
+    }
+}
+
+fn parse_part(value: &str) -> Result<u8, ArgsError> {
+    match value.parse() {
+        Ok(part @ 1..=2) => Ok(part),
+        _ => Err(ArgsError::InvalidPart(value.to_string())),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, ArgsError> {
+    value
+        .parse()
+        .map_err(|_| ArgsError::InvalidNumber(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse(args("run 16 --part 2 --input inputs/day16.txt --spans")),
+            Ok(Command::Run {
+                day: 16,
+                part: Some(2),
+                input: Some("inputs/day16.txt".to_string()),
+                spans: true,
+            })
+        );
+        assert_eq!(
+            parse(args("run 3")),
+            Ok(Command::Run {
+                day: 3,
+                part: None,
+                input: None,
+                spans: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(
+            parse(args("verify --json verify.json --day 7")),
+            Ok(Command::Verify {
+                day: Some(7),
+                answers: None,
+                json: Some("verify.json".to_string()),
+            })
+        );
+        assert_eq!(
+            parse(args("verify --answers my_answers.txt")),
+            Ok(Command::Verify {
+                day: None,
+                answers: Some("my_answers.txt".to_string()),
+                json: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse(args("bench")),
+            Ok(Command::Bench(BenchOptions::default()))
+        );
+        assert_eq!(
+            parse(args(
+                "bench --day 1 --samples 50 --baseline base.json --threshold 5.5"
+            )),
+            Ok(Command::Bench(BenchOptions {
+                day: Some(1),
+                samples: 50,
+                baseline: Some("base.json".to_string()),
+                threshold: 5.5,
+                ..BenchOptions::default()
+            }))
+        );
+        assert_eq!(
+            parse(args("bench --samples lots")),
+            Err(ArgsError::InvalidNumber("lots".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse(args("")), Err(ArgsError::MissingCommand));
+        assert_eq!(
+            parse(args("run 26")),
+            Err(ArgsError::InvalidDay("26".to_string()))
+        );
+        assert_eq!(
+            parse(args("run 1 --part 3")),
+            Err(ArgsError::InvalidPart("3".to_string()))
+        );
+        assert_eq!(
+            parse(args("run 1 --input")),
+            Err(ArgsError::MissingValue("--input"))
+        );
+        assert_eq!(
+            parse(args("list all")),
+            Err(ArgsError::UnexpectedArgument("all".to_string()))
+        );
+    }
+}
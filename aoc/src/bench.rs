@@ -0,0 +1,36 @@
+use aoc_common::{
+    bench::{Bench, BenchReport},
+    input::{self, Source},
+};
+
+use crate::{args::BenchOptions, puzzles::Puzzle};
+
+/// Benchmarks each available part, parsing included, on the day's default input.
+pub fn bench<'a>(
+    puzzles: impl IntoIterator<Item = &'a Puzzle>,
+    options: &BenchOptions,
+) -> BenchReport {
+    let bench = Bench::new().warmup(options.warmup).samples(options.samples);
+    let mut report = BenchReport::default();
+    for puzzle in puzzles {
+        let input = match input::load(puzzle.day, &Source::Default, puzzle.embedded_input) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping day {:02}: {e}", puzzle.day);
+                continue;
+            }
+        };
+        for part in [1, 2] {
+            if options.part.is_some_and(|p| p != part) {
+                continue;
+            }
+            if let Some(solver) = puzzle.part(part) {
+                let name = format!("day {:02} part {part}", puzzle.day);
+                report
+                    .results
+                    .push(bench.run(&name, || solver(&input.text)));
+            }
+        }
+    }
+    report
+}
@@ -0,0 +1,183 @@
+use std::{process::ExitCode, time::Duration};
+
+use aoc_common::{
+    input::{self, Source},
+    span,
+};
+use stephen_morris_utils::timer::time;
+
+mod answers;
+mod args;
+mod bench;
+mod puzzles;
+mod verify;
+
+use answers::Answers;
+use aoc_common::bench::BenchReport;
+use args::Command;
+use puzzles::{PUZZLES, Puzzle};
+
+fn main() -> ExitCode {
+    let command = match args::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", args::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match command {
+        Command::Help => println!("{}", args::USAGE),
+        Command::List => list(),
+        Command::RunAll => {
+            for puzzle in &PUZZLES {
+                if let Some(input) = load_input(puzzle, &Source::Default) {
+                    run_parts(puzzle, None, &input.text);
+                }
+            }
+        }
+        Command::Run {
+            day,
+            part,
+            input,
+            spans,
+        } => {
+            let puzzle = puzzles::find(day).unwrap();
+            let source = input.as_deref().map_or(Source::Default, Source::from);
+            let Some(input) = load_input(puzzle, &source) else {
+                return ExitCode::FAILURE;
+            };
+            if spans {
+                span::enable();
+            }
+            run_parts(puzzle, part, &input.text);
+        }
+        Command::Verify { day, answers, json } => {
+            let path = answers.as_deref().unwrap_or(answers::DEFAULT_PATH);
+            let answers = match std::fs::read_to_string(path) {
+                Ok(text) => match Answers::parse(&text) {
+                    Ok(answers) => answers,
+                    Err(e) => {
+                        eprintln!("error: {path}: {e}");
+                        return ExitCode::FAILURE;
+                    }
+                },
+                Err(e) => {
+                    eprintln!("error: could not read {path}: {e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let puzzles = PUZZLES
+                .iter()
+                .filter(|puzzle| day.is_none_or(|day| puzzle.day == day));
+            // solvers may print their own output, so the JSON report goes to a file
+            let report = verify::verify(puzzles, &answers);
+            report.print();
+            if let Some(path) = json
+                && let Err(e) = std::fs::write(&path, report.to_json())
+            {
+                eprintln!("error: could not write {path}: {e}");
+                return ExitCode::FAILURE;
+            }
+            if !report.passed() {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Bench(options) => {
+            let baseline = match &options.baseline {
+                Some(path) => match read_baseline(path) {
+                    Ok(baseline) => Some(baseline),
+                    Err(e) => {
+                        eprintln!("error: {path}: {e}");
+                        return ExitCode::FAILURE;
+                    }
+                },
+                None => None,
+            };
+            let puzzles = PUZZLES
+                .iter()
+                .filter(|puzzle| options.day.is_none_or(|day| puzzle.day == day));
+            let report = bench::bench(puzzles, &options);
+            report.print();
+            if let Some(path) = &options.save
+                && let Err(e) = std::fs::write(path, report.to_json())
+            {
+                eprintln!("error: could not write {path}: {e}");
+                return ExitCode::FAILURE;
+            }
+            if let Some(baseline) = baseline {
+                let changes = report.diff(&baseline, options.threshold);
+                println!("\ncompared with baseline, threshold {}%", options.threshold);
+                for change in &changes {
+                    println!("{change}");
+                }
+                if changes.iter().any(|change| change.regression) {
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn list() {
+    for puzzle in &PUZZLES {
+        let parts = match (puzzle.part1.is_some(), puzzle.part2.is_some()) {
+            (true, true) => "parts 1, 2",
+            (true, false) => "part 1",
+            (false, true) => "part 2",
+            (false, false) => "no parts",
+        };
+        println!("day {:02}  {:24} {parts}", puzzle.day, puzzle.title);
+    }
+}
+
+fn load_input(puzzle: &Puzzle, source: &Source) -> Option<input::Input> {
+    match input::load(puzzle.day, source, puzzle.embedded_input) {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("error: {e}");
+            None
+        }
+    }
+}
+
+fn read_baseline(path: &str) -> Result<BenchReport, Box<dyn std::error::Error>> {
+    Ok(BenchReport::from_json(&std::fs::read_to_string(path)?)?)
+}
+
+/// Runs `f` under `timer::time`, returning its result and how long it took.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let timed = time(f, "solve");
+    (timed.result, timed.duration)
+}
+
+fn run_parts(puzzle: &Puzzle, part: Option<u8>, input: &str) {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    for part in parts {
+        match puzzle.part(part) {
+            Some(solver) => {
+                let answer = time(
+                    || {
+                        let _span = span::enter("solve");
+                        solver(input)
+                    },
+                    "solve",
+                );
+                match &*answer {
+                    Ok(answer) => println!("day {:02} part {part}: {answer}", puzzle.day),
+                    Err(e) => println!("day {:02} part {part}: parse error: {e}", puzzle.day),
+                }
+                answer.print_duration();
+                let spans = span::take();
+                if !spans.is_empty() {
+                    print!("{spans}");
+                }
+            }
+            None => println!("day {:02} part {part}: not available", puzzle.day),
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use aoc_common::{Result, Solution};
+
+pub type Solver = fn(&str) -> Result<String>;
+
+fn part1<S: Solution>(input: &str) -> Result<String> {
+    S::solve_part1(input).map(|answer| answer.to_string())
+}
+
+fn part2<S: Solution>(input: &str) -> Result<String> {
+    S::solve_part2(input).map(|answer| answer.to_string())
+}
+
+pub struct Puzzle {
+    pub day: u8,
+    pub title: &'static str,
+    pub embedded_input: Option<&'static str>,
+    pub part1: Option<Solver>,
+    pub part2: Option<Solver>,
+}
+
+impl Puzzle {
+    pub fn part(&self, part: u8) -> Option<Solver> {
+        match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        }
+    }
+}
+
+pub fn find(day: u8) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.day == day)
+}
+
+pub static PUZZLES: [Puzzle; 25] = [
+    Puzzle {
+        day: 1,
+        title: "Historian Hysteria",
+        embedded_input: day01::EMBEDDED_INPUT,
+        part1: Some(part1::<day01::Day01>),
+        part2: Some(part2::<day01::Day01>),
+    },
+    Puzzle {
+        day: 2,
+        title: "Red-Nosed Reports",
+        embedded_input: day02::EMBEDDED_INPUT,
+        part1: Some(part1::<day02::Day02>),
+        part2: Some(part2::<day02::Day02>),
+    },
+    Puzzle {
+        day: 3,
+        title: "Mull It Over",
+        embedded_input: day03::EMBEDDED_INPUT,
+        part1: Some(part1::<day03::Day03>),
+        part2: Some(part2::<day03::Day03>),
+    },
+    Puzzle {
+        day: 4,
+        title: "Ceres Search",
+        embedded_input: day04::EMBEDDED_INPUT,
+        part1: Some(part1::<day04::Day04>),
+        part2: Some(part2::<day04::Day04>),
+    },
+    Puzzle {
+        day: 5,
+        title: "Print Queue",
+        embedded_input: day05::EMBEDDED_INPUT,
+        part1: Some(part1::<day05::Day05>),
+        part2: Some(part2::<day05::Day05>),
+    },
+    Puzzle {
+        day: 6,
+        title: "Guard Gallivant",
+        embedded_input: day06::EMBEDDED_INPUT,
+        part1: Some(part1::<day06::Day06>),
+        part2: Some(part2::<day06::Day06>),
+    },
+    Puzzle {
+        day: 7,
+        title: "Bridge Repair",
+        embedded_input: day07::EMBEDDED_INPUT,
+        part1: Some(part1::<day07::Day07>),
+        part2: Some(part2::<day07::Day07>),
+    },
+    Puzzle {
+        day: 8,
+        title: "Resonant Collinearity",
+        embedded_input: day08::EMBEDDED_INPUT,
+        part1: Some(part1::<day08::Day08>),
+        part2: Some(part2::<day08::Day08>),
+    },
+    Puzzle {
+        day: 9,
+        title: "Disk Fragmenter",
+        embedded_input: day09::EMBEDDED_INPUT,
+        part1: Some(part1::<day09::Day09>),
+        part2: Some(part2::<day09::Day09>),
+    },
+    Puzzle {
+        day: 10,
+        title: "Hoof It",
+        embedded_input: day10::EMBEDDED_INPUT,
+        part1: Some(part1::<day10::Day10>),
+        part2: Some(part2::<day10::Day10>),
+    },
+    Puzzle {
+        day: 11,
+        title: "Plutonian Pebbles",
+        embedded_input: day11::EMBEDDED_INPUT,
+        part1: Some(part1::<day11::Day11>),
+        part2: Some(part2::<day11::Day11>),
+    },
+    Puzzle {
+        day: 12,
+        title: "Garden Groups",
+        embedded_input: day12::EMBEDDED_INPUT,
+        part1: Some(part1::<day12::Day12>),
+        part2: Some(part2::<day12::Day12>),
+    },
+    Puzzle {
+        day: 13,
+        title: "Claw Contraption",
+        embedded_input: day13::EMBEDDED_INPUT,
+        part1: Some(part1::<day13::Day13>),
+        part2: Some(part2::<day13::Day13>),
+    },
+    Puzzle {
+        day: 14,
+        title: "Restroom Redoubt",
+        embedded_input: day14::EMBEDDED_INPUT,
+        part1: Some(part1::<day14::Day14>),
+        part2: Some(part2::<day14::Day14>),
+    },
+    Puzzle {
+        day: 15,
+        title: "Warehouse Woes",
+        embedded_input: day15::EMBEDDED_INPUT,
+        part1: Some(part1::<day15::Day15>),
+        part2: Some(part2::<day15::Day15>),
+    },
+    Puzzle {
+        day: 16,
+        title: "Reindeer Maze",
+        embedded_input: day16::EMBEDDED_INPUT,
+        part1: Some(part1::<day16::Day16>),
+        part2: Some(part2::<day16::Day16>),
+    },
+    Puzzle {
+        day: 17,
+        title: "Chronospatial Computer",
+        embedded_input: day17::EMBEDDED_INPUT,
+        part1: Some(part1::<day17::Day17>),
+        part2: Some(part2::<day17::Day17>),
+    },
+    Puzzle {
+        day: 18,
+        title: "RAM Run",
+        embedded_input: day18::EMBEDDED_INPUT,
+        part1: Some(part1::<day18::Day18>),
+        part2: Some(part2::<day18::Day18>),
+    },
+    Puzzle {
+        day: 19,
+        title: "Linen Layout",
+        embedded_input: day19::EMBEDDED_INPUT,
+        part1: Some(part1::<day19::Day19>),
+        part2: Some(part2::<day19::Day19>),
+    },
+    Puzzle {
+        day: 20,
+        title: "Race Condition",
+        embedded_input: day20::EMBEDDED_INPUT,
+        part1: Some(part1::<day20::Day20>),
+        part2: Some(part2::<day20::Day20>),
+    },
+    Puzzle {
+        day: 21,
+        title: "Keypad Conundrum",
+        embedded_input: day21::EMBEDDED_INPUT,
+        part1: Some(part1::<day21::Day21>),
+        part2: Some(part2::<day21::Day21>),
+    },
+    Puzzle {
+        day: 22,
+        title: "Monkey Market",
+        embedded_input: day22::EMBEDDED_INPUT,
+        part1: Some(part1::<day22::Day22>),
+        part2: Some(part2::<day22::Day22>),
+    },
+    Puzzle {
+        day: 23,
+        title: "LAN Party",
+        embedded_input: day23::EMBEDDED_INPUT,
+        part1: Some(part1::<day23::Day23>),
+        part2: Some(part2::<day23::Day23>),
+    },
+    Puzzle {
+        day: 24,
+        title: "Crossed Wires",
+        embedded_input: day24::EMBEDDED_INPUT,
+        part1: Some(part1::<day24::Day24>),
+        part2: Some(part2::<day24::Day24>),
+    },
+    Puzzle {
+        day: 25,
+        title: "Code Chronicle",
+        embedded_input: day25::EMBEDDED_INPUT,
+        part1: Some(part1::<day25::Day25>),
+        part2: None,
+    },
+];
@@ -0,0 +1,225 @@
+use std::{panic, time::Duration};
+
+use aoc_common::input::{self, Source};
+use serde::Serialize;
+
+use crate::{
+    answers::Answers,
+    puzzles::{Puzzle, Solver},
+    timed,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase", tag = "status")]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    Missing,
+    Error { message: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<String>,
+    pub answer: Option<String>,
+    #[serde(flatten)]
+    pub status: Status,
+    #[serde(rename = "duration_ms", serialize_with = "as_millis")]
+    pub duration: Duration,
+}
+
+fn as_millis<S: serde::Serializer>(duration: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub results: Vec<PartResult>,
+}
+
+impl Report {
+    pub fn count(&self, f: impl Fn(&Status) -> bool) -> usize {
+        self.results.iter().filter(|r| f(&r.status)).count()
+    }
+
+    pub fn passed(&self) -> bool {
+        self.count(|s| matches!(s, Status::Fail { .. } | Status::Error { .. })) == 0
+    }
+
+    pub fn print(&self) {
+        for r in &self.results {
+            let status = match &r.status {
+                Status::Pass => "pass".to_string(),
+                Status::Fail { expected } => format!("FAIL expected {expected}"),
+                Status::Missing => "missing answer".to_string(),
+                Status::Error { message } => format!("ERROR {message}"),
+            };
+            println!(
+                "day {:02} part {} {:>12.3?}  {:20} {status}",
+                r.day,
+                r.part,
+                r.duration,
+                r.answer.as_deref().unwrap_or("-"),
+            );
+        }
+        let total: Duration = self.results.iter().map(|r| r.duration).sum();
+        println!(
+            "{} passed, {} failed, {} missing, {} errors in {total:.3?}",
+            self.count(|s| *s == Status::Pass),
+            self.count(|s| matches!(s, Status::Fail { .. })),
+            self.count(|s| *s == Status::Missing),
+            self.count(|s| matches!(s, Status::Error { .. })),
+        );
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Runs a solver, turning a parse error or a panic into an error message, so that one broken day
+/// doesn't stop the rest from being verified.
+fn solve_catching(solver: Solver, input: &str) -> Result<String, String> {
+    match panic::catch_unwind(|| solver(input)) {
+        Ok(answer) => answer.map_err(|e| e.to_string()),
+        Err(payload) => {
+            let message = match payload.downcast_ref::<&str>() {
+                Some(message) => message.to_string(),
+                None => payload
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+            Err(format!("panicked: {message}"))
+        }
+    }
+}
+
+pub fn verify<'a>(puzzles: impl IntoIterator<Item = &'a Puzzle>, answers: &Answers) -> Report {
+    let mut report = Report::default();
+    for puzzle in puzzles {
+        let parts = [1, 2]
+            .into_iter()
+            .filter(|&part| puzzle.part(part).is_some());
+        let input = match input::load(puzzle.day, &Source::Default, puzzle.embedded_input) {
+            Ok(input) => input,
+            Err(e) => {
+                for part in parts {
+                    report.results.push(PartResult {
+                        day: puzzle.day,
+                        part,
+                        input: None,
+                        answer: None,
+                        status: Status::Error {
+                            message: e.to_string(),
+                        },
+                        duration: Duration::ZERO,
+                    });
+                }
+                continue;
+            }
+        };
+        for part in parts {
+            let solver = puzzle.part(part).unwrap();
+            let (answer, duration) = timed(|| solve_catching(solver, &input.text));
+            let (answer, status) = match answer {
+                Ok(answer) => {
+                    let status = match answers.get(puzzle.day, part, &input.name) {
+                        Some(expected) if expected == answer => Status::Pass,
+                        Some(expected) => Status::Fail {
+                            expected: expected.to_string(),
+                        },
+                        None => Status::Missing,
+                    };
+                    (Some(answer), status)
+                }
+                Err(message) => (None, Status::Error { message }),
+            };
+            report.results.push(PartResult {
+                day: puzzle.day,
+                part,
+                input: Some(input.name.clone()),
+                answer,
+                status,
+                duration,
+            });
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles;
+
+    #[test]
+    #[cfg(feature = "embedded-input")]
+    fn test_verify() {
+        let answers = Answers::parse("25 1 embedded 3690\n25 2 embedded 1").unwrap();
+        let report = verify(puzzles::find(25), &answers);
+        // day 25 has no second part, so only part 1 is checked
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.results[0].status, Status::Pass);
+        assert!(report.passed());
+
+        let answers = Answers::parse("25 1 embedded 1").unwrap();
+        let report = verify(puzzles::find(25), &answers);
+        assert_eq!(
+            report.results[0].status,
+            Status::Fail {
+                expected: "1".to_string()
+            }
+        );
+        assert!(!report.passed());
+
+        let report = verify(puzzles::find(25), &Answers::default());
+        assert_eq!(report.results[0].status, Status::Missing);
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn test_verify_panic() {
+        let puzzle = Puzzle {
+            day: 99,
+            title: "Panics",
+            embedded_input: Some("1"),
+            part1: Some(|_| panic!("no answer")),
+            part2: Some(|input| Ok(input.to_string())),
+        };
+        let answers = Answers::parse("99 2 embedded 1").unwrap();
+        let report = verify([&puzzle], &answers);
+        assert_eq!(
+            report.results[0].status,
+            Status::Error {
+                message: "panicked: no answer".to_string()
+            }
+        );
+        assert_eq!(report.results[1].status, Status::Pass);
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn test_json() {
+        let report = Report {
+            results: vec![PartResult {
+                day: 1,
+                part: 2,
+                input: Some("embedded".to_string()),
+                answer: Some("5".to_string()),
+                status: Status::Fail {
+                    expected: "6".to_string(),
+                },
+                duration: Duration::from_millis(3),
+            }],
+        };
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        let result = &json["results"][0];
+        assert_eq!(result["status"], "fail");
+        assert_eq!(result["expected"], "6");
+        assert_eq!(result["answer"], "5");
+        assert_eq!(result["duration_ms"], 3.0);
+    }
+}
@@ -0,0 +1,256 @@
+use std::{collections::HashMap, fmt::Display, hint::black_box, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use stephen_morris_utils::timer::time;
+
+/// Runs a function repeatedly, discarding the warm-up runs, and summarises the timings of the rest.
+#[derive(Debug, Clone, Copy)]
+pub struct Bench {
+    warmup: usize,
+    samples: usize,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            samples: 20,
+        }
+    }
+}
+
+impl Bench {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    pub fn run<T>(&self, name: &str, mut f: impl FnMut() -> T) -> Stats {
+        for _ in 0..self.warmup {
+            black_box(f());
+        }
+        let samples = (0..self.samples)
+            .map(|_| {
+                let timed = time(&mut f, name);
+                timed.duration
+            })
+            .collect();
+        Stats::from_samples(name, samples)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub name: String,
+    pub samples: usize,
+    #[serde(with = "millis")]
+    pub min: Duration,
+    #[serde(with = "millis")]
+    pub median: Duration,
+    #[serde(with = "millis")]
+    pub p90: Duration,
+    #[serde(with = "millis")]
+    pub p99: Duration,
+    #[serde(with = "millis")]
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(name: &str, mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples for {name}");
+        samples.sort();
+        Self {
+            name: name.to_string(),
+            samples: samples.len(),
+            min: samples[0],
+            median: percentile(&samples, 50),
+            p90: percentile(&samples, 90),
+            p99: percentile(&samples, 99),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:30} median {:>10.3?}  p90 {:>10.3?}  p99 {:>10.3?}  min {:>10.3?}  max {:>10.3?}  ({} samples)",
+            self.name, self.median, self.p90, self.p99, self.min, self.max, self.samples
+        )
+    }
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub results: Vec<Stats>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub name: String,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub regression: bool,
+}
+
+impl Change {
+    /// The change in median time as a percentage of the baseline.
+    pub fn percent(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:30} {:>10.3?} -> {:>10.3?}  {:+7.1}%{}",
+            self.name,
+            self.baseline,
+            self.current,
+            self.percent(),
+            if self.regression { "  REGRESSION" } else { "" }
+        )
+    }
+}
+
+impl BenchReport {
+    pub fn print(&self) {
+        for stats in &self.results {
+            println!("{stats}");
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Compares medians against a baseline. A benchmark regresses if it is slower than the
+    /// baseline by more than `threshold` percent. Benchmarks missing from either side are skipped.
+    pub fn diff(&self, baseline: &BenchReport, threshold: f64) -> Vec<Change> {
+        let baseline: HashMap<&str, &Stats> = baseline
+            .results
+            .iter()
+            .map(|stats| (stats.name.as_str(), stats))
+            .collect();
+        self.results
+            .iter()
+            .filter_map(|stats| {
+                let base = baseline.get(stats.name.as_str())?;
+                let mut change = Change {
+                    name: stats.name.clone(),
+                    baseline: base.median,
+                    current: stats.median,
+                    regression: false,
+                };
+                change.regression = change.percent() > threshold;
+                Some(change)
+            })
+            .collect()
+    }
+}
+
+mod millis {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_f64(duration.as_secs_f64() * 1000.0)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        let millis = f64::deserialize(d)?;
+        Ok(Duration::from_secs_f64(millis / 1000.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = (1..=10).rev().map(ms).collect();
+        let stats = Stats::from_samples("test", samples);
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.p90, ms(9));
+        assert_eq!(stats.p99, ms(10));
+        assert_eq!(stats.max, ms(10));
+
+        let stats = Stats::from_samples("single", vec![ms(3)]);
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.p99, ms(3));
+    }
+
+    #[test]
+    fn test_run() {
+        let mut calls = 0;
+        let stats = Bench::new()
+            .warmup(2)
+            .samples(5)
+            .run("count", || calls += 1);
+        assert_eq!(calls, 7);
+        assert_eq!(stats.samples, 5);
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let report = BenchReport {
+            results: vec![Stats::from_samples("a", vec![ms(1), ms(2), ms(4)])],
+        };
+        assert_eq!(BenchReport::from_json(&report.to_json()).unwrap(), report);
+    }
+
+    #[test]
+    fn test_diff() {
+        let baseline = BenchReport {
+            results: vec![
+                Stats::from_samples("same", vec![ms(10)]),
+                Stats::from_samples("slower", vec![ms(10)]),
+                Stats::from_samples("removed", vec![ms(10)]),
+            ],
+        };
+        let current = BenchReport {
+            results: vec![
+                Stats::from_samples("same", vec![ms(11)]),
+                Stats::from_samples("slower", vec![ms(20)]),
+                Stats::from_samples("added", vec![ms(10)]),
+            ],
+        };
+        let changes = current.diff(&baseline, 20.0);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].name, "same");
+        assert!(!changes[0].regression);
+        assert_eq!(changes[1].name, "slower");
+        assert!(changes[1].regression);
+        assert!((changes[1].percent() - 100.0).abs() < 1e-9);
+    }
+}
@@ -1,21 +1,82 @@
-use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Rem, RemAssign};
+//! The grid shared by the puzzle crates: a row-major `Grid<T>` indexed by `Point`, with `Vector`
+//! offsets and compass `Direction`s.
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+use std::ops::{
+    Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+use crate::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Direction {
-    Right,
-    Down,
-    Left,
-    Up,
-    Wait,
+    North,
+    #[default]
+    East,
+    South,
+    West,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+pub const NORTH: Direction = Direction::North;
+pub const EAST: Direction = Direction::East;
+pub const SOUTH: Direction = Direction::South;
+pub const WEST: Direction = Direction::West;
+
+impl Direction {
+    pub fn right(self) -> Self {
+        Direction::from((self as usize + 1) % 4)
+    }
+    pub fn left(self) -> Self {
+        Direction::from((self as usize + 3) % 4)
+    }
+    pub fn reverse(self) -> Self {
+        Direction::from((self as usize + 2) % 4)
+    }
+    pub fn try_from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+impl From<usize> for Direction {
+    fn from(i: usize) -> Self {
+        match i {
+            0 => Direction::North,
+            1 => Direction::East,
+            2 => Direction::South,
+            _ => Direction::West,
+        }
+    }
+}
+
+impl From<Direction> for char {
+    fn from(d: Direction) -> char {
+        match d {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(d: Direction) -> Vector {
+        Vector::from_direction(d)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Vector {
     pub x: isize,
     pub y: isize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
 pub struct Point {
     pub x: usize,
     pub y: usize,
@@ -32,13 +93,16 @@ impl Vector {
         Self { x, y }
     }
 
+    pub fn abs(self) -> Self {
+        Vector::new(self.x.abs(), self.y.abs())
+    }
+
     pub fn from_direction(dir: Direction) -> Self {
         match dir {
-            Direction::Right => Vector::new(1, 0),
-            Direction::Down => Vector::new(0, 1),
-            Direction::Left => Vector::new(-1, 0),
-            Direction::Up => Vector::new(0, -1),
-            Direction::Wait => Vector::new(0, 0),
+            Direction::East => Vector::new(1, 0),
+            Direction::South => Vector::new(0, 1),
+            Direction::West => Vector::new(-1, 0),
+            Direction::North => Vector::new(0, -1),
         }
     }
 }
@@ -71,6 +135,28 @@ impl RemAssign<isize> for Vector {
     }
 }
 
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
 impl Mul<isize> for Vector {
     type Output = Vector;
 
@@ -98,6 +184,11 @@ impl Point {
         Self { x, y }
     }
 
+    pub fn manhattan(&self, other: &Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn sub(self, other: Point) -> Vector {
         Vector::new(
             self.x as isize - other.x as isize,
@@ -137,7 +228,7 @@ impl Add<Vector> for Point {
     }
 }
 
-impl std::ops::Sub for Point {
+impl Sub for Point {
     type Output = Vector;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -200,6 +291,18 @@ impl<T: Clone + Default + PartialEq> Grid<T> {
         None
     }
 
+    pub fn add_direction(&self, point: Point, direction: Direction) -> Option<Point> {
+        self.add_vector(point, Vector::from(direction))
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn iter(&self) -> GridIterRef<'_, T> {
+        self.into_iter()
+    }
+
     pub fn in_bounds(&self, point: Point) -> bool {
         point.x < self.width && point.y < self.height
     }
@@ -208,6 +311,11 @@ impl<T: Clone + Default + PartialEq> Grid<T> {
         (point + vector).filter(|p| self.in_bounds(*p))
     }
 
+    /// The point at `vector`, taken as an offset from the origin, if it lies inside the grid.
+    pub fn point_from_vector(&self, vector: Vector) -> Option<Point> {
+        (Point::new(0, 0) + vector).filter(|p| self.in_bounds(*p))
+    }
+
     pub fn add_points(&self, p1: Point, p2: Point) -> Option<Point> {
         let x = p1.x.checked_add(p2.x)?;
         let y = p1.y.checked_add(p2.y)?;
@@ -221,11 +329,10 @@ impl<T: Clone + Default + PartialEq> Grid<T> {
 
     pub fn test_bound_direction(&self, point: Point, direction: Direction) -> bool {
         match direction {
-            Direction::Right => point.x < self.width - 1,
-            Direction::Down => point.y < self.height - 1,
-            Direction::Left => point.x > 0,
-            Direction::Up => point.y > 0,
-            Direction::Wait => true,
+            Direction::East => point.x < self.width - 1,
+            Direction::South => point.y < self.height - 1,
+            Direction::West => point.x > 0,
+            Direction::North => point.y > 0,
         }
     }
 }
@@ -266,22 +373,51 @@ impl<T: Clone + Default + PartialEq> From<&[&[T]]> for Grid<T> {
 
 impl From<&str> for Grid<u8> {
     fn from(s: &str) -> Self {
-        let height = s.lines().count();
-        let width = s.lines().next().unwrap().len();
-        let mut data = Vec::with_capacity(width * height);
-        for line in s.lines() {
-            assert!(line.len() == width);
+        Self::parse(s, |_| true).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Grid<u8> {
+    /// Reads one row per line, ignoring blank lines at the end. Every row must be as wide as the
+    /// first, and hold only bytes which `allowed` accepts.
+    pub fn parse(s: &str, allowed: impl Fn(u8) -> bool) -> crate::Result<Self> {
+        let lines: Vec<&str> = s.trim_end().lines().collect();
+        let Some(first) = lines.first().filter(|line| !line.is_empty()) else {
+            return Err(ParseError::new("the grid is empty"));
+        };
+        let width = first.len();
+        let mut data = Vec::with_capacity(width * lines.len());
+        for (idx, line) in lines.iter().enumerate() {
+            let error = |message: String| ParseError::at_line(idx + 1, message);
+            if line.len() != width {
+                return Err(error(format!(
+                    "expected a row of {width} characters, found {}",
+                    line.len()
+                )));
+            }
+            if let Some(c) = line.chars().find(|&c| !c.is_ascii() || !allowed(c as u8)) {
+                return Err(error(format!("unexpected character {c:?}")));
+            }
             data.extend(line.bytes());
         }
-        Self {
+        Ok(Self {
             data,
             width,
-            height,
+            height: lines.len(),
+        })
+    }
+
+    /// The position of `tile`, which must appear exactly once. `what` names it in the error.
+    pub fn find_one(&self, tile: u8, what: &str) -> crate::Result<Point> {
+        let count = self.data.iter().filter(|&&cell| cell == tile).count();
+        match count {
+            1 => Ok(self.find(tile).unwrap()),
+            _ => Err(ParseError::new(format!(
+                "expected one {what}, found {count}"
+            ))),
         }
     }
-}
 
-impl Grid<u8> {
     pub fn print(&self) {
         for y in 0..self.height {
             for x in 0..self.width {
@@ -366,7 +502,26 @@ impl<'a, T: Clone + Default + PartialEq> IntoIterator for &'a Grid<T> {
     }
 }
 
-// Iterator implementations
+/// Offsets to the orthogonal neighbours, clockwise from north.
+pub const ORTHOGONAL: [Vector; 4] = [
+    Vector { x: 0, y: -1 },
+    Vector { x: 1, y: 0 },
+    Vector { x: 0, y: 1 },
+    Vector { x: -1, y: 0 },
+];
+
+/// Offsets to all eight neighbours, clockwise from north.
+pub const ALL_NEIGHBOURS: [Vector; 8] = [
+    Vector { x: 0, y: -1 },
+    Vector { x: 1, y: -1 },
+    Vector { x: 1, y: 0 },
+    Vector { x: 1, y: 1 },
+    Vector { x: 0, y: 1 },
+    Vector { x: -1, y: 1 },
+    Vector { x: -1, y: 0 },
+    Vector { x: -1, y: -1 },
+];
+
 pub struct OrthogonalNeighbors<'a, T: Clone + Default + PartialEq> {
     grid: &'a Grid<T>,
     center: Point,
@@ -383,13 +538,9 @@ impl<'a, T: Clone + Default + PartialEq> Iterator for OrthogonalNeighbors<'a, T>
     type Item = (Point, &'a T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-
-        while self.current_direction < DIRECTIONS.len() {
-            let (dx, dy) = DIRECTIONS[self.current_direction];
+        while let Some(&vector) = ORTHOGONAL.get(self.current_direction) {
             self.current_direction += 1;
-
-            if let Some(p) = self.grid.add_vector(self.center, Vector::new(dx, dy)) {
+            if let Some(p) = self.grid.add_vector(self.center, vector) {
                 return Some((p, &self.grid[p]));
             }
         }
@@ -401,22 +552,9 @@ impl<'a, T: Clone + Default + PartialEq> Iterator for DiagonalNeighbors<'a, T> {
     type Item = (Point, &'a T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        const DIRECTIONS: [(isize, isize); 8] = [
-            (0, -1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-            (0, 1),
-            (-1, 1),
-            (-1, 0),
-            (-1, -1),
-        ];
-
-        while self.current_direction < DIRECTIONS.len() {
-            let (dx, dy) = DIRECTIONS[self.current_direction];
+        while let Some(&vector) = ALL_NEIGHBOURS.get(self.current_direction) {
             self.current_direction += 1;
-
-            if let Some(p) = self.grid.add_vector(self.center, Vector::new(dx, dy)) {
+            if let Some(p) = self.grid.add_vector(self.center, vector) {
                 return Some((p, &self.grid[p]));
             }
         }
@@ -425,7 +563,7 @@ impl<'a, T: Clone + Default + PartialEq> Iterator for DiagonalNeighbors<'a, T> {
 }
 
 impl<T: Clone + Default + PartialEq> Grid<T> {
-    pub fn orthogonal_neighbors(&self, center: Point) -> OrthogonalNeighbors<T> {
+    pub fn orthogonal_neighbors(&self, center: Point) -> OrthogonalNeighbors<'_, T> {
         OrthogonalNeighbors {
             grid: self,
             center,
@@ -433,7 +571,7 @@ impl<T: Clone + Default + PartialEq> Grid<T> {
         }
     }
 
-    pub fn all_neighbors(&self, center: Point) -> DiagonalNeighbors<T> {
+    pub fn all_neighbors(&self, center: Point) -> DiagonalNeighbors<'_, T> {
         DiagonalNeighbors {
             grid: self,
             center,
@@ -456,6 +594,12 @@ impl<T: Clone + Default + PartialEq> IndexMut<Point> for Grid<T> {
     }
 }
 
+impl From<&[u8]> for Grid<u8> {
+    fn from(s: &[u8]) -> Self {
+        Grid::from(std::str::from_utf8(s).unwrap())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -544,21 +688,26 @@ mod tests {
     }
 
     #[test]
-    fn test_vector_boundaries() {
-        let v1 = Vector::new(0, 0);
+    fn test_vector_arithmetic() {
+        let v1 = Vector::new(3, -2);
+        let v2 = Vector::new(-1, 4);
 
-        assert_eq!(v1 + v1, Vector::new(0, 0));
-        assert_eq!(Vector::from_direction(Direction::Wait), v1);
+        assert_eq!(v1 + v2, Vector::new(2, 2));
+        assert_eq!(v1 - v2, Vector::new(4, -6));
+        assert_eq!(-v1, Vector::new(-3, 2));
+        assert_eq!(v1 * 2, Vector::new(6, -4));
+        assert_eq!(v1 % 2, Vector::new(1, 0));
+        assert_eq!(Point::new(1, 5) - Point::new(3, 2), Vector::new(-2, 3));
+        assert_eq!(Point::new(1, 5).manhattan(&Point::new(3, 2)), 5);
     }
 
     #[test]
     fn test_all_directions() {
         let directions = [
-            (Direction::Right, (1, 0)),
-            (Direction::Down, (0, 1)),
-            (Direction::Left, (-1, 0)),
-            (Direction::Up, (0, -1)),
-            (Direction::Wait, (0, 0)),
+            (Direction::East, (1, 0)),
+            (Direction::South, (0, 1)),
+            (Direction::West, (-1, 0)),
+            (Direction::North, (0, -1)),
         ];
 
         for (dir, (x, y)) in directions {
@@ -568,6 +717,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rotation() {
+        assert_eq!(NORTH.right(), EAST);
+        assert_eq!(NORTH.left(), WEST);
+        assert_eq!(WEST.right(), NORTH);
+        assert_eq!(SOUTH.reverse(), NORTH);
+        assert_eq!(EAST.right().right(), EAST.reverse());
+        for c in ['^', '>', 'v', '<'] {
+            assert_eq!(char::from(Direction::try_from_char(c).unwrap()), c);
+        }
+        assert_eq!(Direction::try_from_char('.'), None);
+    }
+
+    #[test]
+    fn test_add_direction() {
+        let grid = Grid::new(3, 3, 0u8);
+        assert_eq!(
+            grid.add_direction(Point::new(1, 1), NORTH),
+            Some(Point::new(1, 0))
+        );
+        assert_eq!(grid.add_direction(Point::new(1, 0), NORTH), None);
+        assert_eq!(grid.add_direction(Point::new(2, 1), EAST), None);
+    }
+
+    #[test]
+    fn test_point_from_vector() {
+        let grid = Grid::new(3, 2, 0u8);
+        assert_eq!(
+            grid.point_from_vector(Vector::new(2, 1)),
+            Some(Point::new(2, 1))
+        );
+        assert_eq!(grid.point_from_vector(Vector::new(-1, 0)), None);
+        assert_eq!(grid.point_from_vector(Vector::new(0, 2)), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let grid = Grid::from("ab\ncd\n");
+        assert_eq!(grid.width, 2);
+        assert_eq!(grid.height, 2);
+        assert_eq!(grid.as_slice(), b"abcd");
+        assert_eq!(grid[Point::new(0, 1)], b'c');
+        assert_eq!(grid.find(b'd'), Some(Point::new(1, 1)));
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("ab\r\ncd\n\n", |_| true).unwrap();
+        assert_eq!(grid.as_slice(), b"abcd");
+        let digits = |byte: u8| byte.is_ascii_digit();
+        assert_eq!(
+            Grid::parse("12\n3\n", digits),
+            Err(ParseError::at_line(
+                2,
+                "expected a row of 2 characters, found 1"
+            ))
+        );
+        assert_eq!(
+            Grid::parse("12\n3x\n", digits),
+            Err(ParseError::at_line(2, "unexpected character 'x'"))
+        );
+        assert_eq!(
+            Grid::parse("\n", digits),
+            Err(ParseError::new("the grid is empty"))
+        );
+        let grid = Grid::parse("S.\n.S", |_| true).unwrap();
+        assert_eq!(
+            grid.find_one(b'S', "start S").unwrap_err().to_string(),
+            "expected one start S, found 2"
+        );
+        assert_eq!(
+            grid.find_one(b'.', "dot"),
+            Err(ParseError::new("expected one dot, found 2"))
+        );
+        let grid = Grid::parse("S.\n..", |_| true).unwrap();
+        assert_eq!(grid.find_one(b'S', "start S"), Ok(Point::new(0, 0)));
+    }
+
     // Iterator tests
     #[test]
     fn test_empty_grid_iteration() {
@@ -600,10 +827,10 @@ mod tests {
         let mut grid = Grid::new(3, 3, 0u8);
         let center = Point::new(1, 1);
         let directions = [
-            Direction::Up,
-            Direction::Right,
-            Direction::Down,
-            Direction::Left,
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
         ];
 
         // Set center and mark neighbors
@@ -648,10 +875,10 @@ mod tests {
         // Find value and transform surrounding area
         if let Some(center) = grid.find(5) {
             let directions = [
-                Direction::Up,
-                Direction::Right,
-                Direction::Down,
-                Direction::Left,
+                Direction::North,
+                Direction::East,
+                Direction::South,
+                Direction::West,
             ];
             for dir in directions {
                 if let Some(neighbor) = grid.add_vector(center, Vector::from_direction(dir)) {
@@ -0,0 +1,173 @@
+use std::{
+    fmt::Display,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Overrides the directory searched for `dayNN.txt` files.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS";
+const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+#[derive(Debug)]
+pub enum InputError {
+    Read(PathBuf, std::io::Error),
+    Stdin(std::io::Error),
+    NotFound(u8, PathBuf),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Read(path, e) => write!(f, "could not read {}: {e}", path.display()),
+            InputError::Stdin(e) => write!(f, "could not read stdin: {e}"),
+            InputError::NotFound(day, path) => write!(
+                f,
+                "no input for day {day}: {} does not exist and no input was compiled in",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    /// `dayNN.txt` in the inputs directory, falling back to the embedded input.
+    Default,
+}
+
+impl From<&str> for Source {
+    fn from(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::Path(PathBuf::from(path)),
+        }
+    }
+}
+
+pub fn inputs_dir() -> PathBuf {
+    std::env::var_os(INPUTS_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR))
+}
+
+pub fn day_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day:02}.txt"))
+}
+
+/// The name of the embedded input, as used in place of a path.
+pub const EMBEDDED: &str = "embedded";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    /// Where the input came from: a path, `stdin` or `embedded`.
+    pub name: String,
+    pub text: String,
+}
+
+pub fn load(day: u8, source: &Source, embedded: Option<&str>) -> Result<Input, InputError> {
+    match source {
+        Source::Path(path) => read_file(path),
+        Source::Stdin => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(InputError::Stdin)?;
+            Ok(Input {
+                name: "stdin".to_string(),
+                text,
+            })
+        }
+        Source::Default => {
+            let path = day_path(&inputs_dir(), day);
+            if path.exists() {
+                read_file(&path)
+            } else {
+                let text = embedded.ok_or(InputError::NotFound(day, path))?;
+                Ok(Input {
+                    name: EMBEDDED.to_string(),
+                    text: text.to_string(),
+                })
+            }
+        }
+    }
+}
+
+/// Loads the input for a day's own binary, taking an optional path (or `-` for stdin) as the
+/// first argument which is not a `--flag`. Exits with a message if no input can be found.
+pub fn load_from_args(day: u8, embedded: Option<&str>) -> String {
+    let source = std::env::args()
+        .skip(1)
+        .find(|arg| !arg.starts_with("--"))
+        .map_or(Source::Default, |arg| Source::from(arg.as_str()));
+    match load(day, &source, embedded) {
+        Ok(input) => input.text,
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Unwraps the parsed input for a day's own binary. Exits with the error if parsing failed.
+pub fn or_exit<T, E: Display>(parsed: Result<T, E>) -> T {
+    match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<Input, InputError> {
+    let text =
+        std::fs::read_to_string(path).map_err(|e| InputError::Read(path.to_path_buf(), e))?;
+    Ok(Input {
+        name: path.display().to_string(),
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_from_arg() {
+        assert_eq!(Source::from("-"), Source::Stdin);
+        assert_eq!(
+            Source::from("inputs/day01.txt"),
+            Source::Path(PathBuf::from("inputs/day01.txt"))
+        );
+    }
+
+    #[test]
+    fn test_day_path() {
+        assert_eq!(
+            day_path(Path::new("inputs"), 7),
+            PathBuf::from("inputs/day07.txt")
+        );
+    }
+
+    #[test]
+    fn test_load_fallback() {
+        // day 0 never has a file in the inputs directory
+        let input = load(0, &Source::Default, Some("1 2")).unwrap();
+        assert_eq!(input.text, "1 2");
+        assert_eq!(input.name, EMBEDDED);
+        assert!(matches!(
+            load(0, &Source::Default, None),
+            Err(InputError::NotFound(0, _))
+        ));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let source = Source::Path(PathBuf::from("does/not/exist.txt"));
+        assert!(matches!(load(1, &source, None), Err(InputError::Read(..))));
+    }
+}
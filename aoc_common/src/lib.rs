@@ -0,0 +1,10 @@
+pub mod bench;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod rng;
+pub mod search;
+pub mod solution;
+pub mod span;
+
+pub use solution::{ParseError, Result, Solution, Unsolved};
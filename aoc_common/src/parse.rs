@@ -0,0 +1,88 @@
+//! Helpers for the days' parsers. Errors about a single line are built as plain messages and
+//! given their line number by `lines`.
+
+use std::str::FromStr;
+
+use crate::{ParseError, Result};
+
+/// A message for an error within a line, before its line number is known.
+pub type LineResult<T> = std::result::Result<T, String>;
+
+/// Parses a word as a number, naming the word if it isn't one.
+pub fn number<T: FromStr>(word: &str) -> LineResult<T> {
+    word.parse().map_err(|_| format!("invalid number '{word}'"))
+}
+
+/// Parses the numbers on a line which are separated by `separator` or by whitespace.
+pub fn numbers<T: FromStr>(line: &str, separator: char) -> LineResult<Vec<T>> {
+    line.split(|c: char| c == separator || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(number)
+        .collect()
+}
+
+/// Parses every line which isn't blank, giving an error the number of its line.
+pub fn lines<T>(input: &str, mut parse_line: impl FnMut(&str) -> LineResult<T>) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            parse_line(line).map_err(|message| ParseError::at_line(idx + 1, message))
+        })
+        .collect()
+}
+
+/// Splits the input at its first blank line, returning both sections and the number of lines
+/// before the second.
+pub fn sections(input: &str) -> Result<(&str, &str, usize)> {
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            let first_lines = input[..offset].lines().count();
+            return Ok((
+                &input[..offset],
+                &input[offset + line.len()..],
+                first_lines + 1,
+            ));
+        }
+        offset += line.len();
+    }
+    Err(ParseError::new(
+        "expected a blank line between the two sections",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(number::<u8>("12"), Ok(12));
+        assert_eq!(number::<u8>("256"), Err("invalid number '256'".to_string()));
+        assert_eq!(numbers::<i32>(" 1  -2\t3", ','), Ok(vec![1, -2, 3]));
+        assert_eq!(numbers::<i32>("1,2, 3", ','), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            numbers::<i32>("1,x", ','),
+            Err("invalid number 'x'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let parsed = lines("1\n\n  \n2\r\n", number::<u8>).unwrap();
+        assert_eq!(parsed, [1, 2]);
+        assert_eq!(
+            lines("1\n\nx", number::<u8>),
+            Err(ParseError::at_line(3, "invalid number 'x'"))
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(sections("a\nb\n\nc\n"), Ok(("a\nb\n", "c\n", 3)));
+        assert_eq!(sections("a\r\n \r\nc"), Ok(("a\r\n", "c", 2)));
+        assert!(sections("a\nb").is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! A small seeded random number generator, for tests and suites which need the same values on
+//! every run without a dependency.
+
+/// splitmix64, which mixes well from any seed, zero included.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value below `bound`, which must not be zero. The modulo bias is too small to matter here.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let values: Vec<u64> = {
+            let mut rng = Rng::new(0);
+            (0..100).map(|_| rng.next_u64()).collect()
+        };
+        let mut rng = Rng::new(0);
+        assert!((0..100).all(|idx| rng.next_u64() == values[idx]));
+        assert!(values.iter().all(|&value| value != 0));
+        assert_ne!(Rng::new(1).next_u64(), values[0]);
+
+        let mut rng = Rng::new(7);
+        let dice: Vec<u64> = (0..600).map(|_| rng.below(6)).collect();
+        assert!(dice.iter().all(|&value| value < 6));
+        assert!((0..6).all(|face| dice.contains(&face)));
+    }
+}
@@ -0,0 +1,499 @@
+//! Breadth-first, Dijkstra and A* search over any state space.
+//!
+//! A search starts from one state and asks a successor function for the states reachable from
+//! each state it settles, until the goal predicate accepts a state or the space is exhausted.
+//! Pass `|_| false` as the goal to explore everything reachable. Costs start at `C::default()`.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::grid::{Grid, Point};
+
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    /// The cost of the cheapest path from the start to each settled state.
+    pub distances: HashMap<S, C>,
+    /// The state before each settled state on one of its cheapest paths. The start has none.
+    pub predecessors: HashMap<S, S>,
+    /// The first state the goal predicate accepted, if any.
+    pub goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn cost(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    /// A cheapest path from the start to `state`, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        let mut current = state;
+        while let Some(previous) = self.predecessors.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+}
+
+/// Search where every step costs one.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search {
+        distances: HashMap::from([(start.clone(), 0)]),
+        predecessors: HashMap::new(),
+        goal: None,
+    };
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, distance)) = queue.pop_front() {
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        for next in successors(&state) {
+            if !search.distances.contains_key(&next) {
+                search.distances.insert(next.clone(), distance + 1);
+                search.predecessors.insert(next.clone(), state.clone());
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Search where each successor comes with the non-negative cost of the step to it.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Dijkstra guided by a heuristic, which must never overestimate the remaining cost to the goal
+/// and must not drop by more than the cost of any step, or the costs found may not be the lowest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search {
+        distances: HashMap::from([(start.clone(), C::default())]),
+        predecessors: HashMap::new(),
+        goal: None,
+    };
+    let mut settled = HashSet::new();
+    let mut heap = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        cost: C::default(),
+        state: start,
+    }]);
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        if !settled.insert(state.clone()) {
+            continue;
+        }
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if settled.contains(&next)
+                || search
+                    .distances
+                    .get(&next)
+                    .is_some_and(|&known| known <= next_cost)
+            {
+                continue;
+            }
+            search.distances.insert(next.clone(), next_cost);
+            search.predecessors.insert(next.clone(), state.clone());
+            heap.push(Entry {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+    // states still waiting in the heap only have provisional costs
+    if !heap.is_empty() {
+        search.distances.retain(|state, _| settled.contains(state));
+        search
+            .predecessors
+            .retain(|state, _| settled.contains(state));
+    }
+    search
+}
+
+/// Every cheapest path from the start to the cheapest goals, as a DAG of predecessors.
+#[derive(Debug, Clone)]
+pub struct AllPaths<S, C> {
+    /// The cost of the cheapest path from the start to each settled state.
+    pub distances: HashMap<S, C>,
+    /// Every state before each settled state on one of its cheapest paths. The start has none.
+    pub predecessors: HashMap<S, Vec<S>>,
+    /// The goal states reached at the lowest cost.
+    pub goals: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> AllPaths<S, C> {
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn cost(&self) -> Option<C> {
+        self.distance(self.goals.first()?)
+    }
+
+    /// Every cheapest path from the start to a goal, both included. There can be exponentially
+    /// many; `states_on_paths` is cheaper when only the states matter.
+    pub fn paths(&self) -> Vec<Vec<S>> {
+        fn walk<S: Clone + Eq + Hash>(
+            predecessors: &HashMap<S, Vec<S>>,
+            suffix: &mut Vec<S>,
+            paths: &mut Vec<Vec<S>>,
+        ) {
+            let state = suffix.last().unwrap();
+            match predecessors.get(state) {
+                Some(previous) if !previous.is_empty() => {
+                    for p in previous {
+                        suffix.push(p.clone());
+                        walk(predecessors, suffix, paths);
+                        suffix.pop();
+                    }
+                }
+                _ => paths.push(suffix.iter().rev().cloned().collect()),
+            }
+        }
+        let mut paths = Vec::new();
+        for goal in &self.goals {
+            walk(&self.predecessors, &mut vec![goal.clone()], &mut paths);
+        }
+        paths
+    }
+
+    /// The states on at least one cheapest path to a goal.
+    pub fn states_on_paths(&self) -> HashSet<S> {
+        let mut states: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors.get(&state).into_iter().flatten() {
+                if states.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+        states
+    }
+}
+
+/// Dijkstra which keeps every predecessor on a cheapest path, and every goal reached at the
+/// lowest cost, rather than the first of each. With zero-cost moves a predecessor is only kept if
+/// it was settled first, so the cheapest paths never loop.
+pub fn dijkstra_all<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> AllPaths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut all = AllPaths {
+        distances: HashMap::from([(start.clone(), C::default())]),
+        predecessors: HashMap::new(),
+        goals: Vec::new(),
+    };
+    let mut settled = HashSet::new();
+    let mut heap = BinaryHeap::from([Entry {
+        priority: C::default(),
+        cost: C::default(),
+        state: start,
+    }]);
+    let mut goal_cost = None;
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        if !settled.insert(state.clone()) {
+            continue;
+        }
+        if is_goal(&state) {
+            goal_cost = Some(cost);
+            all.goals.push(state);
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            match all.distances.get(&next) {
+                Some(&known) if known < next_cost => {}
+                Some(&known) if known == next_cost => {
+                    if settled.contains(&next) {
+                        continue;
+                    }
+                    all.predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                _ => {
+                    all.distances.insert(next.clone(), next_cost);
+                    all.predecessors.insert(next.clone(), vec![state.clone()]);
+                    heap.push(Entry {
+                        priority: next_cost,
+                        cost: next_cost,
+                        state: next,
+                    });
+                }
+            }
+        }
+    }
+    if !heap.is_empty() {
+        all.distances.retain(|state, _| settled.contains(state));
+        all.predecessors.retain(|state, _| settled.contains(state));
+    }
+    all
+}
+
+/// Breadth-first search between orthogonally adjacent cells whose values are `passable`.
+/// The start cell is always entered, whatever its value.
+pub fn grid_bfs<T: Clone + Default + PartialEq>(
+    grid: &Grid<T>,
+    start: Point,
+    passable: impl Fn(&T) -> bool,
+    is_goal: impl FnMut(&Point) -> bool,
+) -> Search<Point, usize> {
+    bfs(
+        start,
+        |&point| {
+            grid.orthogonal_neighbors(point)
+                .filter(|(_, value)| passable(value))
+                .map(|(next, _)| next)
+                .collect::<Vec<_>>()
+        },
+        is_goal,
+    )
+}
+
+// a min-heap entry, ordered by priority alone so that states need not be Ord
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+S..#
+.#.#
+.#..
+...E";
+
+    #[test]
+    fn test_grid_bfs() {
+        let grid = Grid::from(MAZE);
+        let start = grid.find(b'S').unwrap();
+        let end = grid.find(b'E').unwrap();
+        let search = grid_bfs(&grid, start, |&c| c != b'#', |&p| p == end);
+        assert_eq!(search.goal, Some(end));
+        assert_eq!(search.cost(), Some(6));
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path[0], start);
+        assert_eq!(path[6], end);
+        assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+        assert!(path.iter().all(|&p| grid[p] != b'#'));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let grid = Grid::from("S#E");
+        let search = grid_bfs(&grid, Point::new(0, 0), |&c| c != b'#', |&p| p.x == 2);
+        assert_eq!(search.goal, None);
+        assert_eq!(search.cost(), None);
+        assert_eq!(search.path(), None);
+        assert_eq!(search.distances.len(), 1);
+        assert_eq!(
+            search.path_to(&Point::new(0, 0)),
+            Some(vec![Point::new(0, 0)])
+        );
+    }
+
+    #[test]
+    fn test_explore_all() {
+        let grid = Grid::from(MAZE);
+        let search = grid_bfs(&grid, Point::new(0, 0), |&c| c != b'#', |_| false);
+        assert_eq!(search.distances.len(), 12);
+        assert_eq!(search.distance(&Point::new(3, 2)), Some(5));
+        assert_eq!(search.distance(&Point::new(3, 0)), None);
+    }
+
+    // walking from 0 to 10 in steps of +1 (cost 1) or +3 (cost 2)
+    fn steps(&n: &u32) -> Vec<(u32, u32)> {
+        [(n + 1, 1), (n + 3, 2)]
+            .into_iter()
+            .filter(|&(next, _)| next <= 10)
+            .collect()
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra(0, steps, |&n| n == 10);
+        assert_eq!(search.cost(), Some(7));
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!((path[0], path[4]), (0, 10));
+
+        let search = dijkstra(0, steps, |_| false);
+        assert_eq!(search.distances.len(), 11);
+        assert_eq!(search.distance(&9), Some(6));
+    }
+
+    #[test]
+    fn test_dijkstra_stops_early() {
+        let search = dijkstra(0, steps, |&n| n == 3);
+        assert_eq!(search.cost(), Some(2));
+        // every distance kept is final
+        let full = dijkstra(0, steps, |_| false);
+        for (state, distance) in &search.distances {
+            assert_eq!(full.distance(state), Some(*distance));
+        }
+    }
+
+    #[test]
+    fn test_astar() {
+        let grid = Grid::from(MAZE);
+        let start = grid.find(b'S').unwrap();
+        let end = grid.find(b'E').unwrap();
+        let search = astar(
+            start,
+            |&p| {
+                grid.orthogonal_neighbors(p)
+                    .filter(|&(_, &c)| c != b'#')
+                    .map(|(next, _)| (next, 1))
+                    .collect::<Vec<_>>()
+            },
+            |p| p.manhattan(&end),
+            |&p| p == end,
+        );
+        assert_eq!(search.cost(), Some(6));
+        assert_eq!(search.path().unwrap().len(), 7);
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        let all = dijkstra_all(0, steps, |&n| n == 10);
+        assert_eq!(all.cost(), Some(7));
+        assert_eq!(all.goals, vec![10]);
+        let mut paths = all.paths();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                vec![0, 1, 4, 7, 10],
+                vec![0, 3, 4, 7, 10],
+                vec![0, 3, 6, 7, 10],
+                vec![0, 3, 6, 9, 10],
+            ]
+        );
+        let mut states: Vec<u32> = all.states_on_paths().into_iter().collect();
+        states.sort();
+        assert_eq!(states, vec![0, 1, 3, 4, 6, 7, 9, 10]);
+    }
+
+    #[test]
+    fn test_dijkstra_all_goals() {
+        // opposite corners, each two steps from the centre by two routes
+        let grid = Grid::new(3, 3, b'.');
+        let all = dijkstra_all(
+            Point::new(1, 1),
+            |&p| {
+                grid.orthogonal_neighbors(p)
+                    .map(|(next, _)| (next, 1))
+                    .collect::<Vec<_>>()
+            },
+            |&p| p == Point::new(0, 0) || p == Point::new(2, 2),
+        );
+        assert_eq!(all.cost(), Some(2));
+        assert_eq!(all.goals.len(), 2);
+        assert_eq!(all.paths().len(), 4);
+        assert_eq!(all.states_on_paths().len(), 7);
+    }
+
+    #[test]
+    fn test_dijkstra_all_zero_cost() {
+        // 0 and 1 are joined both ways for free, and both step to the goal 2
+        let all = dijkstra_all(
+            0,
+            |&n: &u32| match n {
+                0 => vec![(1, 0), (2, 1)],
+                1 => vec![(0, 0), (1, 0), (2, 1)],
+                _ => vec![],
+            },
+            |&n| n == 2,
+        );
+        assert_eq!(all.cost(), Some(1));
+        let mut paths = all.paths();
+        paths.sort();
+        assert_eq!(paths, vec![vec![0, 1, 2], vec![0, 2]]);
+        assert_eq!(all.states_on_paths().len(), 3);
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::Display;
+
+use crate::span;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+
+    /// The same error in a block which starts after `lines` lines of the whole input.
+    pub fn offset(self, lines: usize) -> Self {
+        Self {
+            line: self.line.map(|line| line + lines),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// A day's puzzle. The input is parsed once and both parts are solved from the parsed value,
+/// so parts which need to mutate their state work on a clone. A part fails with the same error
+/// as the parser when a well-formed input has no answer.
+pub trait Solution {
+    type Parsed;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1>;
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2>;
+
+    fn solve_part1(input: &str) -> Result<Self::Part1> {
+        let parsed = {
+            let _span = span::enter("parse");
+            Self::parse(input)?
+        };
+        let _span = span::enter("part 1");
+        Self::part1(&parsed)
+    }
+
+    fn solve_part2(input: &str) -> Result<Self::Part2> {
+        let parsed = {
+            let _span = span::enter("parse");
+            Self::parse(input)?
+        };
+        let _span = span::enter("part 2");
+        Self::part2(&parsed)
+    }
+}
+
+/// The answer for a part which has no solver, e.g. day 25 has no second part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not available")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_display() {
+        assert_eq!(ParseError::new("empty input").to_string(), "empty input");
+        assert_eq!(
+            ParseError::at_line(3, "expected a number").to_string(),
+            "line 3: expected a number"
+        );
+        assert_eq!(
+            ParseError::at_line(3, "expected a number").offset(4),
+            ParseError::at_line(7, "expected a number")
+        );
+        assert_eq!(ParseError::new("empty").offset(4).line, None);
+    }
+}
@@ -0,0 +1,250 @@
+//! Nested timing spans for library code.
+//!
+//! `let _span = span::enter("parse");` times everything until the guard is dropped, nested under
+//! whichever span was open when it was entered. Spans with the same name under the same parent
+//! are merged, so spans inside loops add up. Recording is off by default, when `enter` costs a
+//! single atomic load.
+
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static RECORDER: RefCell<Recorder> = RefCell::new(Recorder::default());
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+#[must_use = "the span ends when the guard is dropped"]
+pub struct SpanGuard {
+    start: Option<Instant>,
+}
+
+pub fn enter(name: &'static str) -> SpanGuard {
+    if !is_enabled() {
+        return SpanGuard { start: None };
+    }
+    RECORDER.with_borrow_mut(|recorder| recorder.enter(name));
+    SpanGuard {
+        start: Some(Instant::now()),
+    }
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            let elapsed = start.elapsed();
+            RECORDER.with_borrow_mut(|recorder| recorder.exit(elapsed));
+        }
+    }
+}
+
+/// Removes and returns the spans recorded on this thread so far. Spans which are still open are
+/// returned with the time of their children so far, and stay open to be counted by a later `take`.
+pub fn take() -> SpanTree {
+    RECORDER.with_borrow_mut(|recorder| SpanTree {
+        roots: recorder.take_roots(),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanNode {
+    pub name: &'static str,
+    pub count: u32,
+    pub total: Duration,
+    pub children: Vec<SpanNode>,
+}
+
+impl SpanNode {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            count: 0,
+            total: Duration::ZERO,
+            children: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Recorder {
+    roots: Vec<SpanNode>,
+    // the path of child indices from the roots to the open span
+    open: Vec<usize>,
+}
+
+impl Recorder {
+    fn children_of_open(&mut self) -> &mut Vec<SpanNode> {
+        let mut children = &mut self.roots;
+        for &idx in &self.open {
+            children = &mut children[idx].children;
+        }
+        children
+    }
+
+    fn enter(&mut self, name: &'static str) {
+        let children = self.children_of_open();
+        let idx = match children.iter().position(|node| node.name == name) {
+            Some(idx) => idx,
+            None => {
+                children.push(SpanNode::new(name));
+                children.len() - 1
+            }
+        };
+        self.open.push(idx);
+    }
+
+    // leaves a fresh node for each open span, so that their guards can still exit
+    fn take_roots(&mut self) -> Vec<SpanNode> {
+        let roots = std::mem::take(&mut self.roots);
+        let mut taken = &roots;
+        let mut fresh = &mut self.roots;
+        for idx in &mut self.open {
+            let node = &taken[*idx];
+            fresh.push(SpanNode::new(node.name));
+            *idx = 0;
+            taken = &node.children;
+            fresh = &mut fresh[0].children;
+        }
+        roots
+    }
+
+    fn exit(&mut self, elapsed: Duration) {
+        let idx = self.open.pop().expect("span exited without being entered");
+        let node = &mut self.children_of_open()[idx];
+        node.count += 1;
+        node.total += elapsed;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanTree {
+    pub roots: Vec<SpanNode>,
+}
+
+impl SpanTree {
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+}
+
+impl Display for SpanTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_node(
+            f: &mut std::fmt::Formatter<'_>,
+            node: &SpanNode,
+            parent_total: Option<Duration>,
+            depth: usize,
+        ) -> std::fmt::Result {
+            let name = format!("{:indent$}{}", "", node.name, indent = depth * 2);
+            write!(f, "{name:40} {:>12.3?}", node.total)?;
+            if let Some(parent_total) = parent_total.filter(|total| !total.is_zero()) {
+                let percent = node.total.as_secs_f64() / parent_total.as_secs_f64() * 100.0;
+                write!(f, " {percent:5.1}%")?;
+            }
+            if node.count > 1 {
+                write!(f, "  x{}", node.count)?;
+            }
+            writeln!(f)?;
+            for child in &node.children {
+                write_node(f, child, Some(node.total), depth + 1)?;
+            }
+            Ok(())
+        }
+        for root in &self.roots {
+            write_node(f, root, None, 0)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ENABLED is shared by every test thread, so the recording tests run inside one test
+    #[test]
+    fn test_spans() {
+        enable();
+        {
+            let _outer = enter("outer");
+            for _ in 0..3 {
+                let _inner = enter("inner");
+            }
+            let _other = enter("other");
+        }
+        {
+            let _second = enter("second");
+        }
+        let tree = take();
+        assert_eq!(tree.roots.len(), 2);
+        let outer = &tree.roots[0];
+        assert_eq!(outer.name, "outer");
+        assert_eq!(outer.count, 1);
+        assert_eq!(outer.children.len(), 2);
+        assert_eq!(outer.children[0].name, "inner");
+        assert_eq!(outer.children[0].count, 3);
+        assert!(outer.children.iter().map(|c| c.total).sum::<Duration>() <= outer.total);
+        assert_eq!(tree.roots[1].name, "second");
+        assert!(take().is_empty());
+
+        let outer = enter("open");
+        {
+            let _inner = enter("inner");
+        }
+        let tree = take();
+        assert_eq!(tree.roots[0].name, "open");
+        assert_eq!(tree.roots[0].count, 0);
+        assert_eq!(tree.roots[0].children[0].count, 1);
+        drop(outer);
+        let tree = take();
+        assert_eq!(tree.roots[0].name, "open");
+        assert_eq!(tree.roots[0].count, 1);
+        assert!(tree.roots[0].children.is_empty());
+
+        disable();
+        {
+            let _ignored = enter("ignored");
+        }
+        assert!(take().is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        let tree = SpanTree {
+            roots: vec![SpanNode {
+                name: "solve",
+                count: 1,
+                total: Duration::from_millis(10),
+                children: vec![SpanNode {
+                    name: "parse",
+                    count: 2,
+                    total: Duration::from_millis(4),
+                    children: vec![],
+                }],
+            }],
+        };
+        let text = tree.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("solve "));
+        assert!(lines[1].starts_with("  parse "));
+        assert!(lines[1].contains(" 40.0%"));
+        assert!(lines[1].ends_with("x2"));
+    }
+}
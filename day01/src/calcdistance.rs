@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 use std::collections::{BTreeMap, HashMap};
 
+use aoc_common::{Result, parse};
+
 #[allow(dead_code)]
 const EXAMPLE: &str = "3   4
 4   3
@@ -9,25 +11,19 @@ const EXAMPLE: &str = "3   4
 3   9
 3   3";
 
-pub fn parse_input(input: &str) -> (Vec<i32>, Vec<i32>) {
-    let mut x = Vec::new();
-    let mut y = Vec::new();
-    for line in input.lines() {
-        let mut it = line.split_whitespace().map(|n| n.parse());
-        if let (Some(Ok(xx)), Some(Ok(yy))) = (it.next(), it.next()) {
-            x.push(xx);
-            y.push(yy);
-        }
-    }
-    (x, y)
+pub fn parse_input(input: &str) -> Result<(Vec<i32>, Vec<i32>)> {
+    let pairs = parse::lines(input, |line| match parse::numbers::<i32>(line, ' ')?[..] {
+        [x, y] => Ok((x, y)),
+        _ => Err("expected two numbers".to_string()),
+    })?;
+    Ok(pairs.into_iter().unzip())
 }
 
 pub fn calc_distance(mut a: Vec<i32>, mut b: Vec<i32>) -> i32 {
     a.sort();
     b.sort();
 
-    let ret = a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum();
-    ret
+    a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum()
 }
 
 fn vec_to_count_map(v: Vec<i32>) -> HashMap<i32, i32> {
@@ -53,20 +49,18 @@ fn vec_to_sum_map(v: Vec<i32>) -> HashMap<i32, i32> {
 
 pub fn count_repeats1(a: Vec<i32>, b: Vec<i32>) -> i32 {
     let b_sums = vec_to_sum_map(b);
-    let ret = a.iter().map(|i| b_sums.get(i).unwrap_or(&0)).sum();
 
-    ret
+    a.iter().map(|i| b_sums.get(i).unwrap_or(&0)).sum()
 }
 
 pub fn count_repeats2(a: Vec<i32>, b: Vec<i32>) -> i32 {
     let a_counts = vec_to_count_map(a);
     let b_sums = vec_to_sum_map(b);
-    let ret = a_counts
+
+    a_counts
         .iter()
         .map(|(i, count)| b_sums.get(i).unwrap_or(&0) * count)
-        .sum();
-
-    ret
+        .sum()
 }
 
 pub fn count_repeats3(mut a: Vec<i32>, mut b: Vec<i32>) -> i32 {
@@ -135,9 +129,8 @@ fn vec_to_btreemap(v: Vec<i32>) -> BTreeMap<i32, i32> {
 
 pub fn count_repeats4(a: Vec<i32>, b: Vec<i32>) -> i32 {
     let b_sums = vec_to_btreemap(b);
-    let ret = a.iter().map(|i| b_sums.get(i).unwrap_or(&0)).sum();
 
-    ret
+    a.iter().map(|i| b_sums.get(i).unwrap_or(&0)).sum()
 }
 
 #[allow(unused_imports)]
@@ -146,14 +139,26 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let (a, b) = parse_input(EXAMPLE);
+        let (a, b) = parse_input(EXAMPLE).unwrap();
         let distance = calc_distance(a, b);
         assert_eq!(distance, 11);
     }
     #[test]
     fn test_count_repeats() {
-        let (a, b) = parse_input(EXAMPLE);
+        let (a, b) = parse_input(EXAMPLE).unwrap();
         let repeats = count_repeats1(a, b);
         assert_eq!(repeats, 31);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("3   4\n4 x").unwrap_err().to_string(),
+            "line 2: invalid number 'x'"
+        );
+        assert_eq!(
+            parse_input("3   4\n4").unwrap_err().to_string(),
+            "line 2: expected two numbers"
+        );
+    }
 }
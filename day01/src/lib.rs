@@ -0,0 +1,31 @@
+use aoc_common::{Result, Solution};
+
+pub mod calcdistance;
+
+// import input.txt as a static string
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = (Vec<i32>, Vec<i32>);
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        calcdistance::parse_input(input)
+    }
+
+    fn part1((a, b): &Self::Parsed) -> Result<i32> {
+        Ok(calcdistance::calc_distance(a.clone(), b.clone()))
+    }
+
+    fn part2((a, b): &Self::Parsed) -> Result<i32> {
+        Ok(calcdistance::count_repeats1(a.clone(), b.clone()))
+    }
+}
@@ -1,15 +1,17 @@
+use aoc_common::{bench::Bench, input};
 use std::time::Instant;
 use stephen_morris_utils as utils;
 use utils::timer;
 
-mod calcdistance;
-
-// import input.txt as a static string
-const INPUT: &str = include_str!("input.txt");
+use day01::{EMBEDDED_INPUT, calcdistance};
 
 fn main() {
+    let input = input::load_from_args(1, EMBEDDED_INPUT);
     let now = Instant::now();
-    let t_parse_input = timer::time(|| calcdistance::parse_input(INPUT), "parse_input");
+    let t_parse_input = timer::time(
+        || input::or_exit(calcdistance::parse_input(&input)),
+        "parse_input",
+    );
 
     let (a, b) = &*t_parse_input;
 
@@ -52,4 +54,20 @@ fn main() {
     repeats4.print_all();
 
     println!("Main duration: {:?}", main_duration);
+
+    // single runs are too noisy to rank the count_repeats variants, so benchmark them
+    let bench = Bench::new().samples(50);
+    type CountRepeats = fn(Vec<i32>, Vec<i32>) -> i32;
+    let variants: [(&str, CountRepeats); 4] = [
+        ("count_repeats1", calcdistance::count_repeats1),
+        ("count_repeats2", calcdistance::count_repeats2),
+        ("count_repeats3", calcdistance::count_repeats3),
+        ("count_repeats4", calcdistance::count_repeats4),
+    ];
+    for (name, count_repeats) in variants {
+        println!(
+            "{}",
+            bench.run(name, || count_repeats(a.clone(), b.clone()))
+        );
+    }
 }
@@ -1,12 +1,7 @@
-pub fn parse_input(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|num| num.parse().unwrap())
-                .collect()
-        })
-        .collect()
+use aoc_common::{Result, parse};
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i32>>> {
+    parse::lines(input, |line| parse::numbers(line, ' '))
 }
 
 pub fn check_report_asc(report: &[i32]) -> bool {
@@ -128,19 +123,28 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "embedded-input")]
     fn check_report_test1() {
-        let reports = parse_input(crate::INPUT);
+        let reports = parse_input(crate::INPUT).unwrap();
         check_reports2_test(&mut reports.clone());
     }
 
     #[test]
     fn test_parse_input() {
-        let mut reports = parse_input(TESTINPUT);
+        let mut reports = parse_input(TESTINPUT).unwrap();
         assert_eq!(reports.len(), 6);
         let count = check_reports(&mut reports);
         assert_eq!(count, 2);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("7 6 4\n\n1 2 3.5").unwrap_err().to_string(),
+            "line 3: invalid number '3.5'"
+        );
+    }
+
     #[test]
     fn test_bool_conversion() {
         assert_eq!(true as i32, 1);
@@ -149,7 +153,7 @@ mod tests {
 
     #[test]
     fn test_check_reports2() {
-        let mut reports = parse_input(TESTINPUT);
+        let mut reports = parse_input(TESTINPUT).unwrap();
         assert_eq!(reports.len(), 6);
         let count = check_reports2(&mut reports);
         assert_eq!(count, 4);
@@ -0,0 +1,38 @@
+use aoc_common::{Result, Solution};
+
+pub mod check_levels;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<Vec<i32>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        check_levels::parse_input(input)
+    }
+
+    fn part1(reports: &Self::Parsed) -> Result<i32> {
+        Ok(check_levels::check_reports(&mut reports.clone()))
+    }
+
+    fn part2(reports: &Self::Parsed) -> Result<i32> {
+        Ok(check_levels::check_reports2(&mut reports.clone()))
+    }
+}
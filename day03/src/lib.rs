@@ -0,0 +1,38 @@
+use aoc_common::{Result, Solution};
+
+pub mod parse_and_calc;
+pub mod parser;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+#[allow(dead_code)]
+const TESTINPUT2: &str =
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = Vec<parser::Instruction>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        // any text is valid corrupted memory, and what isn't an instruction is skipped
+        Ok(parse_and_calc::parse_input(input))
+    }
+
+    fn part1(instructions: &Self::Parsed) -> Result<i64> {
+        Ok(parse_and_calc::sum_products(instructions))
+    }
+
+    fn part2(instructions: &Self::Parsed) -> Result<i64> {
+        Ok(parse_and_calc::sum_products22(instructions))
+    }
+}
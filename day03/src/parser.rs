@@ -44,7 +44,10 @@ where
         next = chars.next();
         while let Some((_, c)) = next {
             if c.is_ascii_digit() {
-                number = number * T::from(10).unwrap() + T::from(c.to_digit(10).unwrap()).unwrap();
+                // a number too large for T can't be an operand
+                number = number
+                    .checked_mul(&T::from(10).unwrap())?
+                    .checked_add(&(sign * T::from(c.to_digit(10).unwrap()).unwrap()))?;
             } else {
                 break;
             }
@@ -118,7 +121,11 @@ impl Iterator for ParseIterator<'_> {
                     self.slice = rest;
                     return Some(instruction);
                 }
-                None => self.slice = self.slice[1..].trim_start(),
+                None => {
+                    let mut chars = self.slice.chars();
+                    chars.next();
+                    self.slice = chars.as_str().trim_start();
+                }
             }
         }
         None
@@ -146,4 +153,14 @@ mod tests {
         assert_eq!(iter.next(), Some(Instruction::Dont));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_corrupted() {
+        let input = "é mul(-3,4) mul(99999999999999999999,2)✓mul(1,2)";
+        let instructions: Vec<_> = ParseIterator::new(input).collect();
+        assert_eq!(
+            instructions,
+            [Instruction::Mul(-3, 4), Instruction::Mul(1, 2)]
+        );
+    }
 }
@@ -68,38 +68,22 @@ pub fn count_x_mas1(v: &[Vec<u8>]) -> u32 {
     count
 }
 
-use crate::grid::Grid;
-pub fn parse_input(input: &str) -> Grid<u8> {
-    Grid::from(input)
+use crate::grid::{ALL_NEIGHBOURS, Grid, Point};
+use aoc_common::Result;
+pub fn parse_input(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(input, |byte| byte.is_ascii_graphic())
 }
 
 pub fn count_xmas(v: &Grid<u8>) -> u32 {
     let mut count = 0;
-    let width = v.width as i32;
-    let height = v.height as i32;
-    for (start_row, start_col, value) in v {
+    for (start, value) in v {
         if *value == b'X' {
-            for x in [-1_i32, 0, 1] {
-                for y in [-1_i32, 0, 1] {
-                    if x == 0 && y == 0 {
-                        continue;
-                    }
-                    let start_row = start_row as i32;
-                    let start_col = start_col as i32;
-                    let end_row = start_row + 3 * x;
-                    let end_col = start_col + 3 * y;
-                    if end_row >= 0
-                        && end_row < width
-                        && end_col >= 0
-                        && end_col < height
-                        && *v.get(end_row as usize, end_col as usize) == b'S'
-                        && *v.get((start_row + 2 * x) as usize, (start_col + 2 * y) as usize)
-                            == b'A'
-                        && *v.get((start_row + x) as usize, (start_col + y) as usize) == b'M'
-                    {
-                        //println!("found {} {} {} {}", start_row, start_col, x, y);
-                        count += 1;
-                    }
+            for direction in ALL_NEIGHBOURS {
+                if (1..4).all(|i| {
+                    v.add_vector(start, direction * i)
+                        .is_some_and(|p| v[p] == b"XMAS"[i as usize])
+                }) {
+                    count += 1;
                 }
             }
         }
@@ -112,16 +96,16 @@ pub fn count_x_mas(v: &Grid<u8>) -> u32 {
     let mut count = 0;
     let width = v.width;
     let height = v.height;
-    for (start_row, start_col, value) in v {
-        if start_row == 0 || start_row == height - 1 || start_col == 0 || start_col == width - 1 {
+    for (Point { x, y }, value) in v {
+        if y == 0 || y == height - 1 || x == 0 || x == width - 1 {
             continue;
         }
         if *value == b'A' {
             match [
-                v.get(start_row - 1, start_col - 1),
-                v.get(start_row + 1, start_col + 1),
-                v.get(start_row - 1, start_col + 1),
-                v.get(start_row + 1, start_col - 1),
+                v[Point::new(x - 1, y - 1)],
+                v[Point::new(x + 1, y + 1)],
+                v[Point::new(x + 1, y - 1)],
+                v[Point::new(x - 1, y + 1)],
             ] {
                 [b'M', b'S', b'M', b'S']
                 | [b'M', b'S', b'S', b'M']
@@ -143,13 +127,21 @@ mod tests {
 
     #[test]
     fn test_count_xmas() {
-        let v = parse_input(TESTINPUT);
+        let v = parse_input(TESTINPUT).unwrap();
         assert_eq!(count_xmas(&v), 18);
     }
 
     #[test]
     fn test_count_x_mas() {
-        let v = parse_input(TESTINPUT);
+        let v = parse_input(TESTINPUT).unwrap();
         assert_eq!(count_x_mas(&v), 9);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("XMAS\nXMA\n").unwrap_err().to_string(),
+            "line 2: expected a row of 4 characters, found 3"
+        );
+    }
 }
@@ -0,0 +1,42 @@
+use aoc_common::{Result, Solution, grid};
+
+pub mod count_xmas;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = grid::Grid<u8>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        count_xmas::parse_input(input)
+    }
+
+    fn part1(grid: &Self::Parsed) -> Result<u32> {
+        Ok(count_xmas::count_xmas(grid))
+    }
+
+    fn part2(grid: &Self::Parsed) -> Result<u32> {
+        Ok(count_xmas::count_x_mas(grid))
+    }
+}
@@ -0,0 +1,60 @@
+use aoc_common::{Result, Solution};
+
+pub mod print_rules;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[cfg(test)]
+const TESTINPUT: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = (print_rules::Rules, Vec<print_rules::Update>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        print_rules::parse_input(input)
+    }
+
+    fn part1((rules, updates): &Self::Parsed) -> Result<usize> {
+        Ok(print_rules::test_updates(rules, updates))
+    }
+
+    fn part2((rules, updates): &Self::Parsed) -> Result<usize> {
+        Ok(print_rules::reorder(rules, updates))
+    }
+}
@@ -1,6 +1,11 @@
 use std::{collections::HashMap, ops::Deref, ops::DerefMut};
 
-type Rules = Vec<(usize, usize)>;
+use aoc_common::{
+    Result,
+    parse::{self, LineResult},
+};
+
+pub type Rules = Vec<(usize, usize)>;
 
 #[derive(Debug, Clone)]
 pub struct Update(Vec<usize>);
@@ -18,29 +23,29 @@ impl DerefMut for Update {
     }
 }
 
-pub fn parse_input(input: &str) -> (Rules, Vec<Update>) {
+// pages index the arrays in test_update
+fn page(word: &str) -> LineResult<usize> {
+    match parse::number(word.trim())? {
+        page @ 0..100 => Ok(page),
+        page => Err(format!("page {page} is not below 100")),
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<(Rules, Vec<Update>)> {
     let no_lines = input.lines().count();
     let mut rules = Vec::with_capacity(no_lines >> 1);
     let mut update_list = Vec::with_capacity(no_lines >> 1);
-    for line in input.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        };
-        if line.contains('|') {
-            let mut parts = line.split('|');
-            rules.push((
-                parts.next().unwrap().parse().unwrap(),
-                parts.next().unwrap().parse().unwrap(),
-            ));
-        } else {
-            update_list.push(Update(
-                line.split(',').map(|s| s.parse().unwrap()).collect(),
-            ))
+    parse::lines(input, |line| {
+        match line.split_once('|') {
+            Some((a, b)) => rules.push((page(a)?, page(b)?)),
+            None => update_list.push(Update(
+                line.split(',').map(page).collect::<LineResult<_>>()?,
+            )),
         }
-    }
+        Ok(())
+    })?;
 
-    (rules, update_list)
+    Ok((rules, update_list))
 }
 
 fn test_update(rules: &Rules, update: &Update) -> bool {
@@ -49,11 +54,11 @@ fn test_update(rules: &Rules, update: &Update) -> bool {
         index[*value] = Some(i);
     }
     for (a, b) in rules {
-        if let (Some(a_index), Some(b_index)) = (index[*a], index[*b]) {
-            if b_index < a_index {
-                return false;
-            };
-        }
+        if let (Some(a_index), Some(b_index)) = (index[*a], index[*b])
+            && b_index < a_index
+        {
+            return false;
+        };
     }
 
     true
@@ -69,7 +74,7 @@ pub fn test_updates(rules: &Rules, updates: &[Update]) -> usize {
 
 pub fn reorder(rules: &Rules, updates: &[Update]) -> usize {
     let mut hm_rules: HashMap<(usize, usize), std::cmp::Ordering> =
-        HashMap::with_capacity((rules.len() * (rules.len() - 1)) >> 1);
+        HashMap::with_capacity(rules.len() * 2);
     for (a, b) in rules {
         hm_rules.insert((*a, *b), std::cmp::Ordering::Less);
         hm_rules.insert((*b, *a), std::cmp::Ordering::Greater);
@@ -92,13 +97,21 @@ mod tests {
 
     #[test]
     fn test_test_updates() {
-        let (rules, updates) = parse_input(crate::TESTINPUT);
+        let (rules, updates) = parse_input(crate::TESTINPUT).unwrap();
         assert_eq!(test_updates(&rules, &updates), 143);
     }
 
     #[test]
     fn test_reorder() {
-        let (rules, updates) = parse_input(crate::TESTINPUT);
+        let (rules, updates) = parse_input(crate::TESTINPUT).unwrap();
         assert_eq!(super::reorder(&rules, &updates), 123);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(error("47|53\n\n75,x"), "line 3: invalid number 'x'");
+        assert_eq!(error("47|153"), "line 1: page 153 is not below 100");
+        assert_eq!(error("47|53|61"), "line 1: invalid number '53|61'");
+    }
 }
@@ -1,11 +1,14 @@
 use std::collections::HashSet;
 
-use stephen_morris_utils::grid::{Direction, Point};
+use aoc_common::{
+    Result,
+    grid::{Direction, Point},
+};
 
 type Grid = crate::grid::Grid<u8>;
 
-pub fn parse_input(input: &str) -> Grid {
-    Grid::from(input)
+pub fn parse_input(input: &str) -> Result<Grid> {
+    Grid::parse(input, |byte| b".#^v<>".contains(&byte))
 }
 
 pub fn extract_guard(grid: &mut Grid) -> Option<Guard> {
@@ -113,15 +116,25 @@ mod tests {
     use super::*;
     #[test]
     fn test_all() {
-        let mut grid = parse_input(crate::TESTINPUT);
+        let mut grid = parse_input(crate::TESTINPUT).unwrap();
         let mut guard = extract_guard(&mut grid).unwrap();
         assert_eq!(guard.count_guard_positions(&grid), 41);
     }
 
     #[test]
     fn test_blockers() {
-        let mut grid = parse_input(crate::TESTINPUT);
+        let mut grid = parse_input(crate::TESTINPUT).unwrap();
         let guard = extract_guard(&mut grid).unwrap();
         assert_eq!(guard.count_blockers(&grid), 6);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("..#\n.x.").unwrap_err().to_string(),
+            "line 2: unexpected character 'x'"
+        );
+        let mut grid = parse_input("..#\n...").unwrap();
+        assert!(extract_guard(&mut grid).is_none());
+    }
 }
@@ -0,0 +1,44 @@
+use aoc_common::{ParseError, Result, Solution, grid};
+
+pub mod guard;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+#[allow(dead_code)]
+const TESTINPUT: &str = r"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = (grid::Grid<u8>, guard::Guard);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let mut grid = guard::parse_input(input)?;
+        let guard = guard::extract_guard(&mut grid).ok_or(ParseError::new("no guard found"))?;
+        Ok((grid, guard))
+    }
+
+    fn part1((grid, guard): &Self::Parsed) -> Result<usize> {
+        let mut guard = *guard;
+        Ok(guard.count_guard_positions(grid))
+    }
+
+    fn part2((grid, guard): &Self::Parsed) -> Result<usize> {
+        Ok(guard.count_blockers(grid))
+    }
+}
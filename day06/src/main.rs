@@ -1,24 +1,16 @@
-use stephen_morris_utils::grid;
+use aoc_common::input;
 use stephen_morris_utils::timer;
 
-const INPUT: &str = include_str!("input.txt");
-#[allow(dead_code)]
-const TESTINPUT: &str = r"....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-
-mod guard;
+use day06::{EMBEDDED_INPUT, guard};
 
 fn main() {
-    let mut grid = timer::time(|| guard::parse_input(INPUT), "Parse input");
+    let input = input::load_from_args(6, EMBEDDED_INPUT);
+    let mut grid = timer::time(|| input::or_exit(guard::parse_input(&input)), "Parse input");
     let mut guard = timer::time(|| guard::extract_guard(&mut grid), "Extract guard");
+    if guard.is_none() {
+        eprintln!("error: no guard found");
+        std::process::exit(1);
+    }
     guard.print_all();
     //grid.print_all();
     let count = timer::time(
@@ -30,7 +22,7 @@ fn main() {
     guard.print_all();
     count.print_all();
 
-    let mut grid = timer::time(|| guard::parse_input(INPUT), "Parse input");
+    let mut grid = timer::time(|| input::or_exit(guard::parse_input(&input)), "Parse input");
     let mut guard = timer::time(|| guard::extract_guard(&mut grid), "Extract guard");
     let count = timer::time(
         || guard.as_mut().unwrap().count_blockers(&grid),
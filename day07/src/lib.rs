@@ -0,0 +1,42 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution};
+
+pub mod operators;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+#[allow(dead_code)]
+const TESTINPUT: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Vec<operators::Equation>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        operators::parse_input(input)
+    }
+
+    fn part1(equations: &Self::Parsed) -> Result<usize> {
+        Ok(operators::solve_and_sum(equations))
+    }
+
+    fn part2(equations: &Self::Parsed) -> Result<usize> {
+        Ok(operators::solve_and_sum2(equations))
+    }
+}
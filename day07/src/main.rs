@@ -1,22 +1,14 @@
-#![allow(dead_code, unused)]
+use aoc_common::input;
 use stephen_morris_utils::timer;
 
-const INPUT: &str = include_str!("input.txt");
-#[allow(dead_code)]
-const TESTINPUT: &str = "190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20";
-
-mod operators;
+use day07::{EMBEDDED_INPUT, operators};
 
 fn main() {
-    let equations = timer::time(|| operators::parse_input(crate::INPUT), "parse_input");
+    let input = input::load_from_args(7, EMBEDDED_INPUT);
+    let equations = timer::time(
+        || input::or_exit(operators::parse_input(&input)),
+        "parse_input",
+    );
     let sum = timer::time(|| operators::solve_and_sum(&equations), "solve_and_sum");
     let sum2 = timer::time(|| operators::solve_and_sum2(&equations), "solve_and_sum2");
 
@@ -1,4 +1,6 @@
-use std::num::ParseIntError;
+use std::{fmt::Display, num::ParseIntError};
+
+use aoc_common::{Result, parse};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Equation {
@@ -12,21 +14,33 @@ impl Equation {
     }
 }
 
+#[derive(Debug, PartialEq)]
 pub enum EquationParseError {
+    NoColon,
     NoParams,
     ParseIntError(ParseIntError),
 }
 
+impl Display for EquationParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EquationParseError::NoColon => write!(f, "expected '<target>: <numbers>'"),
+            EquationParseError::NoParams => write!(f, "expected numbers after the target"),
+            EquationParseError::ParseIntError(e) => write!(f, "invalid number: {e}"),
+        }
+    }
+}
+
 impl TryFrom<&str> for Equation {
     type Error = EquationParseError;
-    fn try_from(line: &str) -> Result<Self, <Self as TryFrom<&str>>::Error> {
-        let mut split = line.split_whitespace();
-        let target = split.next().ok_or(EquationParseError::NoParams)?;
-        let target = target[..target.len() - 1]
+    fn try_from(line: &str) -> std::result::Result<Self, <Self as TryFrom<&str>>::Error> {
+        let (target, rest) = line.split_once(':').ok_or(EquationParseError::NoColon)?;
+        let target = target
+            .trim()
             .parse()
             .map_err(EquationParseError::ParseIntError)?;
         let mut parameters = Vec::new();
-        for number in split {
+        for number in rest.split_whitespace() {
             parameters.push(number.parse().map_err(EquationParseError::ParseIntError)?)
         }
         if parameters.is_empty() {
@@ -36,13 +50,10 @@ impl TryFrom<&str> for Equation {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Equation> {
-    let ret = input
-        .lines()
-        .filter_map(|s| Equation::try_from(s).ok())
-        .collect();
-
-    ret
+pub fn parse_input(input: &str) -> Result<Vec<Equation>> {
+    parse::lines(input, |line| {
+        Equation::try_from(line).map_err(|e| e.to_string())
+    })
 }
 
 fn solve(target: usize, first: usize, rest: &[usize]) -> Option<usize> {
@@ -106,8 +117,23 @@ pub fn solve_and_sum2(v: &[Equation]) -> usize {
 mod tests {
     #[test]
     fn test_parse_input() {
-        let equations = super::parse_input(crate::TESTINPUT);
+        let equations = super::parse_input(crate::TESTINPUT).unwrap();
         println!("{equations:#?}");
+        assert_eq!(equations.len(), 9);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| super::parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            error("190: 10 19\n3267 81"),
+            "line 2: expected '<target>: <numbers>'"
+        );
+        assert_eq!(error("190:"), "line 1: expected numbers after the target");
+        assert_eq!(
+            error("190: 10 x"),
+            "line 1: invalid number: invalid digit found in string"
+        );
     }
 
     #[test]
@@ -125,13 +151,13 @@ mod tests {
 
     #[test]
     fn test_solve_and_sum() {
-        let equations = super::parse_input(crate::TESTINPUT);
+        let equations = super::parse_input(crate::TESTINPUT).unwrap();
         assert_eq!(super::solve_and_sum(&equations), 3749);
     }
 
     #[test]
     fn test_solve_and_sum2() {
-        let equations = super::parse_input(crate::TESTINPUT);
+        let equations = super::parse_input(crate::TESTINPUT).unwrap();
         for e in &equations {
             println!("{:?} {:?}", e, e.solve2());
         }
@@ -3,55 +3,54 @@ use std::{
     default,
 };
 
-use stephen_morris_utils::pos::Position;
-
 use crate::grid::{Grid, Point, Vector};
+use aoc_common::Result;
 type Cell = Option<u8>;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AntennaLocations {
     antenna: u8,
-    locations: Vec<Position<usize>>,
+    locations: Vec<Point>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AntennaMap {
     grid: Grid<Cell>,
-    antennas: HashMap<u8, Vec<Position<usize>>>,
+    antennas: HashMap<u8, Vec<Point>>,
 }
 
-pub fn parse_input(input: &str) -> AntennaMap {
-    let gridu8 = Grid::from(input);
+pub fn parse_input(input: &str) -> Result<AntennaMap> {
+    let gridu8 = Grid::parse(input, |byte| byte == b'.' || byte.is_ascii_alphanumeric())?;
     let mut grid = Grid::<Cell>::new_default(gridu8.width, gridu8.height);
     let mut antennas = HashMap::new();
-    for (x, y, c) in gridu8 {
+    for (p, c) in gridu8 {
         if c == b'.' {
-            grid.set(x, y, None);
+            grid.set(p, None);
         } else {
-            grid.set(x, y, Some(c));
+            grid.set(p, Some(c));
             antennas
                 .entry(c)
-                .and_modify(|v: &mut Vec<Position<usize>>| v.push(Position::new(x, y)))
-                .or_insert(vec![Position::new(x, y)]);
+                .and_modify(|v: &mut Vec<Point>| v.push(p))
+                .or_insert(vec![p]);
         }
     }
-    AntennaMap { grid, antennas }
+    Ok(AntennaMap { grid, antennas })
 }
 
 pub fn count_antinodes(antenna_map: &AntennaMap) -> usize {
-    let mut antinodes: HashSet<Position<usize>> = HashSet::new();
+    let mut antinodes: HashSet<Point> = HashSet::new();
     let grid = &antenna_map.grid;
     for (antenna, position_list) in &antenna_map.antennas {
         for i in 0..position_list.len() - 1 {
             for j in i + 1..position_list.len() {
-                let i = grid.point2vec(position_list[i]).unwrap();
-                let j = grid.point2vec(position_list[j]).unwrap();
+                let i = Vector::from(position_list[i]);
+                let j = Vector::from(position_list[j]);
                 let i_side = i - (j - i);
                 let j_side = j + (j - i);
-                if let Some(p) = grid.vec2point(i_side) {
+                if let Some(p) = grid.point_from_vector(i_side) {
                     antinodes.insert(p);
                 }
-                if let Some(p) = grid.vec2point(j_side) {
+                if let Some(p) = grid.point_from_vector(j_side) {
                     antinodes.insert(p);
                 }
             }
@@ -65,23 +64,23 @@ pub fn count_antinodes2(antenna_map: &AntennaMap) -> usize {
         let gcd = num_integer::gcd(v.x, v.y);
         Vector::new(v.x / gcd, v.y / gcd)
     }
-    let mut antinodes: HashSet<Position<usize>> = HashSet::new();
+    let mut antinodes: HashSet<Point> = HashSet::new();
     let grid = &antenna_map.grid;
     for (antenna, position_list) in &antenna_map.antennas {
         for i in 0..position_list.len() - 1 {
             for j in i + 1..position_list.len() {
-                let i = grid.point2vec(position_list[i]).unwrap();
-                let j = grid.point2vec(position_list[j]).unwrap();
+                let i = Vector::from(position_list[i]);
+                let j = Vector::from(position_list[j]);
                 let u = coprime(j - i);
                 let mut an = i;
-                while let Some(p) = grid.vec2point(an) {
+                while let Some(p) = grid.point_from_vector(an) {
                     antinodes.insert(p);
-                    an = an + u;
+                    an += u;
                 }
                 an = i;
-                while let Some(p) = grid.vec2point(an) {
+                while let Some(p) = grid.point_from_vector(an) {
                     antinodes.insert(p);
-                    an = an - u;
+                    an -= u;
                 }
             }
         }
@@ -93,13 +92,21 @@ pub fn count_antinodes2(antenna_map: &AntennaMap) -> usize {
 mod tests {
     #[test]
     fn test_count_antinodes() {
-        let antenna_map = super::parse_input(crate::TESTINPUT);
+        let antenna_map = super::parse_input(crate::TESTINPUT).unwrap();
         assert_eq!(super::count_antinodes(&antenna_map), 14);
     }
 
     #[test]
     fn test_count_antinodes2() {
-        let antenna_map = super::parse_input(crate::TESTINPUT);
+        let antenna_map = super::parse_input(crate::TESTINPUT).unwrap();
         assert_eq!(super::count_antinodes2(&antenna_map), 34);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            super::parse_input("..a.\n.#..").unwrap_err().to_string(),
+            "line 2: unexpected character '#'"
+        );
+    }
 }
@@ -0,0 +1,45 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution, grid};
+
+pub mod antinodes;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+#[allow(dead_code)]
+const TESTINPUT: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = antinodes::AntennaMap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        antinodes::parse_input(input)
+    }
+
+    fn part1(antenna_map: &Self::Parsed) -> Result<usize> {
+        Ok(antinodes::count_antinodes(antenna_map))
+    }
+
+    fn part2(antenna_map: &Self::Parsed) -> Result<usize> {
+        Ok(antinodes::count_antinodes2(antenna_map))
+    }
+}
@@ -1,27 +1,14 @@
-#![allow(dead_code, unused)]
+use aoc_common::input;
 use stephen_morris_utils::timer;
 
-const INPUT: &str = include_str!("input.txt");
-#[allow(dead_code)]
-const TESTINPUT: &str = "............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............";
-
-mod antinodes;
-
-mod grid;
+use day08::{EMBEDDED_INPUT, antinodes};
 
 fn main() {
-    let antenna_map = timer::time(|| antinodes::parse_input(INPUT), "parse_input");
+    let input = input::load_from_args(8, EMBEDDED_INPUT);
+    let antenna_map = timer::time(
+        || input::or_exit(antinodes::parse_input(&input)),
+        "parse_input",
+    );
     let count = timer::time(
         || antinodes::count_antinodes(&antenna_map),
         "count_antinodes",
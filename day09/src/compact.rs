@@ -1,25 +1,52 @@
 #![allow(dead_code, unused)]
 
-type Disk = Vec<Option<u16>>;
+use aoc_common::{ParseError, Result};
 
-pub fn parse_input(input: &str) -> Disk {
-    let length = input.bytes().map(|c| (c - b'0') as usize).sum();
-    let mut ret = Vec::with_capacity(length);
-    let mut id = 0u16;
-    let mut bytes = input.bytes();
+pub type Disk = Vec<Option<u16>>;
 
-    loop {
-        let file_size = (bytes.next().unwrap() - b'0') as usize;
-        ret.extend(std::iter::repeat_n(Some(id), file_size));
-        if let Some(free_space) = bytes.next() {
-            let free_space = (free_space - b'0') as usize;
-            ret.extend(std::iter::repeat_n(None, free_space));
-            id += 1;
-        } else {
-            break;
+/// The digits of the disk map, which is a single line, as numbers.
+pub fn disk_map(input: &str) -> Result<Vec<u8>> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((idx, line)) = lines.next() else {
+        return Err(ParseError::new("the disk map is empty"));
+    };
+    if let Some((idx, _)) = lines.next() {
+        return Err(ParseError::at_line(
+            idx + 1,
+            "expected the disk map on a single line",
+        ));
+    }
+    let line = line.trim();
+    if let Some(c) = line.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(ParseError::at_line(
+            idx + 1,
+            format!("unexpected character {c:?}"),
+        ));
+    }
+    // file ids are u16s
+    if line.len() > 2 * (u16::MAX as usize + 1) {
+        return Err(ParseError::at_line(
+            idx + 1,
+            format!("the disk map has more than {} files", u16::MAX as usize + 1),
+        ));
+    }
+    Ok(line.bytes().map(|c| c - b'0').collect())
+}
+
+pub fn parse_input(input: &str) -> Result<Disk> {
+    let digits = disk_map(input)?;
+    let length = digits.iter().map(|&size| size as usize).sum();
+    let mut ret = Vec::with_capacity(length);
+    for (id, sizes) in digits.chunks(2).enumerate() {
+        ret.extend(std::iter::repeat_n(Some(id as u16), sizes[0] as usize));
+        if let Some(&free_space) = sizes.get(1) {
+            ret.extend(std::iter::repeat_n(None, free_space as usize));
         }
     }
-    ret
+    Ok(ret)
 }
 
 pub fn compact_disk(disk: &Disk) -> Disk {
@@ -70,7 +97,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         let input = "2333133121414131402";
-        let disk = super::parse_input(input);
+        let disk = super::parse_input(input).unwrap();
         //assert_eq!(disk.len(), 20);
         let disk_str = super::disk2string(&disk);
         println!("{:?}", disk_str);
@@ -86,4 +113,16 @@ mod tests {
         println!("{checksum}");
         assert_eq!(checksum, 1928);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| super::parse_input(input).unwrap_err().to_string();
+        assert_eq!(super::parse_input("12345\n").unwrap().len(), 15);
+        assert_eq!(error("\n123x5"), "line 2: unexpected character 'x'");
+        assert_eq!(
+            error("12345\n\n678"),
+            "line 3: expected the disk map on a single line"
+        );
+        assert_eq!(error(" \n"), "the disk map is empty");
+    }
 }
@@ -6,6 +6,7 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+use aoc_common::Result;
 use stephen_morris_utils::pos;
 
 use crate::vector_linked_list::IDRef;
@@ -58,23 +59,14 @@ impl FileMap {
     }
 }
 
-pub fn parse_input(input: &str) -> FileMap {
-    let length = input.len();
-    let mut file_map = FileMap::with_capacity(length);
-    let mut id = 0_usize;
-    let mut bytes = input.bytes();
-
-    loop {
-        let blocks = (bytes.next().unwrap() - b'0') as BlocksSize;
-        if let Some(free_blocks) = bytes.next() {
-            let free_blocks = (free_blocks - b'0') as BlocksSize;
-            file_map.push(blocks, free_blocks);
-        } else {
-            file_map.push(blocks, 0);
-            break;
-        }
+pub fn parse_input(input: &str) -> Result<FileMap> {
+    let digits = crate::compact::disk_map(input)?;
+    let mut file_map = FileMap::with_capacity(digits.len());
+    for sizes in digits.chunks(2) {
+        let free_blocks = sizes.get(1).copied().unwrap_or(0);
+        file_map.push(sizes[0] as BlocksSize, free_blocks as BlocksSize);
     }
-    file_map
+    Ok(file_map)
 }
 
 pub fn compact_disk(file_map: &FileMap) -> FileMap {
@@ -146,7 +138,7 @@ mod tests {
     #[test]
     fn test_disk2string() {
         let input = "2333133121414131402";
-        let file_map = super::parse_input(input);
+        let file_map = super::parse_input(input).unwrap();
         println!("{:?}", file_map);
         let disk_str = super::disk2string(&file_map);
         println!("{:?}", disk_str);
@@ -155,7 +147,7 @@ mod tests {
     #[test]
     fn test_parse_input() {
         let input = "2333133121414131402";
-        let file_map = super::parse_input(input);
+        let file_map = super::parse_input(input).unwrap();
         //assert_eq!(disk.len(), 20);
         let disk_str = super::disk2string(&file_map);
         println!("{:?}", disk_str);
@@ -0,0 +1,37 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution};
+
+pub mod compact;
+pub mod compact2;
+pub mod vector_linked_list;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+#[allow(dead_code)]
+const TESTINPUT: &str = "2333133121414131402";
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    // part 1 moves blocks and part 2 moves whole files, so each has its own representation
+    type Parsed = (compact::Disk, compact2::FileMap);
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok((compact::parse_input(input)?, compact2::parse_input(input)?))
+    }
+
+    fn part1((disk, _): &Self::Parsed) -> Result<usize> {
+        Ok(compact::checksum(&compact::compact_disk(disk)))
+    }
+
+    fn part2((_, file_map): &Self::Parsed) -> Result<u64> {
+        Ok(compact2::checksum(&compact2::compact_disk(file_map)))
+    }
+}
@@ -1,18 +1,14 @@
-#![allow(dead_code, unused)]
+use aoc_common::input;
 use stephen_morris_utils::timer;
 
-use std::num::NonZeroU8;
-
-const INPUT: &str = include_str!("input.txt");
-#[allow(dead_code)]
-const TESTINPUT: &str = "2333133121414131402";
-
-mod compact;
-mod compact2;
-mod vector_linked_list;
+use day09::{EMBEDDED_INPUT, compact, compact2};
 
 fn main() {
-    let disk = timer::time(|| compact::parse_input(INPUT), "parse_input");
+    let input = input::load_from_args(9, EMBEDDED_INPUT);
+    let disk = timer::time(
+        || input::or_exit(compact::parse_input(&input)),
+        "parse_input",
+    );
     let compacted_disk = timer::time(|| compact::compact_disk(&disk), "compact_disk");
     let checksum = timer::time(|| compact::checksum(&compacted_disk), "checksum");
 
@@ -20,7 +16,10 @@ fn main() {
     compacted_disk.print_duration();
     checksum.print_all();
 
-    let file_map = timer::time(|| compact2::parse_input(INPUT), "parse_input");
+    let file_map = timer::time(
+        || input::or_exit(compact2::parse_input(&input)),
+        "parse_input",
+    );
     let compact_disk = timer::time(|| compact2::compact_disk(&file_map), "compact_disk");
     let checksum = timer::time(|| compact2::checksum(&compact_disk), "checksum");
 
@@ -38,6 +38,12 @@ pub enum LLError {
     IDInvalid(usize),
     SameID(usize),
 }
+impl<T> Default for Vll<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> Vll<T> {
     pub fn new() -> Self {
         Self {
@@ -144,10 +150,10 @@ impl<T> Vll<T> {
         self.detatch(id)?;
         self.attach_after(after, id)
     }
-    pub fn iter(&self) -> VLLIteratorRef<T> {
+    pub fn iter(&self) -> VLLIteratorRef<'_, T> {
         VLLIteratorRef::new(self)
     }
-    pub fn iter_rev(&self) -> VLLIteratorReverseRef<T> {
+    pub fn iter_rev(&self) -> VLLIteratorReverseRef<'_, T> {
         VLLIteratorReverseRef::new(self)
     }
 }
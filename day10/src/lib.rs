@@ -0,0 +1,39 @@
+use aoc_common::{Result, Solution, grid};
+
+pub mod trailheads;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+#[allow(dead_code)]
+const TESTINPUT: &str = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = grid::Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        trailheads::parse_input(input)
+    }
+
+    fn part1(grid: &Self::Parsed) -> Result<usize> {
+        Ok(trailheads::trailheads(grid))
+    }
+
+    fn part2(grid: &Self::Parsed) -> Result<usize> {
+        Ok(trailheads::trailheads2(grid))
+    }
+}
@@ -1,22 +1,15 @@
-use stephen_morris_utils::grid;
+use aoc_common::input;
 use stephen_morris_utils::timer;
-use trailheads::trailheads2;
 
-const INPUT: &str = include_str!("input.txt");
-#[allow(dead_code)]
-const TESTINPUT: &str = "89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732";
-
-mod trailheads;
+use day10::EMBEDDED_INPUT;
+use day10::trailheads::{self, trailheads2};
 
 fn main() {
-    let grid = timer::time(|| trailheads::parse_input(INPUT), "parse_input");
+    let input = input::load_from_args(10, EMBEDDED_INPUT);
+    let grid = timer::time(
+        || input::or_exit(trailheads::parse_input(&input)),
+        "parse_input",
+    );
     let trailheads = timer::time(|| trailheads::trailheads(&grid), "trailheads");
 
     grid.print_duration();
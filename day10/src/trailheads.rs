@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 
 use crate::grid::{Grid, Point};
-pub fn parse_input(input: &str) -> Grid<u8> {
-    Grid::from(input)
+use aoc_common::Result;
+
+// heights, with . for ground that can't be walked on
+pub fn parse_input(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(input, |byte| byte == b'.' || byte.is_ascii_digit())
 }
 type Count = usize;
 pub fn trailheads(grid: &Grid<u8>) -> Count {
@@ -56,13 +59,21 @@ fn find_heads2(point: Point, v: u8, grid: &Grid<u8>) -> Count {
 
 #[cfg(test)]
 mod tests {
-    use stephen_morris_utils::grid::Point;
+    use aoc_common::grid::Point;
 
     #[test]
     fn test_part2() {
-        let grid = super::parse_input(crate::TESTINPUT);
+        let grid = super::parse_input(crate::TESTINPUT).unwrap();
         assert_eq!(super::trailheads2(&grid), 81);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            super::parse_input("0123\n12a4").unwrap_err().to_string(),
+            "line 2: unexpected character 'a'"
+        );
+    }
     #[test]
     fn test1() {
         test_find_heads(
@@ -118,7 +129,7 @@ mod tests {
     }
 
     fn test_find_heads(input: &str) {
-        let grid = super::parse_input(input);
+        let grid = super::parse_input(input).unwrap();
         for y in 0..grid.height {
             for x in 0..grid.width {
                 print!("{}", *grid.get(Point { x, y }) as char);
@@ -0,0 +1,35 @@
+use aoc_common::{Result, Solution};
+use std::collections::HashMap;
+
+pub mod stones;
+
+//const INPUT: &str = include_str!("input.txt");
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = "0 4 4979 24 4356119 914 85734 698829";
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+#[allow(dead_code)]
+const TESTINPUT: &str = "125 17";
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Vec<u64>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        stones::parse_input(input)
+    }
+
+    fn part1(stones: &Self::Parsed) -> Result<usize> {
+        Ok(stones::f_list(25, stones, &mut HashMap::new()))
+    }
+
+    fn part2(stones: &Self::Parsed) -> Result<usize> {
+        Ok(stones::f_list(75, stones, &mut HashMap::new()))
+    }
+}
@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 
-pub fn parse_input(input: &str) -> Vec<u64> {
-    input
-        .split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect()
+use aoc_common::{Result, parse};
+
+pub fn parse_input(input: &str) -> Result<Vec<u64>> {
+    let lines = parse::lines(input, |line| parse::numbers(line, ' '))?;
+    Ok(lines.concat())
 }
 
 pub fn g(n: u64) -> Vec<u64> {
@@ -13,7 +13,7 @@ pub fn g(n: u64) -> Vec<u64> {
     } else {
         let s = n.to_string();
         let l = s.len();
-        if l % 2 == 0 {
+        if l.is_multiple_of(2) {
             vec![s[0..l / 2].parse().unwrap(), (s[l / 2..]).parse().unwrap()]
         } else {
             vec![n * 2024]
@@ -59,12 +59,21 @@ mod tests {
     #[test]
     fn test_test_input() {
         let mut fhash = HashMap::new();
-        let v = parse_input(crate::TESTINPUT);
+        let v = parse_input(crate::TESTINPUT).unwrap();
         let f = f_list(6, &v, &mut fhash);
         assert_eq!(f, 22);
         let f = f_list(25, &v, &mut fhash);
         assert_eq!(f, 55312);
     }
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_input("125 17\n3").unwrap(), [125, 17, 3]);
+        assert_eq!(
+            parse_input("125 -17").unwrap_err().to_string(),
+            "line 1: invalid number '-17'"
+        );
+    }
+
     #[test]
     fn test_g() {
         assert_eq!(g(0), vec![1]);
@@ -0,0 +1,44 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution, grid};
+
+pub mod region;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = region::RegionMap;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        region::RegionMap::parse(input)
+    }
+
+    fn part1(region_map: &Self::Parsed) -> Result<u32> {
+        Ok(region_map.price())
+    }
+
+    fn part2(region_map: &Self::Parsed) -> Result<u32> {
+        Ok(region_map.price_from_sides())
+    }
+}
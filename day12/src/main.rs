@@ -1,27 +1,14 @@
-#![allow(dead_code, unused)]
-
+use aoc_common::input;
 use stephen_morris_utils::timer;
 
-const INPUT: &str = include_str!("input.txt");
-
-#[allow(dead_code)]
-const TESTINPUT: &str = "RRRRIICCFF
-RRRRIICCCF
-VVRRRCCFFF
-VVRCCCJFFF
-VVVVCJJCFE
-VVIVCCJJEE
-VVIIICJJEE
-MIIIIIJJEE
-MIIISIJEEE
-MMMISSJEEE";
-
-//mod grid;
-mod grid;
-mod region;
+use day12::{EMBEDDED_INPUT, region};
 
 fn main() {
-    let mut region_map = timer::time(|| region::RegionMap::new(INPUT), "Initialise region map");
+    let input = input::load_from_args(12, EMBEDDED_INPUT);
+    let region_map = timer::time(
+        || input::or_exit(region::RegionMap::parse(&input)),
+        "Initialise region map",
+    );
     let price = timer::time(|| region_map.price(), "Calculate price");
     let price_from_sides = timer::time(
         || region_map.price_from_sides(),
@@ -1,6 +1,7 @@
 #![allow(unused)]
 use crate::grid;
 use crate::grid::{Direction, Grid, Point, Vector};
+use aoc_common::Result;
 
 type RegionID = usize;
 type Count = u32;
@@ -20,7 +21,11 @@ struct RegionCounts {
 
 impl RegionMap {
     pub fn new(input: &str) -> RegionMap {
-        let grid: Grid<u8> = input.into();
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn parse(input: &str) -> Result<RegionMap> {
+        let grid = Grid::parse(input, |byte| byte.is_ascii_graphic())?;
         let regions: Grid<Option<RegionID>> = Grid::new_default(grid.width, grid.height);
 
         let mut region_map = RegionMap {
@@ -29,7 +34,7 @@ impl RegionMap {
             no_regions: 0,
         };
         region_map.initialise_regions();
-        region_map
+        Ok(region_map)
     }
 
     pub fn initialise_regions(&mut self) {
@@ -263,4 +268,12 @@ mod tests {
 
         assert_eq!(price, 8 * 16 + 4);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            RegionMap::parse("AAB\nA B").unwrap_err().to_string(),
+            "line 2: unexpected character ' '"
+        );
+    }
 }
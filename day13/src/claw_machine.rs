@@ -1,3 +1,8 @@
+use aoc_common::{
+    parse::{self, LineResult},
+    ParseError, Result,
+};
+
 type Distance = i64;
 type Cost = u64;
 
@@ -31,40 +36,73 @@ impl ClawMachine {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<ClawMachine> {
-    fn extract_integers(line: &str) -> (Distance, Distance) {
-        let mut v = Vec::new();
-        let mut curr = 0;
-        for c in line.chars() {
-            if let Some(d) = c.to_digit(10) {
-                curr = 10 * curr + d;
-            } else if curr > 0 {
-                v.push(curr as Distance);
-                curr = 0;
-            }
-        }
-        if curr > 0 {
-            v.push(curr as Distance);
-        }
-        (v[0], v[1])
+// the label of each line of a machine, and the sign before its numbers
+const LINES: [(&str, char); 3] = [("Button A", '+'), ("Button B", '+'), ("Prize", '=')];
+
+fn coordinates(line: &str, (label, sign): (&str, char)) -> LineResult<(Distance, Distance)> {
+    let format = || format!("expected '{label}: X{sign}<number>, Y{sign}<number>'");
+    let (x, y) = line
+        .trim()
+        .strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .and_then(|rest| rest.split_once(','))
+        .ok_or_else(format)?;
+    let x = x
+        .trim()
+        .strip_prefix('X')
+        .and_then(|x| x.strip_prefix(sign));
+    let y = y
+        .trim()
+        .strip_prefix('Y')
+        .and_then(|y| y.strip_prefix(sign));
+    match (x, y) {
+        (Some(x), Some(y)) => Ok((parse::number(x)?, parse::number(y)?)),
+        _ => Err(format()),
     }
-    input
-        .split("\n\n")
-        .map(|s| {
-            let mut lines = s.lines();
-            let (ax, ay) = extract_integers(lines.next().unwrap());
-            let (bx, by) = extract_integers(lines.next().unwrap());
-            let (px, py) = extract_integers(lines.next().unwrap());
-            let px = px + 10000000000000;
-            let py = py + 10000000000000;
-            ClawMachine {
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<ClawMachine>> {
+    let lines: Vec<(usize, &str)> = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+    lines
+        .chunks(3)
+        .map(|machine| {
+            let mut values = [(0, 0); 3];
+            for (idx, &(label, sign)) in LINES.iter().enumerate() {
+                let Some(&(line_idx, line)) = machine.get(idx) else {
+                    return Err(ParseError::at_line(
+                        input.lines().count() + 1,
+                        format!("expected '{label}: X{sign}<number>, Y{sign}<number>'"),
+                    ));
+                };
+                values[idx] = coordinates(line, (label, sign))
+                    .map_err(|message| ParseError::at_line(line_idx + 1, message))?;
+            }
+            let [(ax, ay), (bx, by), (px, py)] = values;
+            Ok(ClawMachine {
                 ax,
                 ay,
                 bx,
                 by,
                 px,
                 py,
-            }
+            })
+        })
+        .collect()
+}
+
+// part 2 moves every prize this far along both axes
+const PRIZE_OFFSET: Distance = 10000000000000;
+
+pub fn with_prize_offset(v: &[ClawMachine]) -> Vec<ClawMachine> {
+    v.iter()
+        .map(|c| ClawMachine {
+            px: c.px + PRIZE_OFFSET,
+            py: c.py + PRIZE_OFFSET,
+            ..*c
         })
         .collect()
 }
@@ -77,12 +115,30 @@ pub fn cost(v: &[ClawMachine]) -> Cost {
 mod tests {
     #[test]
     fn test_cost() {
-        let v = super::parse_input(crate::TESTINPUT);
+        let v = super::parse_input(crate::TESTINPUT).unwrap();
         for c in &v {
             println!("cost: {:?}", c.cost());
         }
-        //assert_eq!(super::cost(&v), 480);
-        //assert_eq!(super::cost(&v),);
-        println!("cost: {}", super::cost(&v));
+        assert_eq!(super::cost(&v), 480);
+        println!("cost: {}", super::cost(&super::with_prize_offset(&v)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| super::parse_input(input).unwrap_err().to_string();
+        let machine = super::parse_input("Button A: X+0, Y+3\nButton B: X+2, Y+1\nPrize: X=4, Y=5");
+        assert_eq!(machine.unwrap()[0].ax, 0);
+        assert_eq!(
+            error("Button A: X+1, Y+3\nButton B: X+2, Y=1\nPrize: X=4, Y=5"),
+            "line 2: expected 'Button B: X+<number>, Y+<number>'"
+        );
+        assert_eq!(
+            error("Button A: X+1, Y+3\nButton B: X+2, Y+1\nPrize: X=4, Y=x"),
+            "line 3: invalid number 'x'"
+        );
+        assert_eq!(
+            error("Button A: X+1, Y+3\nButton B: X+2, Y+1\n"),
+            "line 3: expected 'Prize: X=<number>, Y=<number>'"
+        );
     }
 }
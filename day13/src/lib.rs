@@ -0,0 +1,51 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution};
+
+pub mod claw_machine;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Vec<claw_machine::ClawMachine>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        claw_machine::parse_input(input)
+    }
+
+    fn part1(machines: &Self::Parsed) -> Result<u64> {
+        Ok(claw_machine::cost(machines))
+    }
+
+    fn part2(machines: &Self::Parsed) -> Result<u64> {
+        Ok(claw_machine::cost(&claw_machine::with_prize_offset(
+            machines,
+        )))
+    }
+}
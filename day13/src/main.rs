@@ -1,32 +1,21 @@
-#![allow(dead_code, unused)]
-
+use aoc_common::input;
 use stephen_morris_utils::timer;
 
-const INPUT: &str = include_str!("input.txt");
-
-#[allow(dead_code)]
-const TESTINPUT: &str = "Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
-
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
-
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
-
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279";
-
-mod claw_machine;
+use day13::{claw_machine, EMBEDDED_INPUT};
 
 fn main() {
-    let v = timer::time(|| claw_machine::parse_input(INPUT), "parse_input");
+    let input = input::load_from_args(13, EMBEDDED_INPUT);
+    let v = timer::time(
+        || input::or_exit(claw_machine::parse_input(&input)),
+        "parse_input",
+    );
     let cost = timer::time(|| claw_machine::cost(&v), "cost");
+    let offset_cost = timer::time(
+        || claw_machine::cost(&claw_machine::with_prize_offset(&v)),
+        "offset_cost",
+    );
 
     v.print_duration();
     cost.print_all();
+    offset_cost.print_all();
 }
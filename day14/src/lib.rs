@@ -0,0 +1,46 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution, grid};
+
+pub mod restroom;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = restroom::Restroom;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        restroom::Restroom::parse(input)
+    }
+
+    fn part1(restroom: &Self::Parsed) -> Result<usize> {
+        Ok(restroom.safety_factor_at_time(100))
+    }
+
+    fn part2(restroom: &Self::Parsed) -> Result<usize> {
+        Ok(restroom.clone().minimize_safety_factor(10000))
+    }
+}
@@ -1,28 +1,14 @@
-#![allow(dead_code, unused)]
-
+use aoc_common::input;
 use stephen_morris_utils::timer;
 
-const INPUT: &str = include_str!("input.txt");
-
-#[allow(dead_code)]
-const TESTINPUT: &str = "p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3";
-
-mod grid;
-mod restroom;
+use day14::{EMBEDDED_INPUT, restroom};
 
 fn main() {
-    let mut restroom = timer::time(|| restroom::Restroom::new(INPUT), "Parsing input");
+    let input = input::load_from_args(14, EMBEDDED_INPUT);
+    let restroom = timer::time(
+        || input::or_exit(restroom::Restroom::parse(&input)),
+        "Parsing input",
+    );
     let safety_factor = timer::time(
         || restroom.safety_factor_at_time(100),
         "Calculating safety factor",
@@ -13,7 +13,10 @@ p=9,5 v=-3,-3";
 
 use crate::grid;
 use crate::grid::{Grid, Point, Vector};
-use stephen_morris_utils::get_numbers;
+use aoc_common::{
+    Result,
+    parse::{self, LineResult},
+};
 
 #[cfg(test)]
 mod config {
@@ -58,21 +61,37 @@ impl Robot {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Robot> {
-    input
-        .lines()
-        .map(|line| {
-            let v = get_numbers(line);
+fn parse_robot(line: &str) -> LineResult<Robot> {
+    let format = || "expected 'p=<x>,<y> v=<x>,<y>'".to_string();
+    let pair = |word: Option<&str>, prefix: &str| -> LineResult<(isize, isize)> {
+        let (x, y) = word
+            .and_then(|word| word.strip_prefix(prefix))
+            .and_then(|word| word.split_once(','))
+            .ok_or_else(format)?;
+        Ok((parse::number(x)?, parse::number(y)?))
+    };
+    let mut words = line.split_whitespace();
+    let (x, y) = pair(words.next(), "p=")?;
+    let (dx, dy) = pair(words.next(), "v=")?;
+    if words.next().is_some() {
+        return Err(format());
+    }
+    if !(0..WIDTH as isize).contains(&x) || !(0..HEIGHT as isize).contains(&y) {
+        return Err(format!(
+            "p={x},{y} is outside the {WIDTH} by {HEIGHT} restroom"
+        ));
+    }
+    Ok(Robot {
+        position: Point {
+            x: x as usize,
+            y: y as usize,
+        },
+        velocity: Vector { x: dx, y: dy },
+    })
+}
 
-            Robot {
-                position: Point {
-                    x: v[0] as usize,
-                    y: v[1] as usize,
-                },
-                velocity: Vector { x: v[2], y: v[3] },
-            }
-        })
-        .collect()
+pub fn parse_input(input: &str) -> Result<Vec<Robot>> {
+    parse::lines(input, parse_robot)
 }
 
 #[derive(Clone, Debug)]
@@ -82,9 +101,13 @@ pub struct Restroom {
 
 impl Restroom {
     pub fn new(input: &str) -> Self {
-        Restroom {
-            robots: parse_input(input),
-        }
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        Ok(Restroom {
+            robots: parse_input(input)?,
+        })
     }
 
     pub fn move_robots(&mut self, seconds: usize) -> &Self {
@@ -155,7 +178,7 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let robots = parse_input(TESTINPUT);
+        let robots = parse_input(TESTINPUT).unwrap();
         assert_eq!(robots.len(), 12);
         assert_eq!(
             robots[0],
@@ -207,4 +230,18 @@ mod tests {
 
         assert_eq!(restroom.safety_factor_at_time(100), 12);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Restroom::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("p=0,4 v=3,-3\np=0,4 v=3"),
+            "line 2: expected 'p=<x>,<y> v=<x>,<y>'"
+        );
+        assert_eq!(error("p=0,4 v=3,x"), "line 1: invalid number 'x'");
+        assert_eq!(
+            error("p=11,4 v=3,-3"),
+            "line 1: p=11,4 is outside the 11 by 7 restroom"
+        );
+    }
 }
@@ -34,10 +34,39 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
 use super::grid::{Direction, Grid, Point, Vector};
+use aoc_common::{ParseError, Result, parse};
+
+/// The warehouse and the robot's moves. The warehouse holds one robot and is surrounded by
+/// walls, so nothing can be pushed out of it.
+pub fn parse_input(input: &str) -> Result<(Grid<u8>, Vec<Direction>)> {
+    let (grid, steps, steps_offset) = parse::sections(input)?;
+    let grid = Grid::parse(grid, |byte| b"#.O@".contains(&byte))?;
+    for (point, &cell) in &grid {
+        let edge =
+            point.x == 0 || point.y == 0 || point.x == grid.width - 1 || point.y == grid.height - 1;
+        if edge && cell != b'#' {
+            return Err(ParseError::at_line(
+                point.y + 1,
+                "the warehouse must be surrounded by walls",
+            ));
+        }
+    }
+    grid.find_one(b'@', "robot in the warehouse")?;
+    let mut moves = Vec::new();
+    for (idx, line) in steps.lines().enumerate() {
+        for c in line.chars().filter(|c| !c.is_whitespace()) {
+            let direction = Direction::try_from_char(c).ok_or_else(|| {
+                ParseError::at_line(steps_offset + idx + 1, format!("unexpected move {c:?}"))
+            })?;
+            moves.push(direction);
+        }
+    }
+    Ok((grid, moves))
+}
 
 type Count = usize;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Lanternfish {
     pub grid: Grid<u8>,
     pub robot: Point,
@@ -47,21 +76,18 @@ pub struct Lanternfish {
 
 impl Lanternfish {
     pub fn new(input: &str) -> Self {
-        let mut split = input.split("\n\n");
-        if let (Some(grid), Some(steps)) = (split.next(), split.next()) {
-            let grid = grid.trim();
-            let grid: Grid<u8> = grid.into();
-            let steps = steps.chars().filter_map(Direction::try_from_char).collect();
-            let robot = grid.find(b'@').unwrap();
-            Self {
-                grid,
-                robot,
-                steps,
-                step_count: 0,
-            }
-        } else {
-            panic!("Lanternfish::new() could not split the input.");
-        }
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let (grid, steps) = parse_input(input)?;
+        let robot = grid.find(b'@').unwrap();
+        Ok(Self {
+            grid,
+            robot,
+            steps,
+            step_count: 0,
+        })
     }
 
     pub fn move_cell(&mut self, cell: Point, direction: Direction) -> bool {
@@ -125,6 +151,34 @@ mod tests {
         assert_eq!(lanternfish.steps.len(), 15);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Lanternfish::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            Lanternfish::parse("####\r\n#@O#\r\n####\r\n\r\n<>\r\n^v\r\n")
+                .unwrap()
+                .steps
+                .len(),
+            4
+        );
+        assert_eq!(
+            error("####\n#@O#\n####\n\n<>\n^x"),
+            "line 6: unexpected move 'x'"
+        );
+        assert_eq!(
+            error("####\n#@O.\n####\n\n<"),
+            "line 2: the warehouse must be surrounded by walls"
+        );
+        assert_eq!(
+            error("####\n#@@#\n####\n\n<"),
+            "expected one robot in the warehouse, found 2"
+        );
+        assert_eq!(
+            error("####\n#@O#\n####\n<"),
+            "expected a blank line between the two sections"
+        );
+    }
+
     #[test]
     fn test_lanternfish_move_cell() {
         let mut lanternfish = Lanternfish::new(TESTINPUT);
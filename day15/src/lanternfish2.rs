@@ -1,10 +1,10 @@
-use core::panic;
-
 use super::grid::{Direction, Grid, Point, Vector};
+use crate::lanternfish::parse_input;
+use aoc_common::Result;
 
 type Count = usize;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Lanternfish {
     pub grid: Grid<u8>,
     pub robot: Point,
@@ -14,36 +14,32 @@ pub struct Lanternfish {
 
 impl Lanternfish {
     pub fn new(input: &str) -> Self {
-        let mut split = input.split("\n\n");
-        if let (Some(grid), Some(steps)) = (split.next(), split.next()) {
-            let grid = grid.trim();
-            let grid = grid.bytes().fold(Vec::new(), |mut acc, c| {
-                acc.extend(
-                    match c {
-                        b'#' => "##",
-                        b'O' => "[]",
-                        b'@' => "@.",
-                        b'.' => "..",
-                        b'\n' => "\n",
-                        _ => panic!("Lanternfish::new() could not parse the grid."),
-                    }
-                    .bytes(),
-                );
-                acc
-            });
-            let grid: Grid<u8> = (&grid[..]).into();
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
 
-            let steps = steps.chars().filter_map(Direction::try_from_char).collect();
-            let robot = grid.find(b'@').unwrap();
-            Self {
-                grid,
-                robot,
-                steps,
-                step_count: 0,
-            }
-        } else {
-            panic!("Lanternfish::new() could not split the input.");
-        }
+    pub fn parse(input: &str) -> Result<Self> {
+        let (grid, steps) = parse_input(input)?;
+        // every cell is twice as wide, and a box takes both halves
+        let grid: Vec<Vec<u8>> = (0..grid.height)
+            .map(|y| {
+                (0..grid.width)
+                    .flat_map(|x| match grid[Point::new(x, y)] {
+                        b'#' => *b"##",
+                        b'O' => *b"[]",
+                        b'@' => *b"@.",
+                        _ => *b"..",
+                    })
+                    .collect()
+            })
+            .collect();
+        let grid = Grid::from(grid);
+        let robot = grid.find(b'@').unwrap();
+        Ok(Self {
+            grid,
+            robot,
+            steps,
+            step_count: 0,
+        })
     }
 
     pub fn move_cell_horizontal(&mut self, this_cell_point: Point, direction: Direction) -> bool {
@@ -0,0 +1,56 @@
+//#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution, grid};
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+
+// mod grid;
+pub mod lanternfish;
+pub mod lanternfish2;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    // part 2 doubles the width of the warehouse, so it is parsed separately
+    type Parsed = (lanternfish::Lanternfish, lanternfish2::Lanternfish);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok((
+            lanternfish::Lanternfish::parse(input)?,
+            lanternfish2::Lanternfish::parse(input)?,
+        ))
+    }
+
+    fn part1((lanternfish, _): &Self::Parsed) -> Result<usize> {
+        let mut lanternfish = lanternfish.clone();
+        lanternfish.move_robot_fully();
+        Ok(lanternfish.gps_sum())
+    }
+
+    fn part2((_, lanternfish): &Self::Parsed) -> Result<usize> {
+        let mut lanternfish = lanternfish.clone();
+        lanternfish.move_robot_fully();
+        Ok(lanternfish.gps_sum())
+    }
+}
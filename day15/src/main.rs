@@ -1,30 +1,14 @@
-//#![allow(dead_code, unused)]
-
-use stephen_morris_utils::grid;
+use aoc_common::input;
 use stephen_morris_utils::timer;
 
-const INPUT: &str = include_str!("input.txt");
-
-#[allow(dead_code)]
-const TESTINPUT: &str = "p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3";
-
-// mod grid;
-mod lanternfish;
-mod lanternfish2;
+use day15::{EMBEDDED_INPUT, lanternfish, lanternfish2};
 
 fn main() {
-    let mut lanternfish = timer::time(|| lanternfish::Lanternfish::new(INPUT), "Lanternfish::new");
+    let input = input::load_from_args(15, EMBEDDED_INPUT);
+    let mut lanternfish = timer::time(
+        || input::or_exit(lanternfish::Lanternfish::parse(&input)),
+        "Lanternfish::new",
+    );
     let gps_sum = timer::time(
         || {
             lanternfish.move_robot_fully();
@@ -36,7 +20,10 @@ fn main() {
     lanternfish.print_duration();
     gps_sum.print_all();
 
-    let mut lanternfish = timer::time(|| lanternfish2::Lanternfish::new(INPUT), "Lanternfish::new");
+    let mut lanternfish = timer::time(
+        || input::or_exit(lanternfish2::Lanternfish::parse(&input)),
+        "Lanternfish::new",
+    );
     let gps_sum = timer::time(
         || {
             lanternfish.move_robot_fully();
@@ -0,0 +1,96 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution, grid};
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+const TESTINPUT2: &str = "#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################";
+
+pub mod maze;
+use maze::Maze;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed = Maze;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Maze::parse(input)
+    }
+
+    fn part1(maze: &Self::Parsed) -> Result<u32> {
+        Ok(maze.lowest_score())
+    }
+
+    fn part2(maze: &Self::Parsed) -> Result<u32> {
+        Ok(maze.cells_on_optimal_path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let res = maze::part1(TESTINPUT);
+        assert_eq!(res, 7036);
+    }
+
+    #[test]
+    fn test2() {
+        let res = maze::part1(TESTINPUT2);
+        assert_eq!(res, 11048);
+    }
+
+    #[test]
+    fn test3() {
+        let res = maze::part2(TESTINPUT);
+        assert_eq!(res, 45);
+    }
+    #[test]
+    fn test4() {
+        let res = maze::part2(TESTINPUT2);
+        assert_eq!(res, 64);
+    }
+}
@@ -1,81 +1,23 @@
-#![allow(dead_code, unused)]
-
-use stephen_morris_utils::grid;
+use aoc_common::input;
 use stephen_morris_utils::timer;
 
-const INPUT: &str = include_str!("input.txt");
-
-#[allow(dead_code)]
-const TESTINPUT: &str = "###############
-#.......#....E#
-#.#.###.#.###.#
-#.....#.#...#.#
-#.###.#####.#.#
-#.#.#.......#.#
-#.#.#####.###.#
-#...........#.#
-###.#.#####.#.#
-#...#.....#.#.#
-#.#.#.###.#.#.#
-#.....#...#.#.#
-#.###.#.#.#.#.#
-#S..#.....#...#
-###############";
-
-const TESTINPUT2: &str = "#################
-#...#...#...#..E#
-#.#.#.#.#.#.#.#.#
-#.#.#.#...#...#.#
-#.#.#.#.###.#.#.#
-#...#.#.#.....#.#
-#.#.#.#.#.#####.#
-#.#...#.#.#.....#
-#.#.#####.#.###.#
-#.#.#.......#...#
-#.#.###.#####.###
-#.#.#...#.....#.#
-#.#.#.#####.###.#
-#.#.#.........#.#
-#.#.#.#########.#
-#S#.............#
-#################";
-
-mod maze;
+use day16::{EMBEDDED_INPUT, maze};
 
 fn main() {
-    let mut maze = timer::time(|| maze::Maze::new(INPUT), "Maze::new");
-    let res = timer::time(|| maze.find_best_score(), "Maze::minimum_score");
+    let input = input::load_from_args(16, EMBEDDED_INPUT);
+    let maze = timer::time(|| input::or_exit(maze::Maze::parse(&input)), "Maze::new");
+    let res = timer::time(|| maze.lowest_score(), "Maze::lowest_score");
     let best_paths = timer::time(|| maze.cells_on_optimal_path(), "find_best_paths");
+    let paths = timer::time(|| maze.optimal_paths(), "Maze::optimal_paths");
 
     maze.print_duration();
     res.print_all();
     best_paths.print_all();
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+    paths.print_duration();
 
-    #[test]
-    fn test1() {
-        let res = maze::part1(TESTINPUT);
-        assert_eq!(res, 7036);
-    }
-
-    #[test]
-    fn test2() {
-        let res = maze::part1(TESTINPUT2);
-        assert_eq!(res, 11048);
-    }
-
-    #[test]
-    fn test3() {
-        let res = maze::part2(TESTINPUT);
-        assert_eq!(res, 45);
-    }
-    #[test]
-    fn test4() {
-        let res = maze::part2(TESTINPUT2);
-        assert_eq!(res, 64);
+    println!("{} optimal paths", paths.len());
+    if let Some(path) = paths.first() {
+        println!("score {}", maze.score(path));
+        maze.render(path).print();
     }
 }
@@ -1,289 +1,363 @@
-use std::{
-    fmt::Display,
-    ops::{Deref, DerefMut},
-    slice::SliceIndex,
-    thread::current,
+use std::{collections::HashSet, fmt::Display};
+
+use aoc_common::{
+    ParseError, Result,
+    search::{self, AllPaths},
 };
 
-use crate::grid::{Direction, Grid, Point, Vector, EAST, NORTH, SOUTH, WEST};
+use crate::grid::{Direction, EAST, Grid, Point};
 
 type Count = u32;
 
+/// A position in the maze and the way the reindeer is facing.
+pub type State = (Point, Direction);
+
 pub fn part1(input: &str) -> Count {
-    Maze::new(input).find_best_score()
+    Maze::new(input).lowest_score()
 }
 pub fn part2(input: &str) -> Count {
     Maze::new(input).cells_on_optimal_path()
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
-enum FindPathStatus {
-    #[default]
-    Ready,
-    FindPathNeeded,
-    EqualPath(Option<Count>),
-}
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
-struct CellDirectionPath {
-    status: FindPathStatus,
-    best_from_start: Option<Count>,
-    best_to_end: Option<Count>,
+/// What each move costs. The puzzle charges 1 per step forward and 1000 per quarter turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostModel {
+    step: Count,
+    turn: Count,
+    reverse: Option<Count>,
+    tile_weights: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum PathError {
-    LoopDetected,
-    TotalExceeded,
-    NoValidPath,
-}
-impl CellDirectionPath {
-    fn touch(&mut self, mut new_from_start: Count, best_total: Count) -> Result<(), PathError> {
-        if self.on_loop() {
-            unreachable!() // should have been caught at the start of find path
-                           // return Err(PathError::LoopDetected);
-        }
-        if new_from_start >= best_total {
-            return Err(PathError::TotalExceeded);
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            step: 1,
+            turn: 1000,
+            reverse: None,
+            tile_weights: false,
         }
-        match self.best_from_start {
-            Some(current_best_from_start) => match current_best_from_start.cmp(&new_from_start) {
-                std::cmp::Ordering::Less => {
-                    self.status = FindPathStatus::Ready;
-                }
-                std::cmp::Ordering::Equal => {
-                    self.status = FindPathStatus::EqualPath(self.best_to_end);
-                }
-                std::cmp::Ordering::Greater => {
-                    self.best_from_start = Some(new_from_start);
-                    self.status = FindPathStatus::FindPathNeeded;
-                }
-            },
-            None => {
-                self.best_from_start = Some(new_from_start);
-                self.status = FindPathStatus::FindPathNeeded;
-            }
-        }
-        Ok(())
-    }
-    fn on_loop(&self) -> bool {
-        self.status != FindPathStatus::Ready
     }
-    fn try_get_total(&self) -> Option<Count> {
-        Some(self.best_from_start? + self.best_to_end?)
+}
+
+impl CostModel {
+    pub fn new() -> Self {
+        Self::default()
     }
-    fn path_found(&mut self, new_to_end: Count) {
-        if self
-            .best_to_end
-            .is_none_or(|best_to_end| best_to_end > new_to_end)
-        {
-            self.best_to_end = Some(new_to_end);
-        }
+
+    pub fn step(mut self, step: Count) -> Self {
+        self.step = step;
+        self
     }
-}
 
-impl Display for CellDirectionPaths {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (i, cdp) in self.paths.iter().enumerate() {
-            writeln!(
-                f,
-                "{:} {:6} {:6}",
-                char::from(Direction::from(i)),
-                match cdp.best_from_start {
-                    Some(count) => count.to_string(),
-                    None => "None".to_string(),
-                },
-                match cdp.best_to_end {
-                    Some(count) => count.to_string(),
-                    None => "None".to_string(),
-                }
-            )?;
-        }
-        Ok(())
+    pub fn turn(mut self, turn: Count) -> Self {
+        self.turn = turn;
+        self
     }
-}
 
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
-struct CellDirectionPaths {
-    paths: [CellDirectionPath; 4],
-}
+    /// Allows turning around in a single move. Without it, turning around takes two turns.
+    pub fn reverse(mut self, reverse: Count) -> Self {
+        self.reverse = Some(reverse);
+        self
+    }
 
-impl Deref for CellDirectionPaths {
-    type Target = [CellDirectionPath];
-    fn deref(&self) -> &Self::Target {
-        &self.paths
+    /// Makes stepping onto a digit tile cost the digit's value in place of the step cost.
+    pub fn tile_weights(mut self, tile_weights: bool) -> Self {
+        self.tile_weights = tile_weights;
+        self
     }
 }
 
-impl DerefMut for CellDirectionPaths {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.paths
-    }
+/// The moves on a path and their total cost.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Score {
+    pub steps: Count,
+    pub turns: Count,
+    pub reversals: Count,
+    pub total: Count,
 }
 
-impl CellDirectionPaths {
-    // update the start_from values when we first consider a cell
-    fn on_loop(&mut self) -> bool {
-        self.paths.iter().any(|&cdp| cdp.on_loop())
-    }
-    fn best_total(&self) -> Option<Count> {
-        self.paths
-            .iter()
-            .filter_map(CellDirectionPath::try_get_total)
-            .min()
-    }
-    fn print(&self) {
-        for (i, cdp) in self.paths.iter().enumerate() {
-            println!(
-                "{:?} {:?} {:?} {:?}",
-                char::from(Direction::from(i)),
-                cdp.status,
-                cdp.best_from_start,
-                cdp.best_to_end
-            );
-        }
+impl Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} for {} steps, {} turns and {} reversals",
+            self.total, self.steps, self.turns, self.reversals
+        )
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Maze {
     maze: Grid<u8>,
-    counts: Grid<CellDirectionPaths>,
-    best_path: Grid<bool>,
     start: Point,
     end: Point,
+    costs: CostModel,
 }
 
 impl Maze {
     pub fn new(input: &str) -> Self {
-        let maze: Grid<u8> = input.into();
-        let counts = Grid::new_default(maze.width, maze.height);
-        let best_path = Grid::new(maze.width, maze.height, false);
-        let start = maze.find(b'S').unwrap();
-        let end = maze.find(b'E').unwrap();
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Reads a maze of walls `#`, open tiles `.` or digit weights, one start `S` and one end `E`.
+    pub fn parse(input: &str) -> Result<Self> {
+        let maze = Grid::parse(input, |byte| {
+            b"#.SE".contains(&byte) || byte.is_ascii_digit()
+        })?;
+        let start = maze.find_one(b'S', "start S in the maze")?;
+        let end = maze.find_one(b'E', "end E in the maze")?;
 
-        Maze {
+        Ok(Maze {
             maze,
-            counts,
-            best_path,
             start,
             end,
-        }
+            costs: CostModel::default(),
+        })
     }
 
-    pub fn find_best_score(&mut self) -> Count {
-        fn find_path(
-            maze: &Grid<u8>,
-            counts: &mut Grid<CellDirectionPaths>,
-            current_cell: Point,
-            current_direction: Direction,
-            from_start: Count,
-            mut best_total: Count,
-        ) -> Result<Count, PathError> {
-            if maze[current_cell] == b'E' {
-                return Ok(0);
-            }
-            if from_start >= best_total {
-                return Err(PathError::TotalExceeded);
-            }
-            if counts[current_cell].on_loop() {
-                return Err(PathError::LoopDetected);
-            }
-            let directions = [
-                (current_direction, 0),
-                (current_direction.left(), 1000),
-                (current_direction.right(), 1000),
-            ];
-            for (new_direction, turn) in directions {
-                if let Some(next_cell) = maze.add_vector(current_cell, Vector::from(new_direction))
-                {
-                    match maze[next_cell] {
-                        b'.' | b'E' => {
-                            // toucn() updates the best from_start and marks the cell/direction for a recursive call to find_path
-                            counts[current_cell][new_direction as usize]
-                                .touch(from_start + turn, best_total);
-                        }
-                        b'S' => return Err(PathError::LoopDetected),
-                        b'#' => {}
-                        _ => panic!("find_best_path: unkown value found in grid"),
-                    }
-                }
-            }
+    pub fn with_costs(mut self, costs: CostModel) -> Self {
+        self.costs = costs;
+        self
+    }
 
-            let mut best_to_end = Count::MAX;
-            let mut path_found = false;
-            for (new_direction, turn) in directions {
-                if let Some(next_cell) = maze.add_vector(current_cell, Vector::from(new_direction))
-                {
-                    match counts[current_cell][new_direction as usize].status {
-                        FindPathStatus::FindPathNeeded | FindPathStatus::EqualPath(None) => {
-                            if let Ok(new_to_end) = find_path(
-                                maze,
-                                counts,
-                                next_cell,
-                                new_direction,
-                                from_start + turn + 1, // add the score for moving to the next cell, ready to turn
-                                best_total,
-                            ) {
-                                // update the best to_end for the cell/direction
-                                // update the best_total
-                                counts[current_cell][new_direction as usize]
-                                    .path_found(new_to_end + 1); // allow for the turn and step to the next cell
-                                best_total = best_total.min(from_start + turn + 1 + new_to_end);
-                                best_to_end = best_to_end.min(turn + 1 + new_to_end);
-                                path_found = true;
-                            }
-                            counts[current_cell][new_direction as usize].status =
-                                FindPathStatus::Ready;
-                        }
-                        // we can assume that if the current path is optimal then any previous path with the same from_start to this cell/direction is also optimal.
-                        // Pf, suppose route A and route B pass through the same cell/direction with the same score (the same from_start) and that A is optimal but B is not.
-                        // It must be that the remaining path for A passes through the previous path for B, since it is not available to B.  But then B could be improved and
-                        // B was not optimal.  So the assumption is correct.
-                        FindPathStatus::EqualPath(Some(new_to_end)) => {
-                            // best_total = best_total.min(from_start + turn + 1 + new_to_end);
-                            best_to_end = best_to_end.min(turn + new_to_end);
-                            path_found = true;
-                            counts[current_cell][new_direction as usize].status =
-                                FindPathStatus::Ready;
-                        }
-                        FindPathStatus::Ready => {}
-                    }
+    fn moves(&self, &(point, direction): &State) -> Vec<(State, Count)> {
+        let costs = &self.costs;
+        let mut moves = vec![
+            ((point, direction.left()), costs.turn),
+            ((point, direction.right()), costs.turn),
+        ];
+        if let Some(reverse) = costs.reverse {
+            moves.push(((point, direction.reverse()), reverse));
+        }
+        if let Some(next) = self.maze.add_direction(point, direction) {
+            match self.maze[next] {
+                b'#' => {}
+                digit @ b'0'..=b'9' if costs.tile_weights => {
+                    moves.push(((next, direction), (digit - b'0') as Count));
                 }
+                _ => moves.push(((next, direction), costs.step)),
             }
-            if path_found {
-                Ok(best_to_end)
+        }
+        moves
+    }
+
+    /// The moves on a path of consecutive states, and their cost under the maze's cost model.
+    pub fn score(&self, path: &[State]) -> Score {
+        let mut score = Score::default();
+        for pair in path.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            if from.0 != to.0 {
+                score.steps += 1;
+            } else if from.1.reverse() == to.1 {
+                score.reversals += 1;
             } else {
-                Err(PathError::NoValidPath)
+                score.turns += 1;
             }
+            let (_, cost) = self
+                .moves(&from)
+                .into_iter()
+                .find(|&(next, _)| next == to)
+                .expect("consecutive states on a path should be one move apart");
+            score.total += cost;
         }
-        find_path(
-            &self.maze,
-            &mut self.counts,
-            self.start,
-            crate::grid::Direction::East,
-            0,
-            Count::MAX,
+        score
+    }
+
+    /// The puzzle's lowest score, with the default costs whatever costs the maze was given.
+    pub fn find_best_score(&mut self) -> Count {
+        let costs = std::mem::take(&mut self.costs);
+        let score = self.lowest_score();
+        self.costs = costs;
+        score
+    }
+
+    /// The lowest score from the start, facing east, to the end.
+    pub fn lowest_score(&self) -> Count {
+        let end = self.end;
+        search::dijkstra(
+            (self.start, EAST),
+            |state| self.moves(state),
+            |&(point, _)| point == end,
         )
+        .cost()
         .unwrap()
     }
 
-    pub fn cells_on_optimal_path(&mut self) -> Count {
-        let mut optimal_cells = self.maze.clone();
-        let best_score = self.find_best_score();
-        let mut count = 2; // count S and E up front
-        for (point, &value) in &self.maze {
-            if value == b'.' {
-                if let Some(total) = self.counts[point].best_total() {
-                    if total == best_score {
-                        optimal_cells[point] = b'O';
-                        count += 1;
-                    }
-                } else {
-                    optimal_cells[point] = b'X';
+    /// The predecessor DAG of cheapest paths over (cell, facing) states, explored until the end is
+    /// reached at the lowest score.
+    pub fn best_paths(&self) -> AllPaths<State, Count> {
+        let end = self.end;
+        search::dijkstra_all(
+            (self.start, EAST),
+            |state| self.moves(state),
+            |&(point, _)| point == end,
+        )
+    }
+
+    /// Every lowest-scoring path, as the sequence of states from the start to the end. A turn
+    /// in place appears as two consecutive states at the same point.
+    pub fn optimal_paths(&self) -> Vec<Vec<State>> {
+        self.best_paths().paths()
+    }
+
+    pub fn cells_on_optimal_path(&self) -> Count {
+        let cells: HashSet<Point> = self
+            .best_paths()
+            .states_on_paths()
+            .into_iter()
+            .map(|(point, _)| point)
+            .collect();
+        cells.len() as Count
+    }
+
+    /// The maze with the path drawn on it, showing the way the reindeer leaves each cell.
+    pub fn render(&self, path: &[State]) -> Grid<u8> {
+        let mut grid = self.maze.clone();
+        for &(point, direction) in path {
+            if point != self.start && point != self.end {
+                grid[point] = char::from(direction) as u8;
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TESTINPUT, TESTINPUT2};
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Maze::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("#####\n#S.E#\n#.x.#"),
+            "line 3: unexpected character 'x'"
+        );
+        assert_eq!(
+            error("#####\n#S..#\n#####"),
+            "expected one end E in the maze, found 0"
+        );
+        assert_eq!(
+            error("#####\n#SSE#\n#####"),
+            "expected one start S in the maze, found 2"
+        );
+    }
+
+    #[test]
+    fn test_optimal_paths() {
+        let maze = Maze::new(TESTINPUT);
+        let paths = maze.optimal_paths();
+        assert_eq!(paths.len(), 3);
+        for path in &paths {
+            assert_eq!(path[0], (maze.start, EAST));
+            assert_eq!(path.last().unwrap().0, maze.end);
+            assert_eq!(
+                maze.score(path),
+                Score {
+                    steps: 36,
+                    turns: 7,
+                    reversals: 0,
+                    total: 7036
                 }
+            );
+        }
+        let cells: HashSet<Point> = paths.iter().flatten().map(|&(p, _)| p).collect();
+        assert_eq!(cells.len(), 45);
+    }
+
+    #[test]
+    fn test_dag() {
+        let maze = Maze::new(TESTINPUT2);
+        let dag = maze.best_paths();
+        assert_eq!(dag.cost(), Some(11048));
+        assert!(!dag.predecessors.contains_key(&(maze.start, EAST)));
+        // every edge in the DAG is a single move whose score accounts for the distances
+        for (state, previous) in &dag.predecessors {
+            for p in previous {
+                let (_, score) = maze
+                    .moves(p)
+                    .into_iter()
+                    .find(|(next, _)| next == state)
+                    .unwrap();
+                assert_eq!(
+                    dag.distance(p).unwrap() + score,
+                    dag.distance(state).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let maze = Maze::new(TESTINPUT);
+        let path = &maze.optimal_paths()[0];
+        let grid = maze.render(path);
+        let arrows = grid
+            .iter()
+            .filter(|&(_, &c)| matches!(c, b'^' | b'>' | b'v' | b'<'))
+            .count();
+        // every cell except the start and the end
+        let cells: HashSet<Point> = path.iter().map(|&(p, _)| p).collect();
+        assert_eq!(arrows, cells.len() - 2);
+        assert_eq!(grid[maze.start], b'S');
+        assert_eq!(grid[maze.end], b'E');
+    }
+
+    #[test]
+    fn test_turn_cost() {
+        let maze = Maze::new(TESTINPUT).with_costs(CostModel::new().turn(1));
+        // with cheap turns the shortest route wins, whatever its number of turns
+        let score = maze.score(&maze.optimal_paths()[0]);
+        assert_eq!(score.total, maze.lowest_score());
+        assert_eq!(score.total, score.steps + score.turns);
+        assert!(score.steps <= 36);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let input = "#####\n#E.S#\n#####";
+        assert_eq!(Maze::new(input).lowest_score(), 2002);
+        let maze = Maze::new(input).with_costs(CostModel::new().reverse(500));
+        assert_eq!(maze.lowest_score(), 502);
+        let path = &maze.optimal_paths()[0];
+        assert_eq!(maze.score(path).reversals, 1);
+        assert_eq!(maze.cells_on_optimal_path(), 3);
+    }
+
+    #[test]
+    fn test_tile_weights() {
+        let input = "#####\n#S9E#\n#.1.#\n#####";
+        let costs = CostModel::new().turn(0);
+        let maze = Maze::new(input).with_costs(costs);
+        assert_eq!(maze.lowest_score(), 2);
+        let maze = Maze::new(input).with_costs(costs.tile_weights(true));
+        assert_eq!(maze.lowest_score(), 4);
+        assert_eq!(maze.cells_on_optimal_path(), 5);
+    }
+
+    #[test]
+    fn test_zero_costs() {
+        let input = "#####\n#S9E#\n#.1.#\n#####";
+        let costs = CostModel::new().turn(0);
+        for maze in [
+            Maze::new(input).with_costs(costs),
+            Maze::new(input).with_costs(costs.tile_weights(true)),
+        ] {
+            let paths = maze.optimal_paths();
+            assert!(!paths.is_empty());
+            for path in &paths {
+                assert_eq!(path[0], (maze.start, EAST));
+                assert_eq!(path.last().unwrap().0, maze.end);
+                assert_eq!(maze.score(path).total, maze.lowest_score());
             }
         }
-        optimal_cells.print();
-        count
+    }
+
+    #[test]
+    fn test_find_best_score() {
+        let mut maze = Maze::new(TESTINPUT).with_costs(CostModel::new().turn(1));
+        assert_eq!(maze.find_best_score(), 7036);
+        assert_eq!(maze.costs, CostModel::new().turn(1));
     }
 }
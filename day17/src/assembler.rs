@@ -0,0 +1,247 @@
+//! An assembler for the 3-bit computer, and a disassembler producing the same syntax.
+//!
+//! Each line holds an optional `label:`, an optional instruction and an optional comment after
+//! `;` or `#`. An instruction is a mnemonic (`adv`, `bxl`, `bst`, `jnz`, `bxc`, `out`, `bdv`,
+//! `cdv`) and an operand: a raw value from 0 to 7, a register `A`, `B` or `C` for the combo
+//! instructions, or a label for `jnz`. The operand of `bxc` is ignored and may be left out.
+//!
+//! ```text
+//! loop: adv 1   ; A = A / 2
+//!       out A
+//!       jnz loop
+//! ```
+
+use std::collections::HashMap;
+
+use aoc_common::{ParseError, Result};
+use itertools::Itertools;
+
+use crate::computer::{Operation, Register};
+
+struct Line<'a> {
+    line_no: usize,
+    operation: Operation,
+    operand: Option<&'a str>,
+}
+
+fn is_label(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+pub fn assemble(source: &str) -> Result<Vec<u8>> {
+    let mut labels = HashMap::new();
+    let mut lines = Vec::new();
+    for (idx, line) in source.lines().enumerate() {
+        let line_no = idx + 1;
+        let mut text = line.split([';', '#']).next().unwrap().trim();
+        while let Some((label, rest)) = text.split_once(':') {
+            let label = label.trim();
+            if !is_label(label) {
+                return Err(ParseError::at_line(
+                    line_no,
+                    format!("invalid label '{label}'"),
+                ));
+            }
+            if labels.insert(label, lines.len() * 2).is_some() {
+                return Err(ParseError::at_line(
+                    line_no,
+                    format!("duplicate label '{label}'"),
+                ));
+            }
+            text = rest.trim();
+        }
+        let mut words = text.split_whitespace();
+        let Some(mnemonic) = words.next() else {
+            continue;
+        };
+        let operation = Operation::from_mnemonic(mnemonic).ok_or_else(|| {
+            ParseError::at_line(line_no, format!("unknown instruction '{mnemonic}'"))
+        })?;
+        let operand = words.next();
+        if let Some(extra) = words.next() {
+            return Err(ParseError::at_line(
+                line_no,
+                format!("unexpected '{extra}' after the operand"),
+            ));
+        }
+        lines.push(Line {
+            line_no,
+            operation,
+            operand,
+        });
+    }
+
+    let mut program = Vec::with_capacity(lines.len() * 2);
+    for Line {
+        line_no,
+        operation,
+        operand,
+    } in lines
+    {
+        let error = |message: String| ParseError::at_line(line_no, message);
+        let value = match operand {
+            None if operation == Operation::Bxc => 0,
+            None => return Err(error(format!("{} needs an operand", operation.mnemonic()))),
+            Some(word) => match word.parse::<u8>() {
+                Ok(value @ 0..=7) => value,
+                Ok(_) => return Err(error(format!("operand {word} is not a 3-bit value"))),
+                Err(_) if operation.takes_combo() => match word.to_ascii_uppercase().as_str() {
+                    "A" => 4,
+                    "B" => 5,
+                    "C" => 6,
+                    _ => return Err(error(format!("invalid combo operand '{word}'"))),
+                },
+                Err(_) if operation == Operation::Jnz => {
+                    let &address = labels
+                        .get(word)
+                        .ok_or_else(|| error(format!("undefined label '{word}'")))?;
+                    u8::try_from(address)
+                        .ok()
+                        .filter(|&address| address <= 7)
+                        .ok_or_else(|| {
+                            error(format!(
+                                "label '{word}' at address {address} is out of reach"
+                            ))
+                        })?
+                }
+                Err(_) => return Err(error(format!("invalid operand '{word}'"))),
+            },
+        };
+        program.push(operation as u8);
+        program.push(value);
+    }
+    Ok(program)
+}
+
+/// One instruction per line, in the syntax `assemble` reads. A trailing opcode without an
+/// operand is never executed, and is shown as a comment, as is an invalid opcode.
+pub fn disassemble(program: &[u8]) -> String {
+    let mut text = String::new();
+    for pair in program.chunks(2) {
+        let operation = match Operation::try_from(pair[0]) {
+            Ok(operation) => operation,
+            Err(error) => {
+                text.push_str(&format!("; {error}\n"));
+                continue;
+            }
+        };
+        let Some(&operand) = pair.get(1) else {
+            text.push_str(&format!("; {} without an operand\n", operation.mnemonic()));
+            break;
+        };
+        let operand = match operand {
+            4 if operation.takes_combo() => "A".to_string(),
+            5 if operation.takes_combo() => "B".to_string(),
+            6 if operation.takes_combo() => "C".to_string(),
+            _ => operand.to_string(),
+        };
+        text.push_str(&format!("{} {operand}\n", operation.mnemonic()));
+    }
+    text
+}
+
+/// A puzzle input for `Computer::new` running `program` with register A set to `a`.
+pub fn computer_input(a: Register, program: &[u8]) -> String {
+    format!(
+        "Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+        program.iter().join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::Computer;
+    use aoc_common::rng::Rng;
+
+    #[test]
+    fn test_assemble() {
+        let source = "\
+; the first example program
+loop: adv 1   ; A = A / 2
+      out A
+      jnz loop
+";
+        assert_eq!(assemble(source).unwrap(), vec![0, 1, 5, 4, 3, 0]);
+        let mut computer = Computer::new(&computer_input(729, &assemble(source).unwrap()));
+        assert_eq!(computer.execute_program().unwrap(), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn test_labels() {
+        let program = assemble("jnz end\nout 1\nend:\nstart: BXC\nout b # comment").unwrap();
+        assert_eq!(program, vec![3, 4, 5, 1, 4, 0, 5, 5]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |source| assemble(source).unwrap_err();
+        assert_eq!(
+            error("adv 1\nfoo 2"),
+            ParseError::at_line(2, "unknown instruction 'foo'")
+        );
+        assert_eq!(
+            error("adv 8"),
+            ParseError::at_line(1, "operand 8 is not a 3-bit value")
+        );
+        assert_eq!(
+            error("bxl A"),
+            ParseError::at_line(1, "invalid operand 'A'")
+        );
+        assert_eq!(error("out"), ParseError::at_line(1, "out needs an operand"));
+        assert_eq!(
+            error("jnz nowhere"),
+            ParseError::at_line(1, "undefined label 'nowhere'")
+        );
+        assert_eq!(
+            error("x: out 1\nx: out 2"),
+            ParseError::at_line(2, "duplicate label 'x'")
+        );
+        assert_eq!(
+            error("1x: out 1"),
+            ParseError::at_line(1, "invalid label '1x'")
+        );
+        assert_eq!(
+            error("out 1 2"),
+            ParseError::at_line(1, "unexpected '2' after the operand")
+        );
+        let far = "out 1\nout 1\nout 1\nout 1\nend: jnz end";
+        assert_eq!(
+            error(far),
+            ParseError::at_line(5, "label 'end' at address 8 is out of reach")
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        assert_eq!(
+            disassemble(&[2, 4, 1, 5, 7, 5, 3, 0, 4, 6]),
+            "bst A\nbxl 5\ncdv B\njnz 0\nbxc 6\n"
+        );
+        assert_eq!(disassemble(&[9, 1, 5, 4]), "; invalid opcode 9\nout A\n");
+    }
+
+    fn random_programs(count: usize) -> impl Iterator<Item = Vec<u8>> {
+        let mut rng = Rng::new(17);
+        (0..count).map(move |_| {
+            let len = (rng.below(16) + 1) as usize * 2;
+            (0..len).map(|_| rng.below(8) as u8).collect()
+        })
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for program in random_programs(1000) {
+            assert_eq!(assemble(&disassemble(&program)).unwrap(), program);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-input")]
+    fn test_round_trip_input() {
+        let computer = Computer::new(crate::INPUT);
+        let program = computer.program();
+        assert_eq!(assemble(&disassemble(program)).unwrap(), program);
+    }
+}
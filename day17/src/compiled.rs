@@ -0,0 +1,173 @@
+//! A fast path for brute-force searches, which decodes the program once instead of on every run.
+//! It agrees with `Computer::step`, which remains the reference interpreter.
+
+use crate::{
+    computer::{Operation, Register},
+    errors::{ComputerError, Result},
+    word::Word,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Literal(Register),
+    A,
+    B,
+    C,
+    Invalid { address: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    ShiftA(Source),
+    XorB(Register),
+    SetB(Source),
+    Jnz(usize),
+    XorBC,
+    Out(Source),
+    ShiftB(Source),
+    ShiftC(Source),
+    Invalid(ComputerError),
+}
+
+/// A program decoded at every address, so that a jump to an odd address also runs as it would
+/// on the interpreter.
+#[derive(Debug, Clone)]
+pub struct CompiledProgram {
+    instructions: Vec<Instruction>,
+}
+
+impl CompiledProgram {
+    pub fn new(program: &[u8]) -> Self {
+        let instructions = program
+            .windows(2)
+            .enumerate()
+            .map(|(address, pair)| {
+                let operand = pair[1];
+                let combo = || match operand {
+                    0..=3 => Source::Literal(operand as Register),
+                    4 => Source::A,
+                    5 => Source::B,
+                    6 => Source::C,
+                    _ => Source::Invalid { address },
+                };
+                match Operation::try_from(pair[0]) {
+                    Ok(Operation::Adv) => Instruction::ShiftA(combo()),
+                    Ok(Operation::Bxl) => Instruction::XorB(operand as Register),
+                    Ok(Operation::Bst) => Instruction::SetB(combo()),
+                    Ok(Operation::Jnz) => Instruction::Jnz(operand as usize),
+                    Ok(Operation::Bxc) => Instruction::XorBC,
+                    Ok(Operation::Out) => Instruction::Out(combo()),
+                    Ok(Operation::Bdv) => Instruction::ShiftB(combo()),
+                    Ok(Operation::Cdv) => Instruction::ShiftC(combo()),
+                    Err(error) => Instruction::Invalid(error),
+                }
+            })
+            .collect();
+        CompiledProgram { instructions }
+    }
+
+    /// Runs from registers `[a, b, c]`, passing each output to `on_output` until it returns
+    /// false. Returns whether the program ran until it halted.
+    pub fn run(
+        &self,
+        registers: [Register; 3],
+        mut on_output: impl FnMut(u8) -> bool,
+    ) -> Result<bool> {
+        let [mut a, mut b, mut c] = registers;
+        let mut ip = 0;
+        while let Some(&instruction) = self.instructions.get(ip) {
+            let value = |source| match source {
+                Source::Literal(value) => Ok(value),
+                Source::A => Ok(a),
+                Source::B => Ok(b),
+                Source::C => Ok(c),
+                Source::Invalid { address } => Err(ComputerError::InvalidComboOperand { address }),
+            };
+            ip += 2;
+            match instruction {
+                Instruction::ShiftA(source) => a = a.shr(&value(source)?),
+                Instruction::XorB(literal) => b ^= literal,
+                Instruction::SetB(source) => b = value(source)? % 8,
+                Instruction::Jnz(address) => {
+                    if a != 0 {
+                        ip = address;
+                    }
+                }
+                Instruction::XorBC => b ^= c,
+                Instruction::Out(source) => {
+                    if !on_output(value(source)?.low_bits()) {
+                        return Ok(false);
+                    }
+                }
+                Instruction::ShiftB(source) => b = a.shr(&value(source)?),
+                Instruction::ShiftC(source) => c = a.shr(&value(source)?),
+                Instruction::Invalid(error) => return Err(error),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Whether the program outputs exactly `target`, stopping at the first difference.
+    pub fn outputs(&self, registers: [Register; 3], target: &[u8]) -> Result<bool> {
+        let mut remaining = target.iter();
+        let halted = self.run(registers, |value| remaining.next() == Some(&value))?;
+        Ok(halted && remaining.len() == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        TESTINPUT3,
+        assembler::computer_input,
+        computer::{Computer, Reg},
+    };
+    use aoc_common::rng::Rng;
+
+    #[test]
+    fn test_outputs() {
+        let computer = Computer::new(TESTINPUT3);
+        let compiled = CompiledProgram::new(computer.program());
+        let a = *computer.register(Reg::A);
+        let program = computer.program();
+        assert_eq!(compiled.outputs([a, 0, 0], program), Ok(true));
+        assert_eq!(compiled.outputs([a + 1, 0, 0], program), Ok(false));
+        assert_eq!(compiled.outputs([a, 0, 0], &program[1..]), Ok(false));
+        assert_eq!(
+            compiled.outputs([a, 0, 0], &[program, &[0]].concat()),
+            Ok(false)
+        );
+        assert_eq!(
+            CompiledProgram::new(&[8, 0]).run([0, 0, 0], |_| true),
+            Err(ComputerError::InvalidOpcode { opcode: 8 })
+        );
+    }
+
+    #[test]
+    fn test_matches_interpreter() {
+        let mut rng = Rng::new(17);
+        for _ in 0..500 {
+            // a random body without jumps or writes to A, then a loop shifting A down to zero
+            let mut program = Vec::new();
+            for _ in 0..rng.below(6) {
+                let opcode = [1, 2, 4, 5, 6, 7][rng.below(6) as usize];
+                let operand = rng.below(8) as u8;
+                program.extend([opcode, operand]);
+            }
+            program.extend([0, 3, 3, 0]);
+            let a = rng.next_u64() >> rng.below(64);
+
+            let mut computer = Computer::new(&computer_input(a, &program));
+            let expected = computer
+                .execute_program()
+                .map(|_| computer.output().to_vec());
+            let mut output = Vec::new();
+            let result = CompiledProgram::new(&program).run([a, 0, 0], |value| {
+                output.push(value);
+                true
+            });
+            assert_eq!(result.map(|_| output), expected, "{program:?} {a}");
+        }
+    }
+}
@@ -1,8 +1,18 @@
+use aoc_common::{ParseError, parse};
 use itertools::Itertools;
-use stephen_morris_utils::get_numbers;
-type Register = u64;
 
-#[derive(Debug)]
+use num_traits::PrimInt;
+
+use crate::{
+    compiled::CompiledProgram,
+    errors::{ComputerError, Result},
+    solver::Solver,
+    word::Word,
+};
+
+pub type Register = u64;
+
+#[derive(Debug, Clone)]
 struct Program {
     instruction_pointer: usize,
     instructions: Vec<u8>,
@@ -18,95 +28,81 @@ impl Program {
             None
         }
     }
-    fn read_instruction(&mut self) -> Option<(Operation, u8)> {
-        if let (Some(operation), Some(operand)) =
-            (self.read_value().map(Operation::from), self.read_value())
-        {
-            Some((operation, operand))
-        } else {
-            None
-        }
+    fn read_instruction(&mut self) -> Result<Option<(Operation, u8)>> {
+        let (Some(opcode), Some(operand)) = (self.read_value(), self.read_value()) else {
+            return Ok(None);
+        };
+        Ok(Some((Operation::try_from(opcode)?, operand)))
     }
-    fn dissasemble(&mut self) -> String {
+    fn dissasemble_instruction(opcode: Operation, operand: u8) -> String {
         fn combo(v: u8) -> String {
             match v {
                 0..=3 => v.to_string(),
                 4 => "A".to_string(),
                 5 => "B".to_string(),
                 6 => "C".to_string(),
-                _ => unreachable!(),
-            }
-        }
-        let mut result = String::new();
-        self.instruction_pointer = 0;
-        while let Some((opcode, operand)) = self.read_instruction() {
-            result.push_str(&format!("{:} {:} ", opcode as usize, operand));
-            match opcode {
-                Operation::Adv => {
-                    result.push_str(&format!("A = A / (2^ {:})\n", combo(operand)));
-                }
-                Operation::Bxl => {
-                    // self.state.register_b ^= operand as Register;
-                    result.push_str(&format!("B = B XOR {:}\n", operand));
-                }
-                Operation::Bst => {
-                    // self.state.register_b = self.state.combo(operand) % 8;
-                    result.push_str(&format!("B = {:} MOD 8\n", combo(operand)));
-                }
-                Operation::Jnz => {
-                    result.push_str(&format!("Jump if A != 0 to {:}\n", combo(operand)));
-                }
-                Operation::Bxc => {
-                    // self.state.register_b ^= self.state.register_c;
-                    result.push_str("B = B XOR C\n");
-                }
-                Operation::Out => {
-                    // self.state.output.push(self.state.combo(operand) % 8);
-                    result.push_str(&format!("Output {:} MOD 8\n", combo(operand)));
-                }
-                Operation::Bdv => {
-                    // self.state.register_b = (self.state.register_a >> self.state.combo(operand));
-                    result.push_str(&format!("B = A / (2^ {:})\n", combo(operand)));
-                }
-                Operation::Cdv => {
-                    // self.state.register_c = (self.state.register_a >> self.state.combo(operand));
-                    result.push_str(&format!("C = A / (2^ {:})\n", combo(operand)));
-                }
+                _ => "invalid".to_string(),
             }
         }
-
-        result
+        let text = match opcode {
+            Operation::Adv => format!("A = A / (2^ {:})", combo(operand)),
+            Operation::Bxl => format!("B = B XOR {:}", operand),
+            Operation::Bst => format!("B = {:} MOD 8", combo(operand)),
+            Operation::Jnz => format!("Jump if A != 0 to {:}", operand),
+            Operation::Bxc => "B = B XOR C".to_string(),
+            Operation::Out => format!("Output {:} MOD 8", combo(operand)),
+            Operation::Bdv => format!("B = A / (2^ {:})", combo(operand)),
+            Operation::Cdv => format!("C = A / (2^ {:})", combo(operand)),
+        };
+        format!("{:} {:} {text}", opcode as usize, operand)
+    }
+    /// One line per instruction, reading from the start of the program in pairs.
+    fn dissasemble_lines(&self) -> Vec<String> {
+        self.instructions
+            .chunks_exact(2)
+            .map(|pair| match Operation::try_from(pair[0]) {
+                Ok(operation) => Self::dissasemble_instruction(operation, pair[1]),
+                Err(error) => error.to_string(),
+            })
+            .collect()
+    }
+    fn dissasemble(&self) -> String {
+        self.dissasemble_lines()
+            .iter()
+            .map(|line| format!("{line}\n"))
+            .collect()
     }
 }
 
-#[derive(Debug)]
-struct ComputerState {
-    register_a: Register,
-    register_b: Register,
-    register_c: Register,
+#[derive(Debug, Clone)]
+struct ComputerState<W> {
+    register_a: W,
+    register_b: W,
+    register_c: W,
     output: Vec<u8>,
 }
 
-impl ComputerState {
-    fn combo(&self, v: u8) -> Register {
+impl<W: Word> ComputerState<W> {
+    fn combo(&self, v: u8, address: usize) -> Result<W> {
         match v {
-            0..=3 => v as Register,
-            4 => self.register_a,
-            5 => self.register_b,
-            6 => self.register_c,
-            _ => unreachable!(),
+            0..=3 => Ok(W::from_u8(v)),
+            4 => Ok(self.register_a.clone()),
+            5 => Ok(self.register_b.clone()),
+            6 => Ok(self.register_c.clone()),
+            _ => Err(ComputerError::InvalidComboOperand { address }),
         }
     }
 }
 
-#[derive(Debug)]
-pub struct Computer {
-    state: ComputerState,
+/// The 3-bit computer, with registers of type `W`.
+#[derive(Debug, Clone)]
+pub struct Computer<W = Register> {
+    state: ComputerState<W>,
     program: Program,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Operation {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
     Adv,
     Bxl,
     Bst,
@@ -117,30 +113,117 @@ enum Operation {
     Cdv,
 }
 
-impl From<u8> for Operation {
-    fn from(v: u8) -> Self {
+impl TryFrom<u8> for Operation {
+    type Error = ComputerError;
+
+    fn try_from(v: u8) -> Result<Self> {
         match v {
-            0 => Operation::Adv,
-            1 => Operation::Bxl,
-            2 => Operation::Bst,
-            3 => Operation::Jnz,
-            4 => Operation::Bxc,
-            5 => Operation::Out,
-            6 => Operation::Bdv,
-            7 => Operation::Cdv,
-            _ => unreachable!(),
+            0 => Ok(Operation::Adv),
+            1 => Ok(Operation::Bxl),
+            2 => Ok(Operation::Bst),
+            3 => Ok(Operation::Jnz),
+            4 => Ok(Operation::Bxc),
+            5 => Ok(Operation::Out),
+            6 => Ok(Operation::Bdv),
+            7 => Ok(Operation::Cdv),
+            opcode => Err(ComputerError::InvalidOpcode { opcode }),
         }
     }
 }
 
+impl Operation {
+    pub const ALL: [Operation; 8] = [
+        Operation::Adv,
+        Operation::Bxl,
+        Operation::Bst,
+        Operation::Jnz,
+        Operation::Bxc,
+        Operation::Out,
+        Operation::Bdv,
+        Operation::Cdv,
+    ];
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Operation::Adv => "adv",
+            Operation::Bxl => "bxl",
+            Operation::Bst => "bst",
+            Operation::Jnz => "jnz",
+            Operation::Bxc => "bxc",
+            Operation::Out => "out",
+            Operation::Bdv => "bdv",
+            Operation::Cdv => "cdv",
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|op| op.mnemonic().eq_ignore_ascii_case(mnemonic))
+    }
+
+    /// Whether the operand is a combo operand, where 4 to 6 stand for registers A to C.
+    pub fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Operation::Adv | Operation::Bst | Operation::Out | Operation::Bdv | Operation::Cdv
+        )
+    }
+}
+
+/// What executing one instruction did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Executed,
+    Output(u8),
+    /// A taken jump, to the given address.
+    Jump(usize),
+    /// There was no instruction to execute.
+    Halt,
+}
+
+/// A register, for inspecting a running `Computer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reg {
+    A,
+    B,
+    C,
+}
+
 impl Computer {
     pub fn new(input: &str) -> Self {
-        let mut lines = input.lines();
-        let register_a = get_numbers(lines.next().unwrap())[0];
-        let register_b = get_numbers(lines.next().unwrap())[0];
-        let register_c = get_numbers(lines.next().unwrap())[0];
-        lines.next();
-        let instructions = get_numbers(lines.next().unwrap());
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+}
+
+impl<W: Word> Computer<W> {
+    /// Reads a computer with registers of any width, as in `Computer::<u128>::parse(input)`.
+    pub fn parse(input: &str) -> aoc_common::Result<Self> {
+        let (registers, program, offset) = parse::sections(input)?;
+        let mut names = ['A', 'B', 'C'].into_iter();
+        let registers = parse::lines(registers, |line| {
+            let name = names.next().ok_or("expected only registers A, B and C")?;
+            let value = line
+                .strip_prefix(&format!("Register {name}: "))
+                .ok_or(format!("expected 'Register {name}: <value>'"))?;
+            parse::number::<W>(value)
+        })?;
+        let Ok([register_a, register_b, register_c]) = <[W; 3]>::try_from(registers) else {
+            return Err(ParseError::new("expected registers A, B and C"));
+        };
+        let instructions = parse::lines(program, |line| {
+            let numbers = line
+                .strip_prefix("Program: ")
+                .ok_or("expected 'Program: <numbers>'")?;
+            let instructions: Vec<u8> = parse::numbers(numbers, ',')?;
+            match instructions.iter().find(|&&value| value > 7) {
+                Some(value) => Err(format!("{value} is not a 3-bit number")),
+                None => Ok(instructions),
+            }
+        })
+        .map_err(|error| error.offset(offset))?;
+        let [instructions] = <[Vec<u8>; 1]>::try_from(instructions)
+            .map_err(|_| ParseError::new("expected the program on a single line"))?;
         let state = ComputerState {
             register_a,
             register_b,
@@ -151,204 +234,174 @@ impl Computer {
             instruction_pointer: 0,
             instructions,
         };
-        Computer { state, program }
+        Ok(Computer { state, program })
     }
-    fn execute_next_instruction(&mut self) -> bool {
-        if let Some((opcode, operand)) = self.program.read_instruction() {
-            match opcode {
-                Operation::Adv => {
-                    self.state.register_a >>= self.state.combo(operand);
-                }
-                Operation::Bxl => {
-                    self.state.register_b ^= operand as Register;
-                }
-                Operation::Bst => {
-                    self.state.register_b = self.state.combo(operand) % 8;
-                }
-                Operation::Jnz => {
-                    if self.state.register_a != 0 {
-                        self.program.instruction_pointer = operand as usize
-                    }
-                }
-                Operation::Bxc => {
-                    self.state.register_b ^= self.state.register_c;
-                }
-                Operation::Out => {
-                    self.state.output.push(self.state.combo(operand) as u8 % 8);
-                }
-                Operation::Bdv => {
-                    self.state.register_b = (self.state.register_a >> self.state.combo(operand));
-                }
-                Operation::Cdv => {
-                    self.state.register_c = (self.state.register_a >> self.state.combo(operand));
+    pub fn instruction_pointer(&self) -> usize {
+        self.program.instruction_pointer
+    }
+    pub fn register(&self, reg: Reg) -> &W {
+        match reg {
+            Reg::A => &self.state.register_a,
+            Reg::B => &self.state.register_b,
+            Reg::C => &self.state.register_c,
+        }
+    }
+    pub fn output(&self) -> &[u8] {
+        &self.state.output
+    }
+    /// The instruction which will execute next, or `None` once the program has halted or if the
+    /// opcode is invalid.
+    pub fn next_instruction(&self) -> Option<(Operation, u8)> {
+        let ip = self.program.instruction_pointer;
+        let instructions = &self.program.instructions;
+        let operation = Operation::try_from(*instructions.get(ip)?).ok()?;
+        Some((operation, *instructions.get(ip + 1)?))
+    }
+    pub fn dissasemble_lines(&self) -> Vec<String> {
+        self.program.dissasemble_lines()
+    }
+    pub fn program(&self) -> &[u8] {
+        &self.program.instructions
+    }
+    /// Executes the next instruction. This is the only interpreter of the instruction set, and
+    /// every way of running the computer is built on it. An instruction which fails leaves the
+    /// computer unchanged.
+    pub fn step(&mut self) -> Result<Event> {
+        let address = self.program.instruction_pointer;
+        let instruction = self
+            .program
+            .read_instruction()
+            .inspect_err(|_| self.program.instruction_pointer = address)?;
+        let Some((opcode, operand)) = instruction else {
+            return Ok(Event::Halt);
+        };
+        let state = &mut self.state;
+        let value = match opcode.takes_combo() {
+            true => state
+                .combo(operand, address)
+                .inspect_err(|_| self.program.instruction_pointer = address)?,
+            false => W::from_u8(operand),
+        };
+        match opcode {
+            Operation::Adv => state.register_a = state.register_a.shr(&value),
+            Operation::Bxl => state.register_b = state.register_b.xor(&value),
+            Operation::Bst => state.register_b = W::from_u8(value.low_bits()),
+            Operation::Jnz => {
+                if !state.register_a.is_zero() {
+                    self.program.instruction_pointer = operand as usize;
+                    return Ok(Event::Jump(operand as usize));
                 }
             }
-            true
-        } else {
-            false
+            Operation::Bxc => state.register_b = state.register_b.xor(&state.register_c),
+            Operation::Out => {
+                let value = value.low_bits();
+                state.output.push(value);
+                return Ok(Event::Output(value));
+            }
+            Operation::Bdv => state.register_b = state.register_a.shr(&value),
+            Operation::Cdv => state.register_c = state.register_a.shr(&value),
         }
+        Ok(Event::Executed)
     }
-    pub fn execute_program(&mut self) -> String {
-        // println!("{:?}", self);
-        while self.execute_next_instruction() {
-            // println!("{:?}", self);
+    /// Steps until the program halts or `stop` returns true for an event, and returns the last
+    /// event.
+    pub fn run_until(&mut self, mut stop: impl FnMut(&Self, Event) -> bool) -> Result<Event> {
+        loop {
+            let event = self.step()?;
+            if event == Event::Halt || stop(self, event) {
+                return Ok(event);
+            }
         }
-        self.state.output.iter().join(",")
+    }
+    pub fn execute_program(&mut self) -> Result<String> {
+        self.run_until(|_, _| false)?;
+        Ok(self.state.output.iter().join(","))
     }
 
-    pub fn execute_program_and_check(&mut self) -> String {
-        while let Some((opcode, operand)) = self.program.read_instruction() {
-            match opcode {
-                Operation::Adv => {
-                    self.state.register_a >>= self.state.combo(operand);
-                }
-                Operation::Bxl => {
-                    self.state.register_b ^= operand as Register;
-                }
-                Operation::Bst => {
-                    self.state.register_b = self.state.combo(operand) % 8;
-                }
-                Operation::Jnz => {
-                    if self.state.register_a != 0 {
-                        self.program.instruction_pointer = operand as usize
-                    }
-                }
-                Operation::Bxc => {
-                    self.state.register_b ^= self.state.register_c;
-                }
-                Operation::Out => {
-                    self.state.output.push(self.state.combo(operand) as u8 % 8);
-                }
-                Operation::Bdv => {
-                    self.state.register_b = (self.state.register_a >> self.state.combo(operand));
-                }
-                Operation::Cdv => {
-                    self.state.register_c = (self.state.register_a >> self.state.combo(operand));
-                }
-            }
-        }
-        self.state.output.iter().join(",")
+    /// Runs the program, stopping early as soon as the output stops being a prefix of the program.
+    pub fn execute_program_and_check(&mut self) -> Result<String> {
+        self.run_until(|computer, event| {
+            matches!(event, Event::Output(_))
+                && !computer
+                    .program
+                    .instructions
+                    .starts_with(&computer.state.output)
+        })?;
+        Ok(self.state.output.iter().join(","))
     }
-    fn reset(&mut self, new_a: Register) {
+    /// Restarts the program with A set to `new_a`, and B and C cleared.
+    pub fn reset(&mut self, new_a: W) {
         self.state.register_a = new_a;
-        self.state.register_b = 0;
-        self.state.register_c = 0;
+        self.state.register_b = W::default();
+        self.state.register_c = W::default();
         self.state.output.clear();
         self.program.instruction_pointer = 0;
     }
-    pub fn find_initial_a(&mut self) -> Register {
+    /// The next output, or `None` if the program halts first.
+    fn get_next_output(&mut self) -> Result<Option<u8>> {
+        match self.run_until(|_, event| matches!(event, Event::Output(_)))? {
+            Event::Output(value) => Ok(Some(value)),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl<W: Word + PrimInt> Computer<W> {
+    /// The smallest initial A for which the program outputs `target` and halts, starting from the
+    /// current B and C, or `None` if there is none.
+    pub fn find_initial_a_for(&self, target: &[u8]) -> Option<W> {
+        let (b, c) = (*self.register(Reg::B), *self.register(Reg::C));
+        Solver::new(self.program(), b, c, target).solve()
+    }
+    /// The smallest initial A for which the program outputs a copy of itself.
+    pub fn find_quine(&self) -> Option<W> {
+        self.find_initial_a_for(self.program())
+    }
+}
+
+impl Computer {
+    /// Brute force from `1 << 54`, on the compiled program.
+    pub fn find_initial_a(&mut self) -> Result<Register> {
+        let compiled = CompiledProgram::new(&self.program.instructions);
+        let target = &self.program.instructions;
         let mut i = 1 << 54;
-        'outer: loop {
+        loop {
             if i % (100000000) == 0 {
                 println!("{}", i);
             }
-            self.reset(i);
-            let mut j = 0;
-            while let Some((opcode, operand)) = self.program.read_instruction() {
-                match opcode {
-                    Operation::Adv => {
-                        self.state.register_a >>= self.state.combo(operand);
-                    }
-                    Operation::Bxl => {
-                        self.state.register_b ^= operand as Register;
-                    }
-                    Operation::Bst => {
-                        self.state.register_b = self.state.combo(operand) % 8;
-                    }
-                    Operation::Jnz => {
-                        if self.state.register_a != 0 {
-                            self.program.instruction_pointer = operand as usize
-                        }
-                    }
-                    Operation::Bxc => {
-                        self.state.register_b ^= self.state.register_c;
-                    }
-                    Operation::Out => {
-                        self.state.output.push(self.state.combo(operand) as u8 % 8);
-                        if self.program.instructions[j] != self.state.combo(operand) as u8 % 8 {
-                            i += 1;
-                            j = 0;
-                            continue 'outer;
-                        } else {
-                            j += 1;
-                        }
-                    }
-                    Operation::Bdv => {
-                        self.state.register_b =
-                            (self.state.register_a >> self.state.combo(operand));
-                    }
-                    Operation::Cdv => {
-                        self.state.register_c =
-                            (self.state.register_a >> self.state.combo(operand));
-                    }
-                }
-            }
-            if self.state.output == self.program.instructions {
-                return i;
+            if compiled.outputs([i, 0, 0], target)? {
+                return Ok(i);
             }
             i += 1;
         }
     }
-    fn get_next_output(&mut self) -> u8 {
-        while let Some((opcode, operand)) = self.program.read_instruction() {
-            match opcode {
-                Operation::Adv => {
-                    self.state.register_a >>= self.state.combo(operand);
-                }
-                Operation::Bxl => {
-                    self.state.register_b ^= operand as Register;
-                }
-                Operation::Bst => {
-                    self.state.register_b = self.state.combo(operand) % 8;
-                }
-                Operation::Jnz => {
-                    if self.state.register_a != 0 {
-                        self.program.instruction_pointer = operand as usize;
-                    }
-                }
-                Operation::Bxc => {
-                    self.state.register_b ^= self.state.register_c;
-                }
-                Operation::Out => {
-                    return self.state.combo(operand) as u8 % 8;
-                }
-                Operation::Bdv => {
-                    self.state.register_b = (self.state.register_a >> self.state.combo(operand));
-                }
-                Operation::Cdv => {
-                    self.state.register_c = (self.state.register_a >> self.state.combo(operand));
-                }
-            }
-        }
-        unreachable!()
-    }
 
-    pub fn find_initial_a2(&mut self) -> Register {
+    pub fn find_initial_a2(&mut self) -> Result<Option<Register>> {
         fn get_next_digit(
             computer: &mut Computer,
             current_a: Register,
             target: &[u8],
-        ) -> Option<Register> {
+        ) -> Result<Option<Register>> {
             println!(
                 "get_next_digit: current_a {:?}, target {:?}",
                 current_a, target
             );
             if target.is_empty() {
-                return Some(current_a);
+                return Ok(Some(current_a));
             }
             for i in 0..=7 {
                 computer.reset(current_a * 8 + i);
-                if computer.get_next_output() == target[0] {
-                    if let Some(new_a) = get_next_digit(computer, current_a * 8 + i, &target[1..]) {
-                        return Some(new_a);
-                    }
+                if computer.get_next_output()? == Some(target[0])
+                    && let Some(new_a) = get_next_digit(computer, current_a * 8 + i, &target[1..])?
+                {
+                    return Ok(Some(new_a));
                 }
             }
-            None
+            Ok(None)
         }
         let mut target = self.program.instructions.clone();
         target.reverse();
-        get_next_digit(self, 0, &target).unwrap()
+        get_next_digit(self, 0, &target)
     }
 }
 
@@ -357,11 +410,31 @@ mod tests {
     use super::*;
     use crate::*;
     #[test]
+    fn test_parse_errors() {
+        let error = |input| Computer::<Register>::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("Register A: 1\nRegister C: 0\nRegister C: 0\n\nProgram: 0,1"),
+            "line 2: expected 'Register B: <value>'"
+        );
+        assert_eq!(
+            error("Register A: 1\nRegister B: 0\n\nProgram: 0,1"),
+            "expected registers A, B and C"
+        );
+        assert_eq!(
+            error("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,8"),
+            "line 5: 8 is not a 3-bit number"
+        );
+        assert_eq!(
+            error("Register A: x\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1"),
+            "line 1: invalid number 'x'"
+        );
+    }
+    #[test]
     fn test_dissasembly() {
         let mut computer = Computer::new(crate::TESTINPUT);
         println!("{}", computer.program.dissasemble());
         let mut computer = Computer::new(crate::TESTINPUT);
-        let output = computer.execute_program();
+        let output = computer.execute_program().unwrap();
         println!("{}", output);
     }
     #[test]
@@ -369,28 +442,72 @@ mod tests {
         let mut computer = Computer::new(crate::TESTINPUT2);
         println!("{}", computer.program.dissasemble());
         let mut computer = Computer::new(crate::TESTINPUT2);
-        let output = computer.execute_program();
+        let output = computer.execute_program().unwrap();
         println!("{}", output);
     }
 
     #[test]
+    #[cfg(feature = "embedded-input")]
     fn test_dissasembly3() {
         let mut computer = Computer::new(crate::INPUT);
         println!("{}", computer.program.dissasemble());
         let mut computer = Computer::new(crate::INPUT);
-        let output = computer.execute_program();
+        let output = computer.execute_program().unwrap();
         println!("{}", output);
     }
 
+    #[test]
+    fn test_events() {
+        let mut computer = Computer::new(TESTINPUT);
+        let events: Vec<Event> = (0..3).map(|_| computer.step().unwrap()).collect();
+        assert_eq!(
+            events,
+            vec![Event::Executed, Event::Output(4), Event::Jump(0)]
+        );
+        assert_eq!(computer.get_next_output(), Ok(Some(6)));
+        let mut computer = Computer::new(TESTINPUT3);
+        let program = computer.program().iter().join(",");
+        assert_eq!(computer.execute_program_and_check().unwrap(), program);
+        computer.reset(117440);
+        assert_eq!(computer.execute_program_and_check().unwrap(), "5");
+    }
+    #[test]
+    fn test_invalid_opcode() {
+        assert_eq!(Operation::try_from(3), Ok(Operation::Jnz));
+        assert_eq!(
+            Operation::try_from(8),
+            Err(ComputerError::InvalidOpcode { opcode: 8 })
+        );
+    }
+    #[test]
+    fn test_invalid_combo_operand() {
+        let mut computer =
+            Computer::new("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,7,5,7");
+        let error = ComputerError::InvalidComboOperand { address: 2 };
+        assert_eq!(computer.execute_program(), Err(error));
+        assert_eq!(computer.instruction_pointer(), 2);
+        assert_eq!(*computer.register(Reg::B), 7);
+    }
+    #[test]
+    fn test_register_widths() {
+        // A is 2^100 + 729, and the shift by A (B = A >> A) overflows every width
+        let input = "Register A: 1267650600228229401496703206105\nRegister B: 0\nRegister C: 0\n\nProgram: 6,4,5,5,0,1,5,4,3,4";
+        let mut wide = Computer::<u128>::parse(input).unwrap();
+        let mut big = Computer::<num_bigint::BigUint>::parse(input).unwrap();
+        let output = wide.execute_program().unwrap();
+        assert_eq!(big.execute_program().unwrap(), output);
+        assert!(output.starts_with("0,4,6,3,5,6,3,5,2,1,0"));
+        assert_eq!(wide.output().len(), 1 + 101);
+    }
     #[test]
     fn test_find_a() {
         let mut computer = Computer::new(TESTINPUT2);
-        println!("{}", computer.find_initial_a2());
+        assert_eq!(computer.find_initial_a2(), Ok(Some(117440)));
     }
     #[test]
     fn test_part2_correct() {
         let mut computer = Computer::new(TESTINPUT3);
-        let output = computer.execute_program();
+        let output = computer.execute_program().unwrap();
         println!(
             "output: {:?}, program {:?}",
             output, computer.program.instructions
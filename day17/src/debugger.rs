@@ -0,0 +1,432 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use crate::{
+    computer::{Computer, Event, Operation, Reg, Register},
+    errors::ComputerError,
+};
+
+/// One executed instruction and the machine state it left behind.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    pub instruction_pointer: usize,
+    pub operation: Operation,
+    pub operand: u8,
+    pub registers: [Register; 3],
+    pub output: Option<u8>,
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [a, b, c] = self.registers;
+        write!(
+            f,
+            "{:3}: {:?} {}  A={a} B={b} C={c}",
+            self.instruction_pointer, self.operation, self.operand
+        )?;
+        if let Some(value) = self.output {
+            write!(f, "  out {value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Why execution stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Step,
+    Halted,
+    Breakpoint(usize),
+    Output(u8),
+    Error(ComputerError),
+    Watch {
+        reg: Reg,
+        old: Register,
+        new: Register,
+    },
+}
+
+impl Display for Stop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stop::Step => write!(f, "stepped"),
+            Stop::Halted => write!(f, "halted"),
+            Stop::Breakpoint(ip) => write!(f, "breakpoint at {ip}"),
+            Stop::Output(value) => write!(f, "output {value}"),
+            Stop::Error(error) => write!(f, "{error}"),
+            Stop::Watch { reg, old, new } => write!(f, "{reg:?} changed from {old} to {new}"),
+        }
+    }
+}
+
+/// Runs a `Computer` an instruction at a time, stopping at breakpoints, on output, or when a
+/// watched register changes.
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    computer: Computer,
+    breakpoints: BTreeSet<usize>,
+    break_on_output: bool,
+    watches: Vec<Reg>,
+    tracing: bool,
+    trace: Vec<TraceEntry>,
+}
+
+impl Debugger {
+    pub fn new(computer: Computer) -> Self {
+        Self {
+            computer,
+            breakpoints: BTreeSet::new(),
+            break_on_output: false,
+            watches: Vec::new(),
+            tracing: false,
+            trace: Vec::new(),
+        }
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn add_breakpoint(&mut self, instruction_pointer: usize) {
+        self.breakpoints.insert(instruction_pointer);
+    }
+
+    pub fn remove_breakpoint(&mut self, instruction_pointer: usize) -> bool {
+        self.breakpoints.remove(&instruction_pointer)
+    }
+
+    pub fn break_on_output(&mut self, enabled: bool) {
+        self.break_on_output = enabled;
+    }
+
+    pub fn watch(&mut self, reg: Reg) {
+        if !self.watches.contains(&reg) {
+            self.watches.push(reg);
+        }
+    }
+
+    pub fn unwatch(&mut self, reg: Reg) {
+        self.watches.retain(|&r| r != reg);
+    }
+
+    pub fn set_tracing(&mut self, enabled: bool) {
+        self.tracing = enabled;
+    }
+
+    pub fn trace(&self) -> &[TraceEntry] {
+        &self.trace
+    }
+
+    fn registers(&self) -> [Register; 3] {
+        [Reg::A, Reg::B, Reg::C].map(|reg| *self.computer.register(reg))
+    }
+
+    /// Executes one instruction, and reports a stop condition it triggered, if any.
+    fn execute(&mut self) -> Option<Stop> {
+        let instruction_pointer = self.computer.instruction_pointer();
+        let Some((operation, operand)) = self.computer.next_instruction() else {
+            return Some(Stop::Halted);
+        };
+        let before = self.registers();
+        let output = match self.computer.step() {
+            Ok(Event::Output(value)) => Some(value),
+            Ok(_) => None,
+            Err(error) => return Some(Stop::Error(error)),
+        };
+        let registers = self.registers();
+        if self.tracing {
+            self.trace.push(TraceEntry {
+                instruction_pointer,
+                operation,
+                operand,
+                registers,
+                output,
+            });
+        }
+        for &reg in &self.watches {
+            let (old, new) = (before[reg as usize], registers[reg as usize]);
+            if old != new {
+                return Some(Stop::Watch { reg, old, new });
+            }
+        }
+        match output {
+            Some(value) if self.break_on_output => Some(Stop::Output(value)),
+            _ => None,
+        }
+    }
+
+    pub fn step(&mut self) -> Stop {
+        self.execute().unwrap_or(Stop::Step)
+    }
+
+    /// Runs until the program halts or a stop condition is met. A breakpoint stops execution
+    /// before the instruction at it runs, except for the first instruction, so that continuing
+    /// from a breakpoint makes progress.
+    pub fn run(&mut self) -> Stop {
+        if let Some(stop) = self.execute() {
+            return stop;
+        }
+        loop {
+            let instruction_pointer = self.computer.instruction_pointer();
+            if self.breakpoints.contains(&instruction_pointer)
+                && self.computer.next_instruction().is_some()
+            {
+                return Stop::Breakpoint(instruction_pointer);
+            }
+            if let Some(stop) = self.execute() {
+                return stop;
+            }
+        }
+    }
+
+    /// The disassembled program with the next instruction marked `=>` and breakpoints `*`.
+    pub fn listing(&self) -> String {
+        let ip = self.computer.instruction_pointer();
+        let mut listing = String::new();
+        for (i, line) in self.computer.dissasemble_lines().iter().enumerate() {
+            let address = i * 2;
+            let marker = if address == ip { "=>" } else { "  " };
+            let breakpoint = if self.breakpoints.contains(&address) {
+                '*'
+            } else {
+                ' '
+            };
+            listing.push_str(&format!("{marker}{breakpoint}{address:3}: {line}\n"));
+        }
+        if ip % 2 == 1 && self.computer.next_instruction().is_some() {
+            listing.push_str(&format!("=> {ip:3}: (odd address, not shown)\n"));
+        }
+        listing
+    }
+
+    pub fn status(&self) -> String {
+        let [a, b, c] = self.registers();
+        format!(
+            "ip={} A={a} B={b} C={c} output={:?}",
+            self.computer.instruction_pointer(),
+            self.computer.output()
+        )
+    }
+
+    /// Runs one command of the command-line debugger, returning the text to show.
+    pub fn command(&mut self, line: &str) -> Result<String, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let reg = |word: Option<&&str>| match word.map(|w| w.to_ascii_lowercase()).as_deref() {
+            Some("a") => Ok(Reg::A),
+            Some("b") => Ok(Reg::B),
+            Some("c") => Ok(Reg::C),
+            _ => Err("expected a register: a, b or c".to_string()),
+        };
+        let address = |word: Option<&&str>| {
+            word.and_then(|w| w.parse::<usize>().ok())
+                .ok_or_else(|| "expected an instruction address".to_string())
+        };
+        let on_off = |word: Option<&&str>| match word.copied() {
+            Some("on") => Ok(true),
+            Some("off") => Ok(false),
+            _ => Err("expected on or off".to_string()),
+        };
+        match words.first().copied() {
+            None | Some("s" | "step") => {
+                let count = match words.get(1) {
+                    Some(n) => n.parse().map_err(|_| format!("invalid count '{n}'"))?,
+                    None => 1,
+                };
+                let mut stop = Stop::Step;
+                for _ in 0..count {
+                    stop = self.step();
+                    if stop != Stop::Step {
+                        break;
+                    }
+                }
+                Ok(format!("{stop}\n{}", self.status()))
+            }
+            Some("c" | "continue") => {
+                let stop = self.run();
+                Ok(format!("{stop}\n{}", self.status()))
+            }
+            Some("b" | "break") => {
+                let address = address(words.get(1))?;
+                self.add_breakpoint(address);
+                Ok(format!("breakpoint at {address}"))
+            }
+            Some("d" | "delete") => {
+                let address = address(words.get(1))?;
+                if self.remove_breakpoint(address) {
+                    Ok(format!("removed breakpoint at {address}"))
+                } else {
+                    Err(format!("no breakpoint at {address}"))
+                }
+            }
+            Some("o" | "out") => {
+                let enabled = on_off(words.get(1))?;
+                self.break_on_output(enabled);
+                Ok(format!(
+                    "break on output {}",
+                    if enabled { "on" } else { "off" }
+                ))
+            }
+            Some("w" | "watch") => {
+                let reg = reg(words.get(1))?;
+                self.watch(reg);
+                Ok(format!("watching {reg:?}"))
+            }
+            Some("u" | "unwatch") => {
+                let reg = reg(words.get(1))?;
+                self.unwatch(reg);
+                Ok(format!("stopped watching {reg:?}"))
+            }
+            Some("t" | "trace") => match words.get(1) {
+                None => Ok(self
+                    .trace
+                    .iter()
+                    .map(|entry| format!("{entry}\n"))
+                    .collect()),
+                word => {
+                    let enabled = on_off(word)?;
+                    self.set_tracing(enabled);
+                    Ok(format!("tracing {}", if enabled { "on" } else { "off" }))
+                }
+            },
+            Some("r" | "regs") => Ok(self.status()),
+            Some("l" | "list") => Ok(self.listing()),
+            Some("h" | "help") => Ok(HELP.to_string()),
+            Some(other) => Err(format!("unknown command '{other}', try help")),
+        }
+    }
+}
+
+pub const HELP: &str = "\
+s, step [n]         execute n instructions (default 1); an empty line steps once
+c, continue         run to a breakpoint, a watch, an output or the end
+b, break <addr>     set a breakpoint at an instruction address
+d, delete <addr>    remove a breakpoint
+o, out on|off       stop after each output
+w, watch a|b|c      stop when a register changes
+u, unwatch a|b|c    stop watching a register
+t, trace [on|off]   show the trace log, or turn tracing on or off
+r, regs             show the registers and output
+l, list             show the program with the next instruction marked
+q, quit             leave the debugger";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TESTINPUT;
+
+    fn debugger() -> Debugger {
+        Debugger::new(Computer::new(TESTINPUT))
+    }
+
+    #[test]
+    fn test_step() {
+        // 0,1 A = A / 2; 5,4 out A; 3,0 jump to 0
+        let mut debugger = debugger();
+        assert_eq!(debugger.step(), Stop::Step);
+        assert_eq!(debugger.computer().instruction_pointer(), 2);
+        assert_eq!(*debugger.computer().register(Reg::A), 364);
+        debugger.step();
+        assert_eq!(debugger.computer().output(), &[4]);
+    }
+
+    #[test]
+    fn test_run_to_end() {
+        let mut debugger = debugger();
+        assert_eq!(debugger.run(), Stop::Halted);
+        assert_eq!(
+            debugger.computer().output(),
+            &[4, 6, 3, 5, 6, 3, 5, 2, 1, 0]
+        );
+        assert_eq!(debugger.step(), Stop::Halted);
+    }
+
+    #[test]
+    fn test_error() {
+        let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 5,4,5,7";
+        let mut debugger = Debugger::new(Computer::new(input));
+        let error = Stop::Error(ComputerError::InvalidComboOperand { address: 2 });
+        assert_eq!(debugger.run(), error);
+        assert_eq!(debugger.computer().instruction_pointer(), 2);
+        assert_eq!(debugger.step(), error);
+        assert_eq!(error.to_string(), "invalid combo operand 7 at address 2");
+    }
+
+    #[test]
+    fn test_breakpoint() {
+        let mut debugger = debugger();
+        debugger.add_breakpoint(4);
+        assert_eq!(debugger.run(), Stop::Breakpoint(4));
+        assert_eq!(debugger.computer().output(), &[4]);
+        // continuing runs the jump, then stops at the breakpoint on the next pass
+        assert_eq!(debugger.run(), Stop::Breakpoint(4));
+        assert_eq!(debugger.computer().output(), &[4, 6]);
+        assert!(debugger.remove_breakpoint(4));
+        assert_eq!(debugger.run(), Stop::Halted);
+    }
+
+    #[test]
+    fn test_break_on_output() {
+        let mut debugger = debugger();
+        debugger.break_on_output(true);
+        assert_eq!(debugger.run(), Stop::Output(4));
+        assert_eq!(debugger.run(), Stop::Output(6));
+    }
+
+    #[test]
+    fn test_watch() {
+        let mut debugger = debugger();
+        debugger.watch(Reg::A);
+        assert_eq!(
+            debugger.run(),
+            Stop::Watch {
+                reg: Reg::A,
+                old: 729,
+                new: 364
+            }
+        );
+        debugger.unwatch(Reg::A);
+        debugger.watch(Reg::B);
+        assert_eq!(debugger.run(), Stop::Halted);
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut debugger = debugger();
+        debugger.set_tracing(true);
+        debugger.step();
+        debugger.step();
+        let trace = debugger.trace();
+        assert_eq!(trace.len(), 2);
+        assert_eq!(trace[0].operation, Operation::Adv);
+        assert_eq!(trace[0].registers, [364, 0, 0]);
+        assert_eq!(trace[1].instruction_pointer, 2);
+        assert_eq!(trace[1].output, Some(4));
+        assert_eq!(trace[1].to_string(), "  2: Out 4  A=364 B=0 C=0  out 4");
+    }
+
+    #[test]
+    fn test_listing() {
+        let mut debugger = debugger();
+        debugger.add_breakpoint(4);
+        debugger.step();
+        let listing = debugger.listing();
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("     0: 0 1 A = A / (2^ 1)"));
+        assert!(lines[1].starts_with("=>   2: 5 4 Output A MOD 8"));
+        assert!(lines[2].starts_with("  *  4: 3 0 Jump if A != 0 to 0"));
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut debugger = debugger();
+        assert!(debugger.command("b 4").is_ok());
+        assert!(debugger.command("w q").is_err());
+        assert!(debugger.command("d 2").is_err());
+        assert!(debugger.command("frobnicate").is_err());
+        let text = debugger.command("c").unwrap();
+        assert!(text.starts_with("breakpoint at 4\nip=4 A=364"));
+        let text = debugger.command("step 2").unwrap();
+        assert!(text.starts_with("stepped\nip=2"));
+        assert!(debugger.command("").unwrap().contains("output=[4, 6]"));
+    }
+}
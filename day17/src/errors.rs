@@ -0,0 +1,32 @@
+use std::{error::Error, fmt::Display};
+
+use aoc_common::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputerError {
+    /// Combo operand 7 is reserved, and is an error wherever a combo operand is read.
+    InvalidComboOperand { address: usize },
+    /// Only 0 to 7 are opcodes.
+    InvalidOpcode { opcode: u8 },
+}
+
+impl Display for ComputerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComputerError::InvalidComboOperand { address } => {
+                write!(f, "invalid combo operand 7 at address {address}")
+            }
+            ComputerError::InvalidOpcode { opcode } => write!(f, "invalid opcode {opcode}"),
+        }
+    }
+}
+
+impl Error for ComputerError {}
+
+impl From<ComputerError> for ParseError {
+    fn from(error: ComputerError) -> Self {
+        ParseError::new(error.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ComputerError>;
@@ -0,0 +1,72 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{ParseError, Result, Solution};
+use computer::Computer;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0";
+
+const TESTINPUT2: &str = "Register A: 121024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0";
+
+const TESTINPUT3: &str = "Register A: 107416870455451
+Register B: 0
+Register C: 0
+
+Program: 2,4,1,5,7,5,1,6,4,2,5,5,0,3,3,0";
+pub mod assembler;
+pub mod compiled;
+pub mod computer;
+pub mod debugger;
+pub mod errors;
+pub mod solver;
+pub mod word;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed = Computer;
+    type Part1 = String;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Computer::parse(input)
+    }
+
+    fn part1(computer: &Self::Parsed) -> Result<String> {
+        Ok(computer.clone().execute_program()?)
+    }
+
+    fn part2(computer: &Self::Parsed) -> Result<u64> {
+        computer
+            .find_quine()
+            .ok_or_else(|| ParseError::new("no initial A makes the program output itself"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day17::solve_part1(TESTINPUT).unwrap(),
+            "4,6,3,5,6,3,5,2,1,0"
+        );
+    }
+}
@@ -1,51 +1,49 @@
-#![allow(dead_code, unused)]
+use std::io::{BufRead, Write};
 
-use computer::Computer;
+use aoc_common::input;
+use day17::EMBEDDED_INPUT;
+use day17::computer::Computer;
+use day17::debugger::{self, Debugger};
 use stephen_morris_utils::timer::time;
-const INPUT: &str = include_str!("input.txt");
-
-#[allow(dead_code)]
-const TESTINPUT: &str = "Register A: 729
-Register B: 0
-Register C: 0
-
-Program: 0,1,5,4,3,0";
-
-const TESTINPUT2: &str = "Register A: 121024
-Register B: 0
-Register C: 0
-
-Program: 0,3,5,4,3,0";
-
-const TESTINPUT3: &str = "Register A: 107416870455451
-Register B: 0
-Register C: 0
-
-Program: 2,4,1,5,7,5,1,6,4,2,5,5,0,3,3,0";
-mod computer;
 
 fn main() {
-    let mut computer = time(|| Computer::new(INPUT), "computer");
+    let input = input::load_from_args(17, EMBEDDED_INPUT);
+    if std::env::args().any(|arg| arg == "--debug") {
+        debug(input::or_exit(Computer::parse(&input)));
+        return;
+    }
+    let mut computer = time(
+        || input::or_exit::<Computer, _>(Computer::parse(&input)),
+        "computer",
+    );
     let output = time(|| computer.execute_program(), "execute program");
 
     computer.print_duration();
     output.print_all();
 
-    let find_a = time(|| computer.find_initial_a2(), "find_a");
+    let computer: Computer = input::or_exit(Computer::parse(&input));
+    let find_a = time(|| computer.find_quine(), "find_a");
     find_a.print_all();
 }
 
-fn part1(input: &str) -> String {
-    let mut computer = Computer::new(input);
-    computer.execute_program()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TESTINPUT), "4,6,3,5,6,3,5,2,1,0");
+fn debug(computer: Computer) {
+    let mut debugger = Debugger::new(computer);
+    debugger.set_tracing(true);
+    println!("{}\n\n{}", debugger::HELP, debugger.listing());
+    let stdin = std::io::stdin();
+    loop {
+        print!("> ");
+        std::io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+        match line.trim() {
+            "q" | "quit" => break,
+            command => match debugger.command(command) {
+                Ok(text) => println!("{text}"),
+                Err(e) => println!("error: {e}"),
+            },
+        }
     }
 }
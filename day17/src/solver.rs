@@ -0,0 +1,302 @@
+//! Finds the smallest initial A for which a program outputs a given sequence, for any program.
+//!
+//! The program is run symbolically. Each register is the XOR of a constant and of low bits of
+//! the initial A shifted right, which every instruction preserves once its shift amount is known.
+//! Bits of A are fixed one at a time, only when a run needs them to choose a shift, a jump or an
+//! output, and a run stops as soon as its output differs from the target. Partial assignments
+//! are explored in order of the smallest A they allow, so the first one to produce the target is
+//! the answer, and running out of assignments proves there is none. Registers of any primitive
+//! width are supported, with the same shift semantics as `Word`.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use num_traits::PrimInt;
+
+use crate::{computer::Operation, word::Word};
+
+/// A run that takes more steps than this is treated as never halting.
+pub const MAX_STEPS: usize = 1 << 16;
+
+fn bits<W: PrimInt>() -> usize {
+    W::zero().count_zeros() as usize
+}
+
+/// The lowest `width` bits of the initial A shifted right by `shift`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Term {
+    shift: usize,
+    width: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Symbolic<W> {
+    terms: Vec<Term>,
+    constant: W,
+}
+
+impl<W: PrimInt + Word> Symbolic<W> {
+    fn constant(value: W) -> Self {
+        Symbolic {
+            terms: Vec::new(),
+            constant: value,
+        }
+    }
+
+    fn initial_a() -> Self {
+        Symbolic {
+            terms: vec![Term {
+                shift: 0,
+                width: bits::<W>(),
+            }],
+            constant: W::zero(),
+        }
+    }
+
+    fn shr(&self, shift: W) -> Self {
+        let shift = shift.to_usize().unwrap_or(usize::MAX).min(bits::<W>());
+        let terms = self
+            .terms
+            .iter()
+            .filter(|term| term.width > shift)
+            .map(|term| Term {
+                shift: term.shift + shift,
+                width: (term.width - shift).min(bits::<W>() - term.shift - shift),
+            })
+            .filter(|term| term.width > 0)
+            .collect();
+        Symbolic {
+            terms,
+            constant: Word::shr(&self.constant, &W::from(shift).unwrap()),
+        }
+    }
+
+    fn xor(&self, other: &Self) -> Self {
+        let mut terms = self.terms.clone();
+        for term in &other.terms {
+            match terms.iter().position(|t| t == term) {
+                Some(idx) => _ = terms.swap_remove(idx),
+                None => terms.push(*term),
+            }
+        }
+        Symbolic {
+            terms,
+            constant: self.constant ^ other.constant,
+        }
+    }
+
+    fn low_bits(&self, count: usize) -> Self {
+        let terms = self
+            .terms
+            .iter()
+            .map(|term| Term {
+                width: term.width.min(count),
+                ..*term
+            })
+            .collect();
+        Symbolic {
+            terms,
+            constant: self.constant & ((W::one() << count) - W::one()),
+        }
+    }
+}
+
+/// Some bits of the initial A fixed to values, and the others still free.
+#[derive(Debug, Clone, Copy)]
+struct Assignment<W> {
+    known: W,
+    value: W,
+}
+
+impl<W: PrimInt + Word> Assignment<W> {
+    /// The value of bit `i`, or `Err` with the lowest free bit of A that it depends on.
+    fn bit(&self, symbolic: &Symbolic<W>, i: usize) -> Result<bool, u32> {
+        let mut bit = ((symbolic.constant >> i) & W::one()).is_one();
+        let mut free = None;
+        for term in symbolic.terms.iter().filter(|term| i < term.width) {
+            let j = i + term.shift;
+            if (self.known >> j) & W::one() == W::zero() {
+                free = Some(free.map_or(j as u32, |free: u32| free.min(j as u32)));
+            } else {
+                bit ^= ((self.value >> j) & W::one()).is_one();
+            }
+        }
+        free.map_or(Ok(bit), Err)
+    }
+
+    fn low_bits(&self, symbolic: &Symbolic<W>, count: usize) -> Result<W, u32> {
+        (0..count).try_fold(W::zero(), |value, i| match self.bit(symbolic, i)? {
+            true => Ok(value | W::one() << i),
+            false => Ok(value),
+        })
+    }
+
+    fn is_zero(&self, symbolic: &Symbolic<W>) -> Result<bool, u32> {
+        let mut free = None;
+        for i in 0..bits::<W>() {
+            match self.bit(symbolic, i) {
+                Ok(true) => return Ok(false),
+                Ok(false) => {}
+                Err(bit) => free = free.or(Some(bit)),
+            }
+        }
+        free.map_or(Ok(true), Err)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Solver<'a, W> {
+    program: &'a [u8],
+    b: W,
+    c: W,
+    target: &'a [u8],
+}
+
+impl<'a, W: PrimInt + Word> Solver<'a, W> {
+    pub fn new(program: &'a [u8], b: W, c: W, target: &'a [u8]) -> Self {
+        Solver {
+            program,
+            b,
+            c,
+            target,
+        }
+    }
+
+    /// Whether every initial A allowed by the assignment outputs the target, or `Err` with a free
+    /// bit of A that the run depends on.
+    fn run(&self, assignment: &Assignment<W>) -> Result<bool, u32> {
+        let mut registers = [
+            Symbolic::initial_a(),
+            Symbolic::constant(self.b),
+            Symbolic::constant(self.c),
+        ];
+        let mut ip = 0;
+        let mut outputs = 0;
+        for _ in 0..MAX_STEPS {
+            let (Some(&opcode), Some(&operand)) = (self.program.get(ip), self.program.get(ip + 1))
+            else {
+                return Ok(outputs == self.target.len());
+            };
+            ip += 2;
+            let Ok(operation) = Operation::try_from(opcode) else {
+                return Ok(false);
+            };
+            let combo = match operand {
+                0..=3 => Symbolic::constant(W::from_u8(operand)),
+                4..=6 => registers[operand as usize - 4].clone(),
+                _ if operation.takes_combo() => return Ok(false),
+                _ => Symbolic::constant(W::zero()),
+            };
+            let [a, b, c] = &mut registers;
+            let bits = bits::<W>();
+            match operation {
+                Operation::Adv => *a = a.shr(assignment.low_bits(&combo, bits)?),
+                Operation::Bxl => *b = b.xor(&Symbolic::constant(W::from_u8(operand))),
+                Operation::Bst => *b = combo.low_bits(3),
+                Operation::Jnz => {
+                    if !assignment.is_zero(a)? {
+                        ip = operand as usize;
+                    }
+                }
+                Operation::Bxc => *b = b.xor(c),
+                Operation::Out => {
+                    let digit = assignment.low_bits(&combo, 3)?.low_bits();
+                    if self.target.get(outputs) != Some(&digit) {
+                        return Ok(false);
+                    }
+                    outputs += 1;
+                }
+                Operation::Bdv => *b = a.shr(assignment.low_bits(&combo, bits)?),
+                Operation::Cdv => *c = a.shr(assignment.low_bits(&combo, bits)?),
+            }
+        }
+        Ok(false)
+    }
+
+    /// The smallest initial A for which the program outputs exactly the target and halts.
+    pub fn solve(&self) -> Option<W> {
+        let mut queue = BinaryHeap::from([Reverse((W::zero(), W::zero()))]);
+        while let Some(Reverse((value, known))) = queue.pop() {
+            match self.run(&Assignment { known, value }) {
+                Ok(true) => return Some(value),
+                Ok(false) => {}
+                Err(bit) => {
+                    let bit = W::one() << bit as usize;
+                    queue.push(Reverse((value, known | bit)));
+                    queue.push(Reverse((value | bit, known | bit)));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        TESTINPUT2, TESTINPUT3,
+        assembler::{assemble, computer_input},
+        computer::{Computer, Register},
+    };
+
+    fn output(program: &[u8], a: Register) -> Vec<u8> {
+        let mut computer = Computer::new(&computer_input(a, program));
+        computer.execute_program().unwrap();
+        computer.output().to_vec()
+    }
+
+    #[test]
+    fn test_quine() {
+        assert_eq!(Computer::new(TESTINPUT2).find_quine(), Some(117440));
+        let computer = Computer::new(TESTINPUT3);
+        let a = computer.find_quine().unwrap();
+        assert!(a <= 107416870455451);
+        assert_eq!(output(computer.program(), a), computer.program());
+    }
+
+    #[test]
+    fn test_wide_registers() {
+        // prints A in octal, which needs an initial A of more than 64 bits
+        let program = assemble("loop: out A\nadv 3\njnz loop").unwrap();
+        let a = 3u128.pow(50);
+        let mut computer = Computer::<u128>::parse(&computer_input(0, &program)).unwrap();
+        computer.reset(a);
+        computer.execute_program().unwrap();
+        let target = computer.output().to_vec();
+        assert_eq!(Solver::new(&program, 0u64, 0, &target).solve(), None);
+        assert_eq!(Solver::new(&program, 0u128, 0, &target).solve(), Some(a));
+    }
+
+    #[test]
+    fn test_other_programs() {
+        let programs = [
+            // shifts by 2 and outputs twice per loop
+            "loop: out A\nbst A\nbxl 3\nout B\nadv 2\njnz loop",
+            // shifts by a varying amount
+            "loop: bst A\nbxl 1\ncdv B\nbxc\nout B\nbst A\nadv 1\nadv B\njnz loop",
+            // uses C before setting it, from the input
+            "loop: bxc\nout B\nbst A\ncdv 1\nadv 1\njnz loop",
+        ];
+        for source in programs {
+            let program = assemble(source).unwrap();
+            let mut seen = std::collections::HashMap::new();
+            for a in 0..128 {
+                seen.entry(output(&program, a)).or_insert(a);
+            }
+            for (target, &a) in &seen {
+                let solver = Solver::new(&program, 0, 0, target);
+                assert_eq!(solver.solve(), Some(a), "{source} {target:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_solution() {
+        let program = assemble("out 1").unwrap();
+        assert_eq!(Solver::new(&program, 0u64, 0, &[2]).solve(), None);
+        assert_eq!(Solver::new(&program, 0u64, 0, &[1, 1]).solve(), None);
+        let program = assemble("loop: out 0\njnz loop").unwrap();
+        assert_eq!(Solver::new(&program, 0u64, 0, &[0, 0]).solve(), None);
+        assert_eq!(Solver::new(&program, 0u64, 0, &[0]).solve(), Some(0));
+    }
+}
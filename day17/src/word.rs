@@ -0,0 +1,88 @@
+//! The values a register can hold. Shifts are defined for every shift amount: shifting out every
+//! bit gives zero, where the primitive `>>` would overflow.
+
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use num_bigint::BigUint;
+
+pub trait Word: Clone + Debug + Default + Display + FromStr + PartialEq {
+    fn from_u8(value: u8) -> Self;
+    fn is_zero(&self) -> bool;
+    /// The value modulo 8.
+    fn low_bits(&self) -> u8;
+    fn xor(&self, other: &Self) -> Self;
+    fn shr(&self, shift: &Self) -> Self;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {$(
+        impl Word for $t {
+            fn from_u8(value: u8) -> Self {
+                value.into()
+            }
+            fn is_zero(&self) -> bool {
+                *self == 0
+            }
+            fn low_bits(&self) -> u8 {
+                (*self % 8) as u8
+            }
+            fn xor(&self, other: &Self) -> Self {
+                self ^ other
+            }
+            fn shr(&self, shift: &Self) -> Self {
+                u32::try_from(*shift)
+                    .ok()
+                    .and_then(|shift| self.checked_shr(shift))
+                    .unwrap_or(0)
+            }
+        }
+    )*};
+}
+
+impl_word!(u32, u64, u128);
+
+impl Word for BigUint {
+    fn from_u8(value: u8) -> Self {
+        value.into()
+    }
+    fn is_zero(&self) -> bool {
+        num_traits::Zero::is_zero(self)
+    }
+    fn low_bits(&self) -> u8 {
+        (self.iter_u32_digits().next().unwrap_or(0) % 8) as u8
+    }
+    fn xor(&self, other: &Self) -> Self {
+        self ^ other
+    }
+    fn shr(&self, shift: &Self) -> Self {
+        match u64::try_from(shift) {
+            Ok(shift) if shift < self.bits() => self >> shift,
+            _ => BigUint::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shr() {
+        assert_eq!(Word::shr(&u32::MAX, &32), 0);
+        assert_eq!(Word::shr(&u64::MAX, &63), 1);
+        assert_eq!(Word::shr(&u64::MAX, &64), 0);
+        assert_eq!(Word::shr(&u128::MAX, &u128::MAX), 0);
+        let big = BigUint::from(1u8) << 200u32;
+        assert_eq!(Word::shr(&big, &BigUint::from(199u8)), BigUint::from(2u8));
+        assert!(Word::shr(&big, &(BigUint::from(1u8) << 100u32)).is_zero());
+    }
+
+    #[test]
+    fn test_low_bits() {
+        assert_eq!(13u32.low_bits(), 5);
+        assert_eq!(((BigUint::from(1u8) << 100u32) + 13u8).low_bits(), 5);
+    }
+}
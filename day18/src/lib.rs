@@ -0,0 +1,118 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution, grid};
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0";
+
+pub mod memory;
+
+use memory::Memory;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed = Memory;
+    type Part1 = usize;
+    // the coordinates of the first blocking byte, formatted as "x,y"
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Memory::parse(input)
+    }
+
+    fn part1(memory: &Self::Parsed) -> Result<usize> {
+        Ok(memory.min_path_period(PERIOD))
+    }
+
+    fn part2(memory: &Self::Parsed) -> Result<String> {
+        let point = memory.find_disconnection();
+        Ok(format!("{},{}", point.x, point.y))
+    }
+}
+
+pub fn part1_jobs(input: &str) -> usize {
+    let mut memory = Memory::new(input);
+    memory.min_path_period2(PERIOD)
+}
+
+#[cfg(test)]
+mod constants {
+    pub const HEIGHT: usize = 7;
+    pub const WIDTH: usize = 7;
+    pub const PERIOD: usize = 12;
+}
+#[cfg(not(test))]
+mod constants {
+    pub const HEIGHT: usize = 71;
+    pub const WIDTH: usize = 71;
+    pub const PERIOD: usize = 1024;
+}
+
+use constants::{HEIGHT, PERIOD, WIDTH};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Memory::parse(input).unwrap_err().to_string();
+        assert_eq!(error("1,2\n3"), "line 2: expected 'x,y'");
+        assert_eq!(
+            error("1,2\n\n7,0"),
+            "line 3: 7,0 is outside the 7 by 7 memory"
+        );
+        assert_eq!(error("1,-2"), "line 1: invalid number '-2'");
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day18::solve_part1(TESTINPUT).unwrap(), 22);
+    }
+    #[test]
+    fn test_part1_jobs() {
+        assert_eq!(part1_jobs(TESTINPUT), 22);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day18::solve_part2(TESTINPUT).unwrap(), "6,1");
+    }
+
+    #[test]
+    fn test_constants() {
+        println!("height: {HEIGHT}, width: {WIDTH}");
+    }
+}
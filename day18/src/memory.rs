@@ -2,7 +2,10 @@ type Generation = usize;
 
 use std::collections::VecDeque;
 
-use stephen_morris_utils::get_numbers;
+use aoc_common::{
+    Result, parse,
+    search::{self, Search},
+};
 
 use crate::{
     constants::{HEIGHT, WIDTH},
@@ -15,7 +18,6 @@ enum PathStatus {
     NotKnown,
     Unreachable,
     ReachableIn(Generation),
-    Reachable,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -28,57 +30,43 @@ pub struct Memory {
 
 impl Memory {
     pub fn new(input: &str) -> Self {
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Reads the falling bytes, one 'x,y' per line, in the order they fall.
+    pub fn parse(input: &str) -> Result<Self> {
+        let byte_list = parse::lines(input, |line| match parse::numbers(line, ',')?[..] {
+            [x, y] if x < WIDTH && y < HEIGHT => Ok(Point { x, y }),
+            [x, y] => Err(format!("{x},{y} is outside the {WIDTH} by {HEIGHT} memory")),
+            _ => Err("expected 'x,y'".to_string()),
+        })?;
         let mut grid_corrupt_from: Grid<Generation> = Grid::new(WIDTH, HEIGHT, Generation::MAX);
-        let mut byte_list = Vec::with_capacity(3450);
-        for (generation, line) in input.lines().enumerate() {
-            let mut numbers = get_numbers(line);
-            if numbers.len() >= 2 {
-                let point = Point {
-                    x: numbers[0],
-                    y: numbers[1],
-                };
-                byte_list.push(point);
-                grid_corrupt_from[point] = generation as Generation;
-            }
+        for (generation, &point) in byte_list.iter().enumerate() {
+            grid_corrupt_from[point] = generation as Generation;
         }
         let start_point = Point::new(0, 0);
         let end_point = Point::new(WIDTH - 1, HEIGHT - 1);
-        Memory {
+        Ok(Memory {
             grid_corrupt_from,
             byte_list,
             start_point,
             end_point,
-        }
+        })
     }
 
     pub fn min_path_period(&self, period: Generation) -> Generation {
-        fn set_reachable_in(grid: &mut Grid<PathStatus>, point: Point, generation: Generation) {
-            if grid[point] == PathStatus::Unreachable {
-                return;
-            }
-            if let PathStatus::ReachableIn(old_generation) = grid[point] {
-                if old_generation <= generation {
-                    return;
-                }
-            }
-            grid[point] = PathStatus::ReachableIn(generation);
-            let v: Vec<Point> = grid.orthogonal_neighbors(point).map(|(p, _)| p).collect();
-            for next_point in v {
-                set_reachable_in(grid, next_point, generation + 1);
-            }
-        }
-        let mut grid = Grid::new_default(WIDTH, HEIGHT);
-        for (point, &corrupt_from) in &self.grid_corrupt_from {
-            if corrupt_from < period {
-                grid[point] = PathStatus::Unreachable;
-            }
-        }
-        set_reachable_in(&mut grid, Point::new(0, 0), 0);
-        if let PathStatus::ReachableIn(generation) = grid[Point::new(WIDTH - 1, HEIGHT - 1)] {
-            generation
-        } else {
-            unreachable!()
-        }
+        self.search(period).cost().unwrap()
+    }
+
+    // a breadth-first search through the bytes which have not fallen by `period`
+    fn search(&self, period: Generation) -> Search<Point, Generation> {
+        let end_point = self.end_point;
+        search::grid_bfs(
+            &self.grid_corrupt_from,
+            self.start_point,
+            |&corrupt_from| corrupt_from >= period,
+            |&point| point == end_point,
+        )
     }
 
     pub fn min_path_period2(&self, period: Generation) -> Generation {
@@ -96,10 +84,10 @@ impl Memory {
             if grid[job.point] == PathStatus::Unreachable {
                 continue;
             }
-            if let PathStatus::ReachableIn(old_generation) = grid[job.point] {
-                if old_generation <= job.generation {
-                    continue;
-                }
+            if let PathStatus::ReachableIn(old_generation) = grid[job.point]
+                && old_generation <= job.generation
+            {
+                continue;
             }
             grid[job.point] = PathStatus::ReachableIn(job.generation);
             let v: Vec<Point> = grid
@@ -121,27 +109,7 @@ impl Memory {
     }
 
     fn is_connected_after(&self, period: Generation) -> bool {
-        fn set_reachable(grid: &mut Grid<PathStatus>, point: Point, end_point: Point) -> bool {
-            if grid[point] != PathStatus::NotKnown {
-                return false;
-            }
-            grid[point] = PathStatus::Reachable;
-            let v: Vec<Point> = grid.orthogonal_neighbors(point).map(|(p, _)| p).collect();
-            for next_point in v {
-                if next_point == end_point || set_reachable(grid, next_point, end_point) {
-                    return true;
-                }
-            }
-            false
-        }
-
-        let mut grid = Grid::new_default(WIDTH, HEIGHT);
-        for (point, &corrupt_from) in &self.grid_corrupt_from {
-            if corrupt_from < period {
-                grid[point] = PathStatus::Unreachable;
-            }
-        }
-        set_reachable(&mut grid, self.start_point, self.end_point)
+        self.search(period).goal.is_some()
     }
 
     pub fn find_disconnection(&self) -> Point {
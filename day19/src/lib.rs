@@ -0,0 +1,63 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution, grid};
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb";
+
+pub mod towels;
+
+use towels::TowelWords;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed = TowelWords;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let mut towel_words = TowelWords::parse(input)?;
+        towel_words.build_minimal_list();
+        Ok(towel_words)
+    }
+
+    fn part1(towel_words: &Self::Parsed) -> Result<usize> {
+        Ok(towel_words.count_possible_targets())
+    }
+
+    fn part2(towel_words: &Self::Parsed) -> Result<usize> {
+        Ok(towel_words.count_possible_ways())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day19::solve_part1(TESTINPUT).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day19::solve_part2(TESTINPUT).unwrap(), 16);
+    }
+}
@@ -1,28 +1,11 @@
-#![allow(dead_code, unused)]
-
-use stephen_morris_utils::grid;
+use aoc_common::input;
+use day19::EMBEDDED_INPUT;
+use day19::towels::TowelWords;
 use stephen_morris_utils::timer::time;
 
-const INPUT: &str = include_str!("input.txt");
-
-#[allow(dead_code)]
-const TESTINPUT: &str = "r, wr, b, g, bwu, rb, gb, br
-
-brwrr
-bggr
-gbbr
-rrbgbr
-ubwu
-bwurrg
-brgr
-bbrgwb";
-
-mod towels;
-
-use towels::TowelWords;
-
 fn main() {
-    let mut towel_words = time(|| TowelWords::new(INPUT), "new");
+    let input = input::load_from_args(19, EMBEDDED_INPUT);
+    let mut towel_words = time(|| input::or_exit(TowelWords::parse(&input)), "new");
     let minimal_list = time(|| towel_words.build_minimal_list(), "minimal list");
     let count = time(|| towel_words.count_possible_targets(), "count");
 
@@ -34,30 +17,3 @@ fn main() {
 
     ways.print_all();
 }
-
-fn part1(input: &str) -> usize {
-    let mut towel_words = TowelWords::new(input);
-    towel_words.build_minimal_list();
-    towel_words.count_possible_targets()
-}
-
-fn part2(input: &str) -> usize {
-    let mut towel_words = TowelWords::new(input);
-    towel_words.build_minimal_list();
-    towel_words.count_possible_ways()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TESTINPUT), 6);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TESTINPUT), 16);
-    }
-}
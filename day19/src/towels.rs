@@ -4,6 +4,11 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+use aoc_common::{
+    ParseError, Result,
+    parse::{self, LineResult},
+};
+
 #[derive(Clone, PartialEq, Eq)]
 struct Word(Vec<u8>);
 
@@ -52,6 +57,15 @@ impl Debug for Word {
     }
 }
 
+/// A towel pattern or design, which must have at least one stripe.
+fn stripes(word: &str) -> LineResult<Word> {
+    match word.bytes().find(|byte| !byte.is_ascii_alphabetic()) {
+        _ if word.is_empty() => Err("expected a sequence of stripes".to_string()),
+        Some(byte) => Err(format!("unexpected stripe {:?}", byte as char)),
+        None => Ok(Word(word.bytes().collect())),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
 struct Basis {
     source_list: Vec<Word>,
@@ -61,11 +75,7 @@ struct Basis {
 }
 
 impl Basis {
-    fn new(input: &str) -> Self {
-        let mut source_list = Vec::new();
-        for word in input.split(", ") {
-            source_list.push(Word(word.bytes().collect()));
-        }
+    fn new(mut source_list: Vec<Word>) -> Self {
         source_list.sort();
         let single_letters = source_list
             .iter()
@@ -135,16 +145,24 @@ pub struct TowelWords {
 
 impl TowelWords {
     pub fn new(input: &str) -> Self {
-        let mut split = input.split("\n\n");
-        let basis = Basis::new(split.next().unwrap());
-        let mut target_list: Vec<Word> = split
-            .next()
-            .unwrap()
-            .lines()
-            .map(|line| Word(line.bytes().collect()))
-            .collect();
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Reads the towel patterns, separated by ", ", then a blank line and one design per line.
+    pub fn parse(input: &str) -> Result<Self> {
+        let (towels, designs, offset) = parse::sections(input)?;
+        let towels = parse::lines(towels, |line| {
+            line.split(", ")
+                .map(|towel| stripes(towel.trim()))
+                .collect()
+        })?;
+        let [towels] = <[Vec<Word>; 1]>::try_from(towels)
+            .map_err(|_| ParseError::new("expected the towel patterns on a single line"))?;
+        let basis = Basis::new(towels);
+        let mut target_list =
+            parse::lines(designs, |line| stripes(line.trim())).map_err(|e| e.offset(offset))?;
         target_list.sort();
-        Self { basis, target_list }
+        Ok(Self { basis, target_list })
     }
     pub fn count_possible_targets(&self) -> usize {
         let mut count = 0;
@@ -174,6 +192,25 @@ mod tests {
     use crate::{towels::*, *};
 
     #[test]
+    fn test_parse_errors() {
+        let error = |input| TowelWords::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("r, wr\nb\n\nrwr"),
+            "expected the towel patterns on a single line"
+        );
+        assert_eq!(
+            error("r, , wr\n\nrwr"),
+            "line 1: expected a sequence of stripes"
+        );
+        assert_eq!(error("r, wr\n\nrwr\nr-r"), "line 4: unexpected stripe '-'");
+        assert_eq!(
+            error("r, wr\nrwr"),
+            "expected a blank line between the two sections"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-input")]
     fn test_new() {
         let word = TowelWords::new(INPUT);
         println!("{:#?}", word.basis);
@@ -0,0 +1,74 @@
+#![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution, grid};
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+pub mod race;
+
+use race::Race;
+
+// for testing use a minimum saving of 12 picoseconds, giving 8
+#[cfg(test)]
+const MIN_SAVING: usize = 12;
+#[cfg(not(test))]
+const MIN_SAVING: usize = 100;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed = Race;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Race::parse(input)
+    }
+
+    fn part1(race: &Self::Parsed) -> Result<usize> {
+        let mut race = race.clone();
+        race.find_cheats();
+        Ok(race.count_cheats_over(MIN_SAVING))
+    }
+
+    fn part2(race: &Self::Parsed) -> Result<usize> {
+        Ok(race.count_long_cheats(MIN_SAVING))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day20::solve_part1(TESTINPUT).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_part2() {
+        // assert_eq!(Day20::solve_part2(TESTINPUT).unwrap(), 16);
+    }
+}
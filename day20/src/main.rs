@@ -1,33 +1,12 @@
-#![allow(dead_code, unused)]
-
-use stephen_morris_utils::grid;
+use aoc_common::{input, span};
+use day20::EMBEDDED_INPUT;
+use day20::race::Race;
 use stephen_morris_utils::timer::time;
 
-const INPUT: &str = include_str!("input.txt");
-
-#[allow(dead_code)]
-const TESTINPUT: &str = "###############
-#...#...#.....#
-#.#.#.#.#.###.#
-#S#...#.#.#...#
-#######.#.#.###
-#######.#.#...#
-#######.#.###.#
-###..E#...#...#
-###.#######.###
-#...###...#...#
-#.#####.#.###.#
-#.#...#.#.#...#
-#.#.#.#.#.#.###
-#...#...#...###
-###############";
-
-mod race;
-
-use race::Race;
-
 fn main() {
-    let mut race = time(|| Race::new(INPUT), "new");
+    let input = input::load_from_args(20, EMBEDDED_INPUT);
+    span::enable();
+    let mut race = time(|| input::or_exit(Race::parse(&input)), "new");
     let cheats = time(|| race.find_cheats(), "cheats");
     let count = time(|| race.count_cheats_over(100), "count");
 
@@ -38,30 +17,6 @@ fn main() {
     let count_long = time(|| race.count_long_cheats(100), "long");
 
     count_long.print_all();
-}
-
-fn part1(input: &str) -> usize {
-    // for testing use a minimum saving of 12 picoseconds, giving 8
-    let mut race = Race::new(TESTINPUT);
-    race.find_cheats();
-    race.count_cheats_over(12)
-}
-
-fn part2(input: &str) -> usize {
-    0
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TESTINPUT), 8);
-    }
 
-    #[test]
-    fn test_part2() {
-        // assert_eq!(part2(TESTINPUT), 16);
-    }
+    print!("\n{}", span::take());
 }
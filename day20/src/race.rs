@@ -1,10 +1,12 @@
 use std::{collections::HashMap, ops::Deref};
 
+use aoc_common::{ParseError, Result, search, span};
+
 use crate::grid::{Direction, Grid, Point, Vector};
 
 type Count = usize;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Cheat {
     start: Point,
     end: Point,
@@ -51,7 +53,7 @@ impl PartialOrd<Count> for Cheat {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Race {
     grid: Grid<u8>,
     start: Point,
@@ -63,81 +65,77 @@ pub struct Race {
 
 impl Race {
     pub fn new(input: &str) -> Self {
-        let grid = Grid::from(input);
-        let start = grid.find(b'S').unwrap();
-        let end = grid.find(b'E').unwrap();
-        let mut track = Grid::new_default(grid.width, grid.height);
-        let mut path = Vec::new();
-        let cheats = Vec::new();
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
 
-        let mut current_pos = start;
-        let mut current_step = 0;
-        track[start] = Some(0);
-        path.push(current_pos);
+    /// Reads a racetrack of walls `#` and track `.` from the start `S` to the end `E`.
+    pub fn parse(input: &str) -> Result<Self> {
+        let _span = span::enter("Race::new");
+        let grid = Grid::parse(input, |byte| b"#.SE".contains(&byte))?;
+        let start = grid.find_one(b'S', "start S on the racetrack")?;
+        let end = grid.find_one(b'E', "end E on the racetrack")?;
+        let cheats = Vec::new();
 
-        while current_pos != end {
-            for (next_pos, &value) in grid.orthogonal_neighbors(current_pos) {
-                if (value == b'.' || value == b'E') && track[next_pos].is_none() {
-                    current_pos = next_pos;
-                    break;
-                }
-            }
-            current_step += 1;
-            track[current_pos] = Some(current_step);
-            path.push(current_pos);
+        // the track has no branches, so the shortest path is the whole track
+        let search = search::grid_bfs(&grid, start, |&c| c != b'#', |&p| p == end);
+        let path = search
+            .path()
+            .ok_or_else(|| ParseError::new("the track doesn't reach the end"))?;
+        let mut track = Grid::new_default(grid.width, grid.height);
+        for (step, &point) in path.iter().enumerate() {
+            track[point] = Some(step);
         }
 
-        Self {
+        Ok(Self {
             grid,
             start,
             end,
             track,
             path,
             cheats,
-        }
+        })
     }
 
     pub fn find_cheats(&mut self) {
+        let _span = span::enter("Race::find_cheats");
         for (point, &value) in &self.grid {
             if value == b'#' {
                 if let (Some(east), Some(west)) = (
                     self.grid.add_direction(point, Direction::East),
                     self.grid.add_direction(point, Direction::West),
-                ) {
-                    if let (Some(v1), Some(v2)) = (self.track[east], self.track[west]) {
-                        if v1 < v2 {
-                            self.cheats.push(Cheat {
-                                start: east,
-                                end: west,
-                                saving: v2 - v1 - 2,
-                            });
-                        } else {
-                            self.cheats.push(Cheat {
-                                start: west,
-                                end: east,
-                                saving: v1 - v2 - 2,
-                            });
-                        }
+                ) && let (Some(v1), Some(v2)) = (self.track[east], self.track[west])
+                {
+                    if v1 < v2 {
+                        self.cheats.push(Cheat {
+                            start: east,
+                            end: west,
+                            saving: v2 - v1 - 2,
+                        });
+                    } else {
+                        self.cheats.push(Cheat {
+                            start: west,
+                            end: east,
+                            saving: v1 - v2 - 2,
+                        });
                     }
                 }
                 if let (Some(north), Some(south)) = (
                     self.grid.add_direction(point, Direction::North),
                     self.grid.add_direction(point, Direction::South),
-                ) {
-                    if let (Some(v1), Some(v2)) = (self.track[north], self.track[south]) {
-                        if v1 < v2 {
-                            self.cheats.push(Cheat {
-                                start: north,
-                                end: south,
-                                saving: v2 - v1 - 2,
-                            });
-                        } else {
-                            self.cheats.push(Cheat {
-                                start: south,
-                                end: north,
-                                saving: v1 - v2 - 2,
-                            });
-                        }
+                ) && let (Some(v1), Some(v2)) = (self.track[north], self.track[south])
+                {
+                    if v1 < v2 {
+                        self.cheats.push(Cheat {
+                            start: north,
+                            end: south,
+                            saving: v2 - v1 - 2,
+                        });
+                    } else {
+                        self.cheats.push(Cheat {
+                            start: south,
+                            end: north,
+                            saving: v1 - v2 - 2,
+                        });
                     }
                 }
             }
@@ -149,6 +147,7 @@ impl Race {
     }
 
     pub fn count_long_cheats(&self, threshhold: Count) -> Count {
+        let _span = span::enter("Race::count_long_cheats");
         // <saving, count>
         let mut hm: HashMap<Count, Count> = HashMap::new();
         for path_index1 in 0..self.path.len() - 1 {
@@ -172,6 +171,23 @@ impl Race {
 mod tests {
     use crate::{race::*, *};
 
+    #[test]
+    fn test_parse_errors() {
+        let error = |input| Race::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("#####\n#S.E#\n##x##"),
+            "line 3: unexpected character 'x'"
+        );
+        assert_eq!(
+            error("#####\n#S..#\n#####"),
+            "expected one end E on the racetrack, found 0"
+        );
+        assert_eq!(
+            error("#####\n#S#E#\n#####"),
+            "the track doesn't reach the end"
+        );
+    }
+
     #[test]
     fn test_new() {
         let race = Race::new(TESTINPUT);
@@ -28,6 +28,8 @@ use std::{
     ops::{Add, AddAssign},
 };
 
+use aoc_common::{Result, parse};
+
 use crate::grid::{Grid, Point, Vector};
 
 type Cost = isize;
@@ -305,16 +307,17 @@ pub struct Scenario {
 
 impl Scenario {
     pub fn new(input: &str, no_of_remotes: Cost) -> Self {
-        let mut targets = Vec::with_capacity(4);
-        for line in input.lines() {
-            let mut bytes = line.bytes();
-            targets.push([
-                bytes.next().unwrap(),
-                bytes.next().unwrap(),
-                bytes.next().unwrap(),
-                bytes.next().unwrap(),
-            ]);
-        }
+        Self::parse(input, no_of_remotes).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Reads one door code per line, each three digits followed by 'A'.
+    pub fn parse(input: &str, no_of_remotes: Cost) -> Result<Self> {
+        let targets = parse::lines(input, |line| match line.trim().as_bytes() {
+            &[a, b, c, b'A'] if [a, b, c].iter().all(u8::is_ascii_digit) => Ok([a, b, c, b'A']),
+            _ => Err(format!(
+                "expected a code of three digits and 'A', found '{line}'"
+            )),
+        })?;
 
         let mut controlled_by = None;
         for _ in 0..no_of_remotes {
@@ -322,10 +325,10 @@ impl Scenario {
             controlled_by = Some(Box::new(new_keypad));
         }
         let room_keypad = Keypad::new(KeypadLayoutStyle::Room, controlled_by);
-        Self {
+        Ok(Self {
             room_keypad,
             targets,
-        }
+        })
     }
 
     pub fn cost_for_targets(&mut self) -> Cost {
@@ -343,6 +346,18 @@ mod tests {
     use super::*;
     use crate::*;
     #[test]
+    fn test_parse_errors() {
+        let error = |input| Scenario::parse(input, 3).unwrap_err().to_string();
+        assert_eq!(
+            error("029A\n98A"),
+            "line 2: expected a code of three digits and 'A', found '98A'"
+        );
+        assert_eq!(
+            error("029A\n\n9x8A"),
+            "line 3: expected a code of three digits and 'A', found '9x8A'"
+        );
+    }
+    #[test]
     fn test_cost_for_sequence() {
         let mut scenario = Scenario::new(TESTINPUT, 3);
         let cost = scenario.cost_for_targets();
@@ -0,0 +1,42 @@
+// #![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution, grid};
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "029A
+980A
+179A
+456A
+379A";
+
+pub mod keypads;
+
+use keypads::Scenario;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    // the keypad chain is built when parsing, so each part has its own scenario
+    type Parsed = (Scenario, Scenario);
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Ok((Scenario::parse(input, 3)?, Scenario::parse(input, 26)?))
+    }
+
+    fn part1((scenario, _): &Self::Parsed) -> Result<isize> {
+        Ok(scenario.clone().cost_for_targets())
+    }
+
+    fn part2((_, scenario): &Self::Parsed) -> Result<isize> {
+        Ok(scenario.clone().cost_for_targets())
+    }
+}
@@ -1,29 +1,23 @@
-// #![allow(dead_code, unused)]
-
-use stephen_morris_utils::grid;
+use aoc_common::input;
+use day21::EMBEDDED_INPUT;
+use day21::keypads::Scenario;
 use stephen_morris_utils::timer::time;
 
-const INPUT: &str = include_str!("input.txt");
-
-#[allow(dead_code)]
-const TESTINPUT: &str = "029A
-980A
-179A
-456A
-379A";
-
-mod keypads;
-
-use keypads::Scenario;
-
 fn main() {
-    let mut scenario = time(|| Scenario::new(INPUT, 3), "Scenario::new()");
+    let input = input::load_from_args(21, EMBEDDED_INPUT);
+    let mut scenario = time(
+        || input::or_exit(Scenario::parse(&input, 3)),
+        "Scenario::new()",
+    );
     let cost = time(|| scenario.cost_for_targets(), "cost");
 
     scenario.print_duration();
     cost.print_all();
 
-    let mut scenario = time(|| Scenario::new(INPUT, 26), "Scenario::new()");
+    let mut scenario = time(
+        || input::or_exit(Scenario::parse(&input, 26)),
+        "Scenario::new()",
+    );
     let cost = time(|| scenario.cost_for_targets(), "cost");
 
     scenario.print_duration();
@@ -0,0 +1,46 @@
+// #![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution};
+use secret::{most_bananas, parse_input, sum_secrets};
+use stephen_morris_utils::fixed_queue;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "1
+10
+100
+2024";
+
+#[allow(dead_code)]
+const TESTINPUT2: &str = "1
+2
+3
+2024";
+
+pub mod secret;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed = Vec<secret::Secret>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_input(input)
+    }
+
+    fn part1(secrets: &Self::Parsed) -> Result<i64> {
+        Ok(sum_secrets(&mut secrets.clone()))
+    }
+
+    fn part2(secrets: &Self::Parsed) -> Result<i64> {
+        Ok(most_bananas(&mut secrets.clone()))
+    }
+}
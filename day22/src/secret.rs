@@ -5,6 +5,8 @@ use std::{
 
 use std::fmt::Debug;
 
+use aoc_common::{Result, parse};
+
 use crate::fixed_queue::FixedQueue;
 
 type SecretNumber = i64;
@@ -53,12 +55,8 @@ impl Secret {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Secret> {
-    let mut result = Vec::with_capacity(1635);
-    for line in input.lines() {
-        result.push(Secret::new(line.parse().unwrap()));
-    }
-    result
+pub fn parse_input(input: &str) -> Result<Vec<Secret>> {
+    parse::lines(input, |line| Ok(Secret::new(parse::number(line.trim())?)))
 }
 
 pub fn sum_secrets(secrets: &mut [Secret]) -> SecretNumber {
@@ -106,6 +104,15 @@ mod tests {
     use super::*;
     use crate::*;
 
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_input("1\n\n10\n").unwrap().len(), 2);
+        assert_eq!(
+            parse_input("1\n1O").unwrap_err().to_string(),
+            "line 2: invalid number '1O'"
+        );
+    }
+
     #[test]
     fn test_examples() {
         assert_eq!(Secret::new(1).next_n(2000), 8685429);
@@ -113,13 +120,13 @@ mod tests {
 
     #[test]
     fn test_all_examples() {
-        let mut secrets = parse_input(TESTINPUT);
+        let mut secrets = parse_input(TESTINPUT).unwrap();
         assert_eq!(sum_secrets(&mut secrets), 37327623);
     }
 
     #[test]
     fn test_most_bananas() {
-        let mut secrets = parse_input(TESTINPUT2);
+        let mut secrets = parse_input(TESTINPUT2).unwrap();
         let most_bananas = most_bananas(&mut secrets);
         assert_eq!(most_bananas, 23);
     }
@@ -0,0 +1,67 @@
+// #![allow(dead_code, unused)]
+
+use aoc_common::{Result, Solution};
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[allow(dead_code)]
+const TESTINPUT: &str = "kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn";
+
+pub mod nodes;
+use nodes::{Cgr, Graph};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed = Graph;
+    type Part1 = usize;
+    type Part2 = Cgr;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Graph::parse(input)
+    }
+
+    fn part1(graph: &Self::Parsed) -> Result<usize> {
+        Ok(graph.count_triangles())
+    }
+
+    fn part2(graph: &Self::Parsed) -> Result<Cgr> {
+        Ok(graph.maximum_clique())
+    }
+}
@@ -1,162 +1,260 @@
-use std::collections::HashMap;
+//! An undirected graph read from lines of `<node>-<node>`, one link per line.
+//!
+//! A node name is any run of characters other than whitespace, `-` and `,`, and whitespace
+//! around the names is ignored, as are blank lines. Nodes are numbered in name order, so cliques
+//! found in index order are also in name order.
 
-use intersect_sorted_iterators::IntersectionIterator;
+use std::collections::{BTreeSet, HashMap};
 
-#[derive(Debug, PartialEq, PartialOrd, Clone)]
-struct Node {
-    name: [u8; 2],
-    index: usize,
-    t_adjacent: bool,
+use aoc_common::{ParseError, Result};
+use itertools::Itertools;
+use stephen_morris_utils::intersect_sorted_iterators::IntersectionIterator;
+
+fn node_name(word: &str) -> std::result::Result<&str, String> {
+    let name = word.trim();
+    if name.is_empty() {
+        return Err("missing node name".to_string());
+    }
+    match name.chars().find(|c| c.is_whitespace() || *c == ',') {
+        Some(c) => Err(format!("invalid character {c:?} in node name '{name}'")),
+        None => Ok(name),
+    }
 }
 
-const fn node_name2index(name: [u8; 2]) -> usize {
-    (name[0] - b'a') as usize * 26 + (name[1] - b'a') as usize
+fn parse_link(line: &str) -> std::result::Result<Option<(&str, &str)>, String> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    let Some((a, b)) = line.split_once('-') else {
+        return Err("expected '<node>-<node>'".to_string());
+    };
+    if b.contains('-') {
+        return Err("expected a single '-' between two nodes".to_string());
+    }
+    let (a, b) = (node_name(a)?, node_name(b)?);
+    if a == b {
+        return Err(format!("{a} is linked to itself"));
+    }
+    Ok(Some((a, b)))
+}
+
+fn intersect(a: &[usize], b: &[usize]) -> Vec<usize> {
+    IntersectionIterator::new(&mut a.iter(), &mut b.iter())
+        .copied()
+        .collect()
 }
 
 #[derive(Debug)]
 pub struct Graph {
-    nodes: Vec<Node>,
+    // the node names, sorted, so that a node's index is its position here
+    names: Vec<String>,
+    // the neighbours of each node with a higher index, sorted
     edges: Vec<Vec<usize>>,
+    // all the neighbours of each node, sorted
+    neighbours: Vec<Vec<usize>>,
 }
 
 impl Graph {
     pub fn new(input: &str) -> Self {
-        let mut nodes = Vec::with_capacity(26 * 26);
-        let mut edges = vec![Vec::new(); 26 * 26];
-        for letter1 in b'a'..=b'z' {
-            for letter2 in b'a'..=b'z' {
-                nodes.push(Node {
-                    name: [letter1, letter2],
-                    index: node_name2index([letter1, letter2]),
-                    t_adjacent: false,
-                })
+        Self::parse(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut links = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            let line_no = idx + 1;
+            if let Some(link) =
+                parse_link(line).map_err(|message| ParseError::at_line(line_no, message))?
+            {
+                links.push(link);
             }
         }
-        for line in input.lines() {
-            let mut bytes = line.bytes();
-            let node_a_name = [bytes.next().unwrap(), bytes.next().unwrap()];
-            let node_a_index = node_name2index(node_a_name);
-            bytes.next();
-            let node_b_name = [bytes.next().unwrap(), bytes.next().unwrap()];
-            let node_b_index = node_name2index(node_b_name);
+        let names: Vec<String> = links
+            .iter()
+            .flat_map(|&(a, b)| [a, b])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let indices: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.as_str(), index))
+            .collect();
+
+        let mut edges = vec![Vec::new(); names.len()];
+        let mut neighbours = vec![Vec::new(); names.len()];
+        for (node_a_name, node_b_name) in links {
+            let node_a_index = indices[node_a_name];
+            let node_b_index = indices[node_b_name];
             if node_a_index < node_b_index {
                 edges[node_a_index].push(node_b_index);
             } else {
                 edges[node_b_index].push(node_a_index)
             }
-            if node_a_name[0] == b't' || node_b_name[0] == b't' {
-                nodes[node_a_index].t_adjacent = true;
-                nodes[node_b_index].t_adjacent = true;
-            }
+            neighbours[node_a_index].push(node_b_index);
+            neighbours[node_b_index].push(node_a_index);
         }
-        for node_edges in edges.iter_mut() {
+        for node_edges in edges.iter_mut().chain(neighbours.iter_mut()) {
             node_edges.sort();
+            node_edges.dedup();
         }
 
-        let mut hm = HashMap::new();
-        for node_edges in edges.clone() {
-            let entry = hm.entry(node_edges.len()).or_insert(0);
-            *entry += 1;
-        }
-        let mut v: Vec<(usize, i32)> = hm.iter().map(|(k, v)| (*k, *v)).collect();
-        v.sort();
-        for (edges, count) in v {
-            println!("edges : {edges:5}    count : {count:5}");
-        }
+        Ok(Self {
+            names,
+            edges,
+            neighbours,
+        })
+    }
 
-        Self { nodes, edges }
+    pub fn len(&self) -> usize {
+        self.names.len()
     }
-    pub fn count_triangles(&self) -> usize {
-        // const T_RANGE: std::ops::Range<usize> =
-        //     node_name2index([b't', b'a'])..node_name2index([b's', b'a']);
-        let mut count = 0;
-        let mut triangles = Vec::new();
-
-        for node_a in &self.nodes {
-            if node_a.t_adjacent {
-                let node_a_edges = &self.edges[node_a.index];
-                for node_b_index_index in 0..node_a_edges.len() {
-                    let node_b_index = node_a_edges[node_b_index_index];
-                    let mut node_b_edges = self.edges[node_b_index].iter();
-                    let mut node_a_edges = node_a_edges[node_b_index_index + 1..].iter();
-                    let intersection =
-                        IntersectionIterator::new(&mut node_a_edges, &mut node_b_edges);
-                    for &node_c_index in intersection {
-                        if node_a.name[0] == b't'
-                            || self.nodes[node_b_index].name[0] == b't'
-                            || self.nodes[node_c_index].name[0] == b't'
-                        {
-                            triangles.push([
-                                node_a.name,
-                                self.nodes[node_b_index].name,
-                                self.nodes[node_c_index].name,
-                            ]);
-                            count += 1;
-                        }
-                    }
-                }
-            }
-        }
-        count
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
     }
 
-    fn max_complete_graph(&self, graph_nodes: &[usize], hm: &mut HashMap<Vec<usize>, Cgr>) -> Cgr {
-        if graph_nodes.is_empty() {
-            unreachable!();
-        }
-        if hm.contains_key(graph_nodes) {
-            hm.get(graph_nodes).unwrap().clone()
-        } else {
-            let mut result;
-            if graph_nodes.len() == 1 {
-                result = Cgr::new(graph_nodes[0]);
-            } else {
-                let rest = self.max_complete_graph(&graph_nodes[1..], hm);
-                let rest_len = rest.len();
-
-                let mut iter1 = self.edges[graph_nodes[0]].iter();
-                let mut iter2 = graph_nodes[1..].iter();
-                let v: Vec<usize> =
-                    intersect_sorted_iterators::IntersectionIterator::new(&mut iter1, &mut iter2)
-                        .copied()
-                        .collect();
-                if v.is_empty() {
-                    result = rest;
-                } else {
-                    let minus_first_node = self.max_complete_graph(v.as_ref(), hm);
-                    let minus_first_node_len = minus_first_node.len();
-                    if minus_first_node_len < rest_len {
-                        result = rest;
-                    } else {
-                        result = minus_first_node;
-                        result.push(graph_nodes[0]);
-                    }
-                }
-            }
-            hm.insert(graph_nodes.to_vec(), result.clone());
-            result
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    fn all_nodes(&self) -> Vec<usize> {
+        (0..self.len()).collect()
+    }
+
+    fn cgr(&self, nodes: &[usize]) -> Cgr {
+        Cgr {
+            nodes: nodes
+                .iter()
+                .sorted()
+                .map(|&node| self.names[node].clone())
+                .collect(),
         }
     }
 
-    const ALL_VALUES: [usize; 26 * 26] = {
-        let mut a: [usize; 26 * 26] = [0; 26 * 26];
-        let mut i = 0;
-        while i < 26 * 26 {
-            a[i] = i;
-            i += 1;
+    /// The number of cliques of `k` nodes which include a node whose name `filter` accepts.
+    pub fn count_cliques(&self, k: usize, filter: impl Fn(&str) -> bool) -> usize {
+        let accepted: Vec<bool> = self.names().map(filter).collect();
+        self.count_extensions(&self.all_nodes(), 0, k, &accepted, false)
+    }
+
+    // the cliques of k nodes made by adding candidates, in increasing order, to a clique of
+    // `size` nodes. every candidate is a neighbour of the whole clique with a higher index.
+    fn count_extensions(
+        &self,
+        candidates: &[usize],
+        size: usize,
+        k: usize,
+        accepted: &[bool],
+        any_accepted: bool,
+    ) -> usize {
+        if size == k {
+            return any_accepted as usize;
         }
-        a
-    };
+        candidates
+            .iter()
+            .enumerate()
+            .take_while(|&(idx, _)| candidates.len() - idx >= k - size)
+            .map(|(idx, &node)| {
+                let next = intersect(&candidates[idx + 1..], &self.edges[node]);
+                self.count_extensions(&next, size + 1, k, accepted, any_accepted || accepted[node])
+            })
+            .sum()
+    }
+
+    /// The triangles with a node whose name starts with t.
+    pub fn count_triangles(&self) -> usize {
+        self.count_cliques(3, |name| name.starts_with('t'))
+    }
+
+    /// Every clique which no other node could join, in name order.
+    pub fn maximal_cliques(&self) -> Vec<Cgr> {
+        self.cliques_of_at_least(1)
+    }
+
+    /// The maximal cliques of at least `k` nodes, in name order.
+    pub fn cliques_of_at_least(&self, k: usize) -> Vec<Cgr> {
+        let mut cliques = Vec::new();
+        self.bron_kerbosch(
+            &mut Vec::new(),
+            self.all_nodes(),
+            Vec::new(),
+            k,
+            &mut cliques,
+        );
+        cliques.sort_by(|a, b| a.nodes.cmp(&b.nodes));
+        cliques
+    }
+
+    /// The largest clique, or the first in name order of those as large.
+    pub fn maximum_clique(&self) -> Cgr {
+        self.maximal_cliques()
+            .into_iter()
+            .reduce(|largest, clique| match clique.len() > largest.len() {
+                true => clique,
+                false => largest,
+            })
+            .unwrap_or_default()
+    }
 
-    pub fn largest_complete_graph_size(&self) -> Cgr {
-        let mut hm = HashMap::new();
-        self.max_complete_graph(&Graph::ALL_VALUES[..], &mut hm)
+    // reports the maximal cliques which extend `clique` with some of the candidates and none of
+    // the excluded nodes, all of which are neighbours of the whole clique
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: Vec<usize>,
+        mut excluded: Vec<usize>,
+        k: usize,
+        cliques: &mut Vec<Cgr>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && clique.len() >= k {
+                cliques.push(self.cgr(clique));
+            }
+            return;
+        }
+        // every maximal clique holds the pivot or one of its non-neighbours, and the pivot with
+        // the most candidate neighbours leaves the fewest branches
+        let pivot = candidates
+            .iter()
+            .chain(&excluded)
+            .copied()
+            .max_by_key(|&node| {
+                IntersectionIterator::new(&mut candidates.iter(), &mut self.neighbours[node].iter())
+                    .count()
+            })
+            .unwrap();
+        let branches: Vec<usize> = candidates
+            .iter()
+            .copied()
+            .filter(|node| self.neighbours[pivot].binary_search(node).is_err())
+            .collect();
+        for node in branches {
+            if clique.len() + candidates.len() < k {
+                return;
+            }
+            let neighbours = &self.neighbours[node];
+            clique.push(node);
+            self.bron_kerbosch(
+                clique,
+                intersect(&candidates, neighbours),
+                intersect(&excluded, neighbours),
+                k,
+                cliques,
+            );
+            clique.pop();
+            candidates.remove(candidates.binary_search(&node).unwrap());
+            let position = excluded.binary_search(&node).unwrap_err();
+            excluded.insert(position, node);
+        }
     }
 }
 
-#[derive(Eq, Clone)]
+/// A complete graph, with its nodes in order.
+#[derive(Eq, Clone, Default)]
 pub struct Cgr {
-    nodes: Vec<usize>,
+    nodes: Vec<String>,
 }
 
 impl std::fmt::Debug for Cgr {
@@ -167,31 +265,28 @@ impl std::fmt::Debug for Cgr {
     }
 }
 
+impl std::fmt::Display for Cgr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
 // const CGR_EMPTY: Cgr = Cgr { nodes: Vec::new() };
 impl Cgr {
-    pub fn new(node: usize) -> Self {
+    pub fn new(node: &str) -> Self {
         Self {
             // size,
-            nodes: vec![node],
+            nodes: vec![node.to_string()],
         }
     }
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.nodes.len()
     }
-    fn as_string(&self) -> String {
-        let mut nodes = self.nodes.clone();
-        nodes.reverse();
-        nodes.iter().fold(String::new(), |mut acc, &index| {
-            if !acc.is_empty() {
-                acc.push(',');
-            }
-            acc.push(((index / 26) as u8 + b'a') as char);
-            acc.push(((index % 26) as u8 + b'a') as char);
-            acc
-        })
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
     }
-    fn push(&mut self, node_index: usize) {
-        self.nodes.push(node_index);
+    fn as_string(&self) -> String {
+        self.nodes.join(",")
     }
     // pub fn append(&mut self, other: &mut Self) {
     //     // self.size += other.size;
@@ -217,21 +312,21 @@ impl PartialOrd for Cgr {
     }
 }
 
-use stephen_morris_utils::intersect_sorted_iterators;
-
 #[cfg(test)]
 mod tests {
-    use crate::{INPUT, TESTINPUT};
+    use crate::TESTINPUT;
 
-    use super::{Cgr, Graph};
+    use super::{Cgr, Graph, ParseError};
+    use aoc_common::rng::Rng;
 
     #[test]
     fn test_new() {
         Graph::new(TESTINPUT);
     }
     #[test]
+    #[cfg(feature = "embedded-input")]
     fn test_new_real_input() {
-        Graph::new(INPUT);
+        Graph::new(crate::INPUT);
     }
     #[test]
     fn test_count_triangles() {
@@ -240,19 +335,187 @@ mod tests {
         assert_eq!(count, 7);
     }
 
+    // the cliques, by checking every subset of the nodes
+    fn brute_force_cliques(graph: &Graph) -> Vec<Vec<usize>> {
+        let nodes = graph.all_nodes();
+        let adjacent = |a: usize, b: usize| graph.neighbours[a].binary_search(&b).is_ok();
+        (1..1u32 << nodes.len())
+            .map(|mask| {
+                (0..nodes.len())
+                    .filter(|bit| mask >> bit & 1 == 1)
+                    .map(|bit| nodes[bit])
+                    .collect::<Vec<_>>()
+            })
+            .filter(|clique| {
+                clique
+                    .iter()
+                    .enumerate()
+                    .all(|(idx, &a)| clique[idx + 1..].iter().all(|&b| adjacent(a, b)))
+            })
+            .collect()
+    }
+
+    fn brute_force_maximal_cliques(graph: &Graph) -> Vec<Vec<usize>> {
+        let adjacent = |a: usize, b: usize| graph.neighbours[a].binary_search(&b).is_ok();
+        let mut cliques: Vec<Vec<usize>> = brute_force_cliques(graph)
+            .into_iter()
+            .filter(|clique| {
+                !graph.all_nodes().into_iter().any(|node| {
+                    !clique.contains(&node) && clique.iter().all(|&a| adjacent(a, node))
+                })
+            })
+            .collect();
+        cliques.sort();
+        cliques
+    }
+
+    fn random_input(seed: u64, no_nodes: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let name = |node: usize| format!("{}{node}", ["a", "sw", "Router_"][node % 3]);
+        let mut lines = Vec::new();
+        for a in 0..no_nodes {
+            for b in a + 1..no_nodes {
+                if rng.below(5) < 3 {
+                    lines.push(format!("{}\t- {}", name(b), name(a)));
+                }
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn nodes(graph: &Graph, cliques: &[Cgr]) -> Vec<Vec<usize>> {
+        cliques
+            .iter()
+            .map(|clique| {
+                clique
+                    .nodes
+                    .iter()
+                    .map(|name| graph.names.binary_search(name).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
     #[test]
     fn test_complete() {
         let graph = Graph::new(TESTINPUT);
-        let largest = graph.largest_complete_graph_size();
-        println!("{:?}", largest);
+        let largest = graph.maximum_clique();
         assert_eq!(largest.len(), 4);
+        assert_eq!(largest.to_string(), "co,de,ka,ta");
+        assert!(Graph::new("").maximum_clique().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-input")]
+    fn test_complete_real_input() {
+        let graph = Graph::new(crate::INPUT);
+        assert_eq!(
+            graph.maximum_clique().to_string(),
+            "as,bu,cp,dj,ez,fd,hu,it,kj,nx,pp,xh,yu"
+        );
+        assert_eq!(graph.cliques_of_at_least(13).len(), 1);
+    }
+
+    #[test]
+    fn test_maximal_cliques() {
+        let graph = Graph::new(TESTINPUT);
+        let cliques = graph.maximal_cliques();
+        assert_eq!(nodes(&graph, &cliques), brute_force_maximal_cliques(&graph));
+        assert_eq!(
+            graph
+                .cliques_of_at_least(4)
+                .iter()
+                .map(Cgr::to_string)
+                .collect::<Vec<_>>(),
+            ["co,de,ka,ta"]
+        );
+        for seed in 1..=20 {
+            let graph = Graph::new(&random_input(seed, 14));
+            let expected = brute_force_maximal_cliques(&graph);
+            assert_eq!(nodes(&graph, &graph.maximal_cliques()), expected, "{seed}");
+            for k in 1..=6 {
+                let at_least: Vec<Vec<usize>> = expected
+                    .iter()
+                    .filter(|clique| clique.len() >= k)
+                    .cloned()
+                    .collect();
+                assert_eq!(
+                    nodes(&graph, &graph.cliques_of_at_least(k)),
+                    at_least,
+                    "{seed} {k}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_cliques() {
+        let graph = Graph::new(TESTINPUT);
+        assert_eq!(graph.count_cliques(1, |_| true), 16);
+        assert_eq!(graph.count_cliques(2, |_| true), TESTINPUT.lines().count());
+        assert_eq!(graph.count_cliques(3, |_| true), 12);
+        assert_eq!(graph.count_cliques(4, |name| name == "ka"), 1);
+        assert_eq!(graph.count_cliques(5, |_| true), 0);
+        for seed in 1..=20 {
+            let graph = Graph::new(&random_input(seed, 14));
+            let cliques = brute_force_cliques(&graph);
+            let starts_with_a = |clique: &&Vec<usize>| {
+                clique
+                    .iter()
+                    .any(|&node| graph.names[node].starts_with('a'))
+            };
+            for k in 1..=6 {
+                let of_size = cliques.iter().filter(|clique| clique.len() == k);
+                assert_eq!(graph.count_cliques(k, |_| true), of_size.clone().count());
+                assert_eq!(
+                    graph.count_cliques(k, |name| name.starts_with('a')),
+                    of_size.filter(starts_with_a).count()
+                );
+            }
+        }
     }
 
     #[test]
     fn test_cgr() {
-        let mut cgr = Cgr::new(0);
-        cgr.push(1);
-        cgr.push(2);
-        println!("{cgr:?}, {}, {}", cgr.nodes.len(), cgr.as_string());
+        let graph = Graph::new("ac-ab\nab-aa");
+        let cgr = graph.cgr(&[2, 0, 1]);
+        assert_eq!(cgr.as_string(), "aa,ab,ac");
+        assert_eq!(Cgr::new("bb").to_string(), "bb");
+    }
+
+    #[test]
+    fn test_parse() {
+        let graph =
+            Graph::parse(" core-switch1\n\n\tswitch1 -\tlaptop \r\nswitch2-core\nlaptop-core")
+                .unwrap();
+        assert_eq!(
+            graph.names().collect::<Vec<_>>(),
+            ["core", "laptop", "switch1", "switch2"]
+        );
+        assert_eq!(graph.maximum_clique().to_string(), "core,laptop,switch1");
+        assert_eq!(graph.count_cliques(2, |name| name.starts_with("switch")), 3);
+
+        let error = |input: &str| Graph::parse(input).unwrap_err();
+        assert_eq!(
+            error("ab-cd\nab cd"),
+            ParseError::at_line(2, "expected '<node>-<node>'")
+        );
+        assert_eq!(
+            error("ab-cd-ef"),
+            ParseError::at_line(1, "expected a single '-' between two nodes")
+        );
+        assert_eq!(error("\nab- "), ParseError::at_line(2, "missing node name"));
+        assert_eq!(
+            error("a b-cd"),
+            ParseError::at_line(1, "invalid character ' ' in node name 'a b'")
+        );
+        assert_eq!(
+            error("ab-a,b").to_string(),
+            "line 1: invalid character ',' in node name 'a,b'"
+        );
+        assert_eq!(
+            error("ab-ab"),
+            ParseError::at_line(1, "ab is linked to itself")
+        );
     }
 }
@@ -0,0 +1,151 @@
+//! The findings of `MachineFixer::run_and_analyse`, by wire name and in name order, so that the
+//! analyses of two revisions of a circuit can be diffed as text or as JSON.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{errors::MachineError, machine::TestCaseOutput, wire::WireName};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationFailure {
+    pub wire: WireName,
+    pub error: MachineError,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Analysis {
+    pub cases: usize,
+    pub failures: Vec<TestCaseOutput>,
+    pub failing_z_bits: Vec<usize>,
+    pub validation_failures: Vec<ValidationFailure>,
+    // the gates which no correct z wire depends on
+    pub suspicious_gates: Vec<WireName>,
+    // the wires each z wire depends on, inputs included
+    pub cones: BTreeMap<WireName, Vec<WireName>>,
+}
+
+impl Analysis {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+fn names(wire_names: &[WireName]) -> String {
+    match wire_names {
+        [] => "none".to_string(),
+        _ => wire_names.iter().map(WireName::as_string).join(" "),
+    }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} of {} test cases failed",
+            self.failures.len(),
+            self.cases
+        )?;
+        for failure in &self.failures {
+            writeln!(f, "  {failure:?}")?;
+        }
+        let failing_z_bits = self
+            .failing_z_bits
+            .iter()
+            .map(|&bit| WireName::from_char_bit(b'z', bit as u8))
+            .collect::<Vec<_>>();
+        writeln!(f, "failing z wires: {}", names(&failing_z_bits))?;
+        writeln!(f, "validation failures: {}", self.validation_failures.len())?;
+        for ValidationFailure { wire, error } in &self.validation_failures {
+            writeln!(f, "  {}: {error}", wire.as_string())?;
+        }
+        writeln!(f, "suspicious gates: {}", names(&self.suspicious_gates))?;
+        writeln!(f, "cones:")?;
+        for (z_wire, cone) in &self.cones {
+            writeln!(f, "  {}: {}", z_wire.as_string(), names(cone))?;
+        }
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{adder_input, machine_fixer::MachineFixer};
+
+    // an adder with the sum and carry of bit 3 exchanged
+    fn swapped_adder() -> String {
+        adder_input(6, 0, 0)
+            .replace("XOR y03 -> s03", "XOR y03 -> tmp")
+            .replace("AND y03 -> a03", "AND y03 -> s03")
+            .replace("-> tmp", "-> a03")
+    }
+
+    #[test]
+    fn test_analysis() {
+        let analysis = MachineFixer::new(&swapped_adder()).run_and_analyse();
+        assert_eq!(analysis.failures.len(), 3);
+        assert_eq!(analysis.failing_z_bits, [3, 4, 5]);
+        assert!(analysis.validation_failures.is_empty());
+        let name = |name: &str| WireName::from_slice(name.as_bytes());
+        assert_eq!(
+            analysis.suspicious_gates,
+            [name("z03"), name("z04"), name("z05")]
+        );
+        assert_eq!(analysis.cones.len(), 7);
+        assert_eq!(analysis.cones[&name("z00")], [name("x00"), name("y00")]);
+
+        let text = analysis.to_string();
+        assert!(text.starts_with("3 of 13 test cases failed\n"));
+        assert!(text.contains("\nfailing z wires: z03 z04 z05\nvalidation failures: 0\n"));
+        assert!(text.contains("\n  z01: c00 s01 x00 x01 y00 y01\n"));
+
+        let analysis = MachineFixer::new(&adder_input(6, 0, 0)).run_and_analyse();
+        assert!(analysis.failures.is_empty() && analysis.suspicious_gates.is_empty());
+        assert!(analysis.to_string().contains("failing z wires: none\n"));
+    }
+
+    #[test]
+    fn test_validation_failures() {
+        let input = adder_input(6, 0, 0).replace("x03 AND y03 -> a03", "x03 AND y02 -> a03");
+        let analysis = MachineFixer::new(&input).run_and_analyse();
+        let failure = &analysis.validation_failures[0];
+        assert_eq!(failure.wire.as_string(), "a03");
+        assert!(
+            failure
+                .error
+                .to_string()
+                .starts_with("Logic Error: x_bits_used and y_bits_used are not equal")
+        );
+        assert!(analysis.to_string().contains("\n  a03: Logic Error: "));
+    }
+
+    #[test]
+    fn test_json() {
+        let input = swapped_adder().replace("x01 AND y01 -> a01", "x01 AND y00 -> a01");
+        let analysis = MachineFixer::new(&input).run_and_analyse();
+        let json = analysis.to_json();
+        assert_eq!(Analysis::from_json(&json).unwrap(), analysis);
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["suspicious_gates"][0],
+            analysis.suspicious_gates[0].as_string()
+        );
+        assert_eq!(value["failing_z_bits"][0], analysis.failing_z_bits[0]);
+        assert_eq!(
+            value["failures"][0]["z_wire_misses"],
+            analysis.failures[0].z_wire_misses
+        );
+        assert_eq!(value["cones"]["z00"], serde_json::json!(["x00", "y00"]));
+        assert_eq!(value["validation_failures"][0]["wire"], "a01");
+        assert!(value["validation_failures"][0]["error"]["LogicError"].is_string());
+
+        let renamed = json.replace("\"x00\"", "\"x000\"");
+        assert!(Analysis::from_json(&renamed).is_err());
+    }
+}
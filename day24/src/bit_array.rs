@@ -1,8 +1,9 @@
 use std::{
-    default,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
     ops::{
-        Add, AddAssign, BitAnd, BitAndAssign, BitOrAssign, BitXorAssign, Not, Shl, ShlAssign, Shr,
-        ShrAssign, Sub, SubAssign,
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl,
+        ShlAssign, Shr, ShrAssign, Sub, SubAssign,
     },
 };
 
@@ -125,53 +126,307 @@ impl<T: PrimInt + BitOrAssign + BitAndAssign> Sub for BitFlags<T> {
     }
 }
 
-// a set of bits indicating whether gate n is included in a set
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct LargeBitFlags(pub [u128; 2]);
+/// A fixed number of bits, such as one for each test case or one for each wire. Operations on
+/// sets of different lengths treat the missing bits as 0, and give a set as long as the longer.
+/// Likewise sets compare by their set bits alone, ordered as binary numbers.
+#[derive(Debug, Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
 
-impl Not for LargeBitFlags {
-    type Output = Self;
-    fn not(self) -> Self::Output {
-        Self([!self.0[0], !self.0[1]])
+const WORD_BITS: usize = u64::BITS as usize;
+
+impl BitSet {
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    // the word and mask of a bit, which must be in range
+    fn position(&self, index: usize) -> (usize, u64) {
+        assert!(
+            index < self.len,
+            "bit {index} is out of range for a set of {} bits",
+            self.len
+        );
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+    pub fn get(&self, index: usize) -> bool {
+        let (word, mask) = self.position(index);
+        self.words[word] & mask != 0
+    }
+    pub fn checked_get(&self, index: usize) -> Option<bool> {
+        (index < self.len).then(|| self.get(index))
+    }
+    pub fn set(&mut self, index: usize) {
+        let (word, mask) = self.position(index);
+        self.words[word] |= mask;
+    }
+    pub fn unset(&mut self, index: usize) {
+        let (word, mask) = self.position(index);
+        self.words[word] &= !mask;
+    }
+    pub fn set_value(&mut self, index: usize, value: bool) {
+        if value {
+            self.set(index);
+        } else {
+            self.unset(index);
+        }
+    }
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|&word| word != 0)
+    }
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(word_idx, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    word_idx * WORD_BITS + bit
+                })
+            })
+        })
+    }
+    // flips every bit in place
+    pub fn invert(&mut self) {
+        for word in self.words.iter_mut() {
+            *word = !*word;
+        }
+        self.clear_unused();
+    }
+    // the highest bit first, so that bit 0 is the last character
+    pub fn as_binary_string(&self) -> String {
+        (0..self.len)
+            .rev()
+            .map(|index| if self.get(index) { '1' } else { '0' })
+            .collect()
+    }
+    // the words up to the highest one with a bit set, which are equal for equal sets
+    fn significant_words(&self) -> &[u64] {
+        let len = self.words.iter().rposition(|&word| word != 0);
+        &self.words[..len.map_or(0, |idx| idx + 1)]
+    }
+    fn grow(&mut self, len: usize) {
+        if self.len < len {
+            self.words.resize(len.div_ceil(WORD_BITS), 0);
+            self.len = len;
+        }
+    }
+    // the bits beyond the length are kept at 0, so that equal sets have equal words
+    fn clear_unused(&mut self) {
+        if let Some(last) = self.words.last_mut()
+            && !self.len.is_multiple_of(WORD_BITS)
+        {
+            *last &= (1 << (self.len % WORD_BITS)) - 1;
+        }
     }
 }
 
-impl BitOrAssign for LargeBitFlags {
-    fn bitor_assign(&mut self, rhs: Self) {
-        self.0[0] |= rhs.0[0];
-        self.0[1] |= rhs.0[1];
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.significant_words() == other.significant_words()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl Hash for BitSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.significant_words().hash(state);
     }
 }
 
-impl LargeBitFlags {
-    pub fn set(&mut self, n: usize) {
-        if n < 128 {
-            self.0[1] |= 1 << n;
-        } else if n < 256 {
-            self.0[0] |= 1 << (n % 128);
+impl PartialOrd for BitSet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BitSet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (words, other_words) = (self.significant_words(), other.significant_words());
+        words
+            .len()
+            .cmp(&other_words.len())
+            .then_with(|| words.iter().rev().cmp(other_words.iter().rev()))
+    }
+}
+
+impl BitAndAssign<&BitSet> for BitSet {
+    fn bitand_assign(&mut self, rhs: &BitSet) {
+        self.grow(rhs.len);
+        for (idx, word) in self.words.iter_mut().enumerate() {
+            *word &= rhs.words.get(idx).copied().unwrap_or(0);
         }
     }
-    pub fn unset(&mut self, n: usize) {
-        if n < 128 {
-            self.0[1] &= !(1 << n);
-        } else {
-            self.0[0] &= !(1 << (n % 128));
+}
+impl BitOrAssign<&BitSet> for BitSet {
+    fn bitor_assign(&mut self, rhs: &BitSet) {
+        self.grow(rhs.len);
+        for (word, rhs_word) in self.words.iter_mut().zip(&rhs.words) {
+            *word |= rhs_word;
         }
     }
-    pub fn get(&self, n: usize) -> bool {
-        if n < 128 {
-            self.0[1] & (1 << n) != 0
-        } else {
-            self.0[0] & (1 << (n % 128)) != 0
+}
+impl BitXorAssign<&BitSet> for BitSet {
+    fn bitxor_assign(&mut self, rhs: &BitSet) {
+        self.grow(rhs.len);
+        for (word, rhs_word) in self.words.iter_mut().zip(&rhs.words) {
+            *word ^= rhs_word;
         }
     }
-    pub fn merge(&self, other: &Self) -> LargeBitFlags {
-        LargeBitFlags([self.0[0] | other.0[0], self.0[1] | other.0[1]])
+}
+
+// the owned and borrowed forms of each operator, from the assigning form
+macro_rules! bit_set_operator {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl $assign_trait for BitSet {
+            fn $assign_method(&mut self, rhs: BitSet) {
+                $assign_trait::$assign_method(self, &rhs);
+            }
+        }
+        impl $trait<&BitSet> for BitSet {
+            type Output = BitSet;
+
+            fn $method(mut self, rhs: &BitSet) -> BitSet {
+                $assign_trait::$assign_method(&mut self, rhs);
+                self
+            }
+        }
+        impl $trait for BitSet {
+            type Output = BitSet;
+
+            fn $method(self, rhs: BitSet) -> BitSet {
+                $trait::$method(self, &rhs)
+            }
+        }
+        impl $trait<&BitSet> for &BitSet {
+            type Output = BitSet;
+
+            fn $method(self, rhs: &BitSet) -> BitSet {
+                $trait::$method(self.clone(), rhs)
+            }
+        }
+    };
+}
+
+bit_set_operator!(BitAnd, bitand, BitAndAssign, bitand_assign);
+bit_set_operator!(BitOr, bitor, BitOrAssign, bitor_assign);
+bit_set_operator!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl Not for BitSet {
+    type Output = BitSet;
+
+    fn not(mut self) -> BitSet {
+        self.invert();
+        self
     }
-    pub fn as_binary_string(&self) -> String {
-        format!("{:0128b}{:0128b}", self.0[0], self.0[1])
+}
+impl Not for &BitSet {
+    type Output = BitSet;
+
+    fn not(self) -> BitSet {
+        !self.clone()
     }
-    pub(crate) fn is_empty(&self) -> bool {
-        self.0[0] == 0 && self.0[1] == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_set() {
+        let mut flags = BitSet::new(1001);
+        assert!(!flags.any());
+        for n in [0, 63, 64, 127, 128, 255, 256, 1000] {
+            flags.set(n);
+            assert!(flags.get(n));
+        }
+        assert!(!flags.get(1) && !flags.get(999));
+        assert_eq!(flags.checked_get(1000), Some(true));
+        assert_eq!(flags.checked_get(1001), None);
+        assert_eq!(flags.count_ones(), 8);
+        assert_eq!(
+            flags.iter_ones().collect::<Vec<_>>(),
+            [0, 63, 64, 127, 128, 255, 256, 1000]
+        );
+        assert_eq!(flags.as_binary_string().len(), 1001);
+        assert!(flags.as_binary_string().starts_with("10"));
+        assert!(flags.as_binary_string().ends_with("01"));
+
+        flags.unset(1000);
+        assert!(!flags.get(1000));
+        flags.set_value(1, true);
+        assert_eq!(flags.count_ones(), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit 130 is out of range for a set of 130 bits")]
+    fn test_bit_set_range() {
+        BitSet::new(130).set(130);
+    }
+
+    #[test]
+    fn test_bit_set_operators() {
+        let from = |len: usize, ones: &[usize]| {
+            let mut flags = BitSet::new(len);
+            ones.iter().for_each(|&n| flags.set(n));
+            flags
+        };
+        let a = from(130, &[0, 64, 129]);
+        let b = from(130, &[0, 65, 128]);
+        assert_eq!(&a & &b, from(130, &[0]));
+        assert_eq!(&a | &b, from(130, &[0, 64, 65, 128, 129]));
+        assert_eq!(a.clone() ^ b.clone(), from(130, &[64, 65, 128, 129]));
+        let not_a = !&a;
+        assert_eq!(not_a.count_ones(), 127);
+        assert_eq!(!not_a, a);
+
+        // the shorter set is padded with 0s
+        let short = from(3, &[0, 2]);
+        assert_eq!(&a | &short, from(130, &[0, 2, 64, 129]));
+        assert_eq!(&short & &a, from(130, &[0]));
+        let mut merged = BitSet::default();
+        merged |= &a;
+        assert_eq!(merged, a);
+    }
+
+    #[test]
+    fn test_bit_set_compare() {
+        let from = |len: usize, ones: &[usize]| {
+            let mut flags = BitSet::new(len);
+            ones.iter().for_each(|&n| flags.set(n));
+            flags
+        };
+        // the same bits in sets of different lengths
+        assert_eq!(from(3, &[0, 2]), from(200, &[0, 2]));
+        assert_eq!(BitSet::new(0), BitSet::new(130));
+        let hash = |flags: &BitSet| {
+            let mut hasher = std::hash::DefaultHasher::new();
+            flags.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&from(3, &[1])), hash(&from(130, &[1])));
+
+        // ordered as binary numbers, whatever the lengths
+        assert!(from(200, &[1]) < from(3, &[2]));
+        assert!(from(3, &[0, 1]) < from(130, &[64]));
+        assert!(from(130, &[0, 64]) > from(130, &[1, 63]));
+        assert_eq!(from(130, &[5]).cmp(&from(6, &[5])), Ordering::Equal);
     }
 }
@@ -1,9 +1,18 @@
 use std::{error::Error, fmt::Display};
 
-#[derive(Debug)]
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::wire::WireName;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MachineError {
     LogicError(String),
     WireError(String),
+    ParseError { line_no: usize, message: String },
+    // the wires around a loop, each driving the next and the last driving the first
+    CycleError(Vec<WireName>),
+    RepairError { max_swaps: usize },
 }
 
 impl Display for MachineError {
@@ -11,10 +20,36 @@ impl Display for MachineError {
         match self {
             MachineError::LogicError(msg) => write!(f, "Logic Error: {}", msg),
             MachineError::WireError(msg) => write!(f, "Wire Error: {}", msg),
+            MachineError::ParseError { line_no, message } => {
+                write!(f, "Parse Error: line {}: {}", line_no, message)
+            }
+            MachineError::CycleError(wire_names) => write!(
+                f,
+                "Cycle Error: {}",
+                wire_names
+                    .iter()
+                    .chain(wire_names.first())
+                    .map(WireName::as_string)
+                    .join(" -> ")
+            ),
+            MachineError::RepairError { max_swaps } => write!(
+                f,
+                "Repair Error: no {} swaps of outputs repair the adder",
+                max_swaps
+            ),
         }
     }
 }
 
 impl Error for MachineError {}
 
+impl From<MachineError> for aoc_common::ParseError {
+    fn from(error: MachineError) -> Self {
+        match error {
+            MachineError::ParseError { line_no, message } => Self::at_line(line_no, message),
+            error => Self::new(error.to_string()),
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, MachineError>;
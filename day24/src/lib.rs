@@ -0,0 +1,138 @@
+use aoc_common::{Result, Solution};
+use errors::MachineError;
+use machine::Machine;
+use machine_fixer::MachineFixer;
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[cfg(test)]
+const TESTINPUT: &str = "x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02";
+#[cfg(test)]
+const TESTINPUT2: &str = "x00: 1
+x01: 0
+x02: 1
+x03: 1
+x04: 0
+y00: 1
+y01: 1
+y02: 1
+y03: 1
+y04: 1
+
+ntg XOR fgs -> mjb
+y02 OR x01 -> tnw
+kwq OR kpj -> z05
+x00 OR x03 -> fst
+tgd XOR rvg -> z01
+vdt OR tnw -> bfw
+bfw AND frj -> z10
+ffh OR nrd -> bqk
+y00 AND y03 -> djm
+y03 OR y00 -> psh
+bqk OR frj -> z08
+tnw OR fst -> frj
+gnj AND tgd -> z11
+bfw XOR mjb -> z00
+x03 OR x00 -> vdt
+gnj AND wpb -> z02
+x04 AND y00 -> kjc
+djm OR pbm -> qhw
+nrd AND vdt -> hwm
+kjc AND fst -> rvg
+y04 OR y02 -> fgs
+y01 AND x02 -> pbm
+ntg OR kjc -> kwq
+psh XOR fgs -> tgd
+qhw XOR tgd -> z09
+pbm OR djm -> kpj
+x03 XOR y03 -> ffh
+x00 XOR y04 -> ntg
+bfw OR bqk -> z06
+nrd XOR fgs -> wpb
+frj XOR qhw -> z04
+bqk OR frj -> z07
+y03 OR x01 -> nrd
+hwm AND bqk -> z03
+tgd XOR rvg -> z12
+tnw OR pbm -> gnj";
+
+// a ripple-carry adder over `bits` bits, with the inputs set to x and y
+#[cfg(test)]
+fn adder_input(bits: usize, x: usize, y: usize) -> String {
+    let mut lines = Vec::new();
+    for (letter, value) in [('x', x), ('y', y)] {
+        for bit in 0..bits {
+            lines.push(format!("{letter}{bit:02}: {}", value >> bit & 1));
+        }
+    }
+    lines.push(String::new());
+    let carry = |bit: usize| match bit + 1 == bits {
+        true => format!("z{bits:02}"),
+        false => format!("c{bit:02}"),
+    };
+    lines.push("x00 XOR y00 -> z00".to_string());
+    lines.push(format!("x00 AND y00 -> {}", carry(0)));
+    for bit in 1..bits {
+        let previous = bit - 1;
+        lines.push(format!("x{bit:02} XOR y{bit:02} -> s{bit:02}"));
+        lines.push(format!("x{bit:02} AND y{bit:02} -> a{bit:02}"));
+        lines.push(format!("s{bit:02} XOR c{previous:02} -> z{bit:02}"));
+        lines.push(format!("s{bit:02} AND c{previous:02} -> b{bit:02}"));
+        lines.push(format!("a{bit:02} OR b{bit:02} -> {}", carry(bit)));
+    }
+    lines.join("\n")
+}
+
+pub mod analysis;
+pub mod bit_array;
+pub mod errors;
+pub mod machine;
+pub mod machine_fixer;
+pub mod netlist;
+pub mod parser;
+pub mod suite;
+pub mod wire;
+pub mod wire_analytics;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Parsed = (Machine<1>, MachineFixer);
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let circuit = parser::parse(input)?;
+        Ok((
+            Machine::with_input_values(&circuit)?,
+            MachineFixer::from_circuit(&circuit)?,
+        ))
+    }
+
+    fn part1((machine, _): &Self::Parsed) -> Result<usize> {
+        let mut machine = machine.clone();
+        Ok(machine.eval_output()[0].actual)
+    }
+
+    fn part2((_, machine_fixer): &Self::Parsed) -> Result<String> {
+        let max_swaps = 4;
+        let repair = machine_fixer
+            .repair(max_swaps)
+            .ok_or(MachineError::RepairError { max_swaps })?;
+        Ok(repair.answer())
+    }
+}
@@ -1,53 +1,170 @@
-#![allow(dead_code)]
 use std::fmt::Debug;
 
+use aoc_common::span;
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    bit_array::BitFlags,
+    bit_array::{BitFlags, BitSet},
     errors::{MachineError, Result},
+    parser::{self, Circuit, numbered_wire},
     wire::{self, WireName, WireValue},
     wire_analytics::{InputWireType, WireAnalytics, WireType},
 };
 
-pub const INPUT_BITS: usize = 45;
-pub const OUTPUT_BITS: usize = INPUT_BITS + 1;
-pub const NO_GATES: usize = 313 - 91;
-pub const X_OFFSET: usize = NO_GATES;
-pub const Y_OFFSET: usize = NO_GATES + INPUT_BITS;
-pub const Z_OFFSET: usize = NO_GATES + INPUT_BITS * 2;
-pub const NO_WIRES: usize = NO_GATES + INPUT_BITS * 2 + OUTPUT_BITS;
+// the input and output values are held in a usize, which limits the width of the adder
+pub const MAX_INPUT_BITS: usize = 63;
+pub const MAX_OUTPUT_BITS: usize = MAX_INPUT_BITS + 1;
 
 #[derive(Debug, PartialEq, Copy, Clone, Eq, PartialOrd, Ord)]
 pub enum Operation {
     And,
     Or,
     Xor,
+    Nand,
+    Nor,
+    Xnor,
+    Not,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 7] = [
+        Operation::And,
+        Operation::Or,
+        Operation::Xor,
+        Operation::Nand,
+        Operation::Nor,
+        Operation::Xnor,
+        Operation::Not,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::And => "AND",
+            Operation::Or => "OR",
+            Operation::Xor => "XOR",
+            Operation::Nand => "NAND",
+            Operation::Nor => "NOR",
+            Operation::Xnor => "XNOR",
+            Operation::Not => "NOT",
+        }
+    }
+
+    // combines the inputs for every case at once. NOT is a NAND of its single input.
+    pub fn apply<'a>(&self, inputs: impl IntoIterator<Item = &'a BitSet>) -> BitSet {
+        let mut output = BitSet::default();
+        self.apply_into(&mut output, inputs);
+        output
+    }
+
+    // as apply, reusing the output's storage
+    pub fn apply_into<'a>(
+        &self,
+        output: &mut BitSet,
+        inputs: impl IntoIterator<Item = &'a BitSet>,
+    ) {
+        let mut inputs = inputs.into_iter();
+        output.clone_from(inputs.next().expect("a gate has at least one input"));
+        for input in inputs {
+            match self {
+                Operation::And | Operation::Nand | Operation::Not => *output &= input,
+                Operation::Or | Operation::Nor => *output |= input,
+                Operation::Xor | Operation::Xnor => *output ^= input,
+            }
+        }
+        if matches!(
+            self,
+            Operation::Nand | Operation::Nor | Operation::Xnor | Operation::Not
+        ) {
+            output.invert();
+        }
+    }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct WireNameValue<const N: usize> {
     wire_name: WireName,
     wire_value: WireValue<WireName, N>,
 }
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Machine<const NO_CASES: usize> {
     cases: [InputPair; NO_CASES],
-    gates: [WireNameValue<NO_CASES>; NO_GATES],
+    gates: Vec<WireNameValue<NO_CASES>>,
+    layout: Layout,
     highest_z_bit: u8,
-    pub wires: [wire::Wire<NO_CASES>; NO_WIRES],
+    pub wires: Vec<wire::Wire<NO_CASES>>,
+    // the gate wires, each after the gates driving its inputs
+    order: Vec<usize>,
+}
+
+/// The sizes of a circuit, read from its input. The wire table holds the gates in name order,
+/// then the x inputs, the y inputs and the z outputs, each indexed by bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub no_gates: usize,
+    pub input_bits: usize,
+    pub output_bits: usize,
+}
+
+impl Layout {
+    pub fn new(circuit: &Circuit) -> Self {
+        let wire_names = circuit
+            .values
+            .iter()
+            .map(|(wire_name, _)| wire_name)
+            .chain(circuit.gates.iter().flat_map(|gate| &gate.inputs))
+            .chain(circuit.gates.iter().map(|gate| &gate.output));
+        let mut input_bits = 0;
+        let mut output_bits = 0;
+        for wire_name in wire_names {
+            match numbered_wire(&wire_name.0) {
+                Some((b'z', bit)) => output_bits = output_bits.max(bit + 1),
+                Some((_, bit)) => input_bits = input_bits.max(bit + 1),
+                None => {}
+            }
+        }
+        Self {
+            no_gates: circuit.gates.len(),
+            input_bits,
+            output_bits: output_bits.max(input_bits + 1),
+        }
+    }
+
+    pub fn from_input(input: &str) -> Result<Self> {
+        parser::parse(input).map(|circuit| Self::new(&circuit))
+    }
+
+    pub fn x_offset(&self) -> usize {
+        self.no_gates
+    }
+    pub fn y_offset(&self) -> usize {
+        self.no_gates + self.input_bits
+    }
+    pub fn z_offset(&self) -> usize {
+        self.no_gates + self.input_bits * 2
+    }
+    pub fn no_wires(&self) -> usize {
+        self.z_offset() + self.output_bits
+    }
 }
 
 impl Machine<1> {
     pub fn new(input: &str) -> Self {
-        let (input_values, input_gates) = input.split_once("\n\n").unwrap();
+        Self::try_new(input).unwrap_or_else(|error| panic!("{error}"))
+    }
 
+    /// A machine with the single case given by the input's wire values.
+    pub fn try_new(input: &str) -> Result<Self> {
+        Self::with_input_values(&parser::parse(input)?)
+    }
+
+    // a machine with the single case given by the circuit's input values
+    pub fn with_input_values(circuit: &Circuit) -> Result<Self> {
         let mut given_x = 0;
         let mut given_y = 0;
-        for line in input_values.lines() {
-            let bytes = line.as_bytes();
-            let bit_index = WireName::from_slice(&bytes[0..3]).bit_index().unwrap();
-            let bit_value = bytes[5] == b'1';
+        for &(wire_name, bit_value) in &circuit.values {
+            let bit_index = wire_name.bit_index().unwrap();
             if bit_value {
-                if bytes[0] == b'x' {
+                if wire_name[0] == b'x' {
                     given_x |= 1 << bit_index
                 } else {
                     given_y |= 1 << bit_index
@@ -60,17 +177,17 @@ impl Machine<1> {
             y: given_y,
         }];
 
-        Self::new_with_cases(input, cases)
+        Self::from_circuit(circuit, cases)
     }
     pub fn calc(input: &str, x: usize, y: usize) -> usize {
         let mut logic = Machine::new_with_cases(input, [InputPair { x, y }]);
-        // eval the z gates, and collect as an integer using the bit value to detemine the binary columns
+        logic.evaluate();
+        // collect the z gates as an integer using the bit value to detemine the binary columns
         let mut z = BitFlags::new();
         for bit_index in 0..=logic.highest_z_bit {
             let wire_name = WireName::from_char_bit(b'z', bit_index);
-            let wire_idx = Self::get_gate_index(&logic.gates, wire_name);
-            let (z_nth_bit, _) = logic.eval(wire_idx, bit_index);
-            if z_nth_bit.get(bit_index as usize) {
+            let wire_idx = logic.get_gate_index(wire_name);
+            if logic.value(wire_idx).get(0) {
                 z.set(bit_index as usize);
             }
         }
@@ -86,131 +203,253 @@ pub struct InputPair {
 
 impl<const NO_CASES: usize> Machine<NO_CASES> {
     pub fn new_with_cases(input: &str, cases: [InputPair; NO_CASES]) -> Self {
-        let (_, input_gates) = input.split_once("\n\n").unwrap();
-
-        let mut gates = [WireNameValue::default(); NO_GATES];
-
-        let mut highest_z_bit = 0;
-        for (idx, line) in input_gates.lines().enumerate() {
-            let mut bytes = line.as_bytes();
-            let input1 = WireName::from_slice(&bytes[0..3]);
-            let operation = match &bytes[4..7] {
-                b"AND" => Operation::And,
-                b"XOR" => Operation::Xor,
-                b"OR " => Operation::Or,
-                _ => unreachable!(),
-            };
+        Self::try_new_with_cases(input, cases).unwrap_or_else(|error| panic!("{error}"))
+    }
 
-            if operation == Operation::Or {
-                bytes = &bytes[7..];
-            } else {
-                bytes = &bytes[8..];
-            }
-            let input2 = WireName::from_slice(&bytes[0..3]);
-            let output = WireName::from_slice(&bytes[7..]);
-            if output[0] == b'z' {
-                highest_z_bit = highest_z_bit.max(output.bit_index().unwrap());
-            }
+    pub fn try_new_with_cases(input: &str, cases: [InputPair; NO_CASES]) -> Result<Self> {
+        Self::from_circuit(&parser::parse(input)?, cases)
+    }
 
-            gates[idx] = WireNameValue {
-                wire_name: output,
+    /// Fails with the wires of a loop if any gate depends on its own output.
+    pub fn from_circuit(circuit: &Circuit, cases: [InputPair; NO_CASES]) -> Result<Self> {
+        let _span = span::enter("Machine::from_circuit");
+        let layout = Layout::new(circuit);
+        let mut gates: Vec<_> = circuit
+            .gates
+            .iter()
+            .map(|gate| WireNameValue {
+                wire_name: gate.output,
                 wire_value: WireValue::Gate {
-                    input1,
-                    input2,
-                    operation,
+                    inputs: gate.inputs.clone(),
+                    operation: gate.operation,
                 },
-            };
-        }
-        gates.sort();
+            })
+            .collect();
+        let highest_z_bit = circuit
+            .gates
+            .iter()
+            .filter_map(|gate| numbered_wire(&gate.output.0))
+            .filter(|&(letter, _)| letter == b'z')
+            .map(|(_, bit)| bit as u8)
+            .max()
+            .unwrap_or(0);
 
-        let mut wires = [wire::Wire::default(); NO_WIRES];
-        for &WireNameValue {
+        let sort_span = span::enter("sort gates");
+        gates.sort();
+        drop(sort_span);
+
+        let wire_span = span::enter("wire gates");
+        // the z gates are counted in no_gates but placed with the outputs, leaving unused slots,
+        // which hold 0 for every case like any output bit no gate drives
+        let unused = wire::Wire {
+            value_start: WireValue::Value(BitSet::new(NO_CASES)),
+            wire_analytics: WireAnalytics::new_gate_wire(WireName::default(), layout.no_wires()),
+            ..Default::default()
+        };
+        let mut wires = vec![unused; layout.no_wires()];
+        for WireNameValue {
             wire_name,
             wire_value,
         } in &gates
         {
-            let wire_index = Self::get_gate_index(&gates, wire_name);
+            let wire_index = Self::find_gate_index(&gates, layout, *wire_name);
             let value_start = match wire_value {
-                WireValue::Value(b) => continue, // for the dummy entries that pad the array
-                WireValue::Gate {
-                    input1,
-                    input2,
-                    operation,
-                } => WireValue::Gate {
-                    input1: Self::get_gate_index(&gates, input1),
-                    input2: Self::get_gate_index(&gates, input2),
-                    operation,
+                WireValue::Value(_) => unreachable!(),
+                WireValue::Gate { inputs, operation } => WireValue::Gate {
+                    inputs: inputs
+                        .iter()
+                        .map(|&input| Self::find_gate_index(&gates, layout, input))
+                        .collect(),
+                    operation: *operation,
                 },
             };
             wires[wire_index] = wire::Wire {
-                wire_name,
+                wire_name: *wire_name,
                 wire_index,
                 value_start,
-                value_calc: value_start,
-                wire_analytics: WireAnalytics::new_gate_wire(wire_name),
+                value_calc: BitSet::default(),
+                wire_analytics: WireAnalytics::new_gate_wire(*wire_name, layout.no_wires()),
             };
         }
+        drop(wire_span);
 
-        for bit in 0..INPUT_BITS {
+        let _inputs_span = span::enter("wire inputs and outputs");
+        for bit in 0..layout.input_bits {
             let wire_name = WireName::from_char_bit(b'x', bit as u8);
-            let mut bit_values_by_cases = BitFlags::new();
+            let mut bit_values_by_cases = BitSet::new(NO_CASES);
             // For this bit, we store the value for each case in a single bit array.
             for (case_idx, case) in cases.iter().enumerate() {
                 bit_values_by_cases.set_value(case_idx, (case.x & (1 << bit)) != 0);
             }
-            wires[X_OFFSET + bit] = wire::Wire {
+            wires[layout.x_offset() + bit] = wire::Wire {
                 wire_name,
-                wire_index: X_OFFSET + bit,
+                wire_index: layout.x_offset() + bit,
                 value_start: WireValue::Value(bit_values_by_cases),
-                value_calc: WireValue::Value(bit_values_by_cases),
+                value_calc: BitSet::default(),
                 wire_analytics: WireAnalytics::new_input_wire(
                     WireType::Input(InputWireType::X),
                     bit,
+                    layout.no_wires(),
                 ),
             };
             let wire_name = WireName::from_char_bit(b'y', bit as u8);
-            let mut bit_by_cases_array = BitFlags::new();
-            // we want a u64 array of 0s and 1s for each bit up to INPUT_BITS.
+            let mut bit_by_cases_array = BitSet::new(NO_CASES);
+            // we want a u64 array of 0s and 1s for each input bit.
             // the bit index in the u64 is the case number
             for (case_idx, case) in cases.iter().enumerate() {
                 bit_by_cases_array.set_value(case_idx, (case.y & (1 << bit)) != 0);
             }
-            wires[Y_OFFSET + bit] = wire::Wire {
+            wires[layout.y_offset() + bit] = wire::Wire {
                 wire_name,
-                wire_index: Y_OFFSET + bit,
+                wire_index: layout.y_offset() + bit,
                 value_start: WireValue::Value(bit_by_cases_array),
-                value_calc: WireValue::Value(bit_by_cases_array),
+                value_calc: BitSet::default(),
                 wire_analytics: WireAnalytics::new_input_wire(
                     WireType::Input(InputWireType::Y),
                     bit,
+                    layout.no_wires(),
                 ),
             };
         }
 
-        for bit in highest_z_bit as usize + 1..OUTPUT_BITS {
+        for bit in 0..layout.output_bits {
             let wire_name = WireName::from_char_bit(b'z', bit as u8);
-            wires[Self::get_gate_index(&gates, wire_name)] = wire::Wire {
+            let wire_index = Self::find_gate_index(&gates, layout, wire_name);
+            if wires[wire_index].wire_name == wire_name {
+                continue;
+            }
+            wires[wire_index] = wire::Wire {
                 wire_name,
-                ..Default::default()
+                wire_index,
+                value_start: WireValue::Value(BitSet::new(NO_CASES)),
+                value_calc: BitSet::default(),
+                wire_analytics: WireAnalytics::new_gate_wire(wire_name, layout.no_wires()),
             };
         }
 
-        // engine.sort();
-
-        Self {
+        let mut machine = Self {
             cases,
             gates,
+            layout,
             highest_z_bit,
             wires,
+            order: Vec::new(),
+        };
+        machine.update_order()?;
+        Ok(machine)
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub fn cases(&self) -> &[InputPair; NO_CASES] {
+        &self.cases
+    }
+
+    // replaces the cases, and the input values which hold them
+    pub fn set_cases(&mut self, cases: [InputPair; NO_CASES]) {
+        let layout = self.layout;
+        for bit in 0..layout.input_bits {
+            let mut x_lanes = BitSet::new(NO_CASES);
+            let mut y_lanes = BitSet::new(NO_CASES);
+            for (case_idx, case) in cases.iter().enumerate() {
+                x_lanes.set_value(case_idx, (case.x & (1 << bit)) != 0);
+                y_lanes.set_value(case_idx, (case.y & (1 << bit)) != 0);
+            }
+            self.wires[layout.x_offset() + bit].value_start = WireValue::Value(x_lanes);
+            self.wires[layout.y_offset() + bit].value_start = WireValue::Value(y_lanes);
+        }
+        self.cases = cases;
+    }
+
+    // the indexes of the wires driven by a gate
+    pub fn gate_wires(&self) -> impl Iterator<Item = usize> + '_ {
+        self.wires
+            .iter()
+            .enumerate()
+            .filter(|(_, wire)| matches!(wire.value_start, WireValue::Gate { .. }))
+            .map(|(idx, _)| idx)
+    }
+
+    // exchange the gates driving two wires, unless that would make a loop
+    pub fn swap_outputs(&mut self, wire_idx1: usize, wire_idx2: usize) -> Result<()> {
+        self.exchange_outputs(wire_idx1, wire_idx2);
+        if let Err(error) = self.update_order() {
+            // the order and analytics are only replaced on success, so they still fit
+            self.exchange_outputs(wire_idx1, wire_idx2);
+            return Err(error);
         }
+        Ok(())
+    }
+
+    fn exchange_outputs(&mut self, wire_idx1: usize, wire_idx2: usize) {
+        let value_start1 = std::mem::take(&mut self.wires[wire_idx1].value_start);
+        self.wires[wire_idx1].value_start =
+            std::mem::replace(&mut self.wires[wire_idx2].value_start, value_start1);
+    }
+
+    // sorts the gates and works out the analytics, which depend only on the wiring
+    fn update_order(&mut self) -> Result<()> {
+        let _span = span::enter("Machine::update_order");
+        self.order = evaluation_order(&self.wires)?;
+        for &wire_idx in &self.order {
+            let WireValue::Gate { inputs, .. } = &self.wires[wire_idx].value_start else {
+                unreachable!("the order only holds gate wires")
+            };
+            let analytics: Vec<_> = inputs
+                .iter()
+                .map(|&input_idx| self.wires[input_idx].wire_analytics.clone())
+                .collect();
+            let mut wire_analytics = WireAnalytics::merge(&analytics);
+            for &input_idx in inputs {
+                wire_analytics.gate_array.set(input_idx);
+            }
+            wire_analytics.wire_type = self.wires[wire_idx].wire_analytics.wire_type;
+            self.wires[wire_idx].wire_analytics = wire_analytics;
+        }
+
+        // an input's lowest output bit is the first z wire that depends on it, and a gate's is
+        // the highest of its inputs'
+        let layout = self.layout;
+        for wire_idx in layout.x_offset()..layout.z_offset() {
+            self.wires[wire_idx].wire_analytics.lowest_output_bit = (0..=self.highest_z_bit)
+                .find(|&bit| {
+                    self.wires[layout.z_offset() + bit as usize]
+                        .wire_analytics
+                        .gate_array
+                        .get(wire_idx)
+                })
+                .unwrap_or(u8::MAX);
+        }
+        for &wire_idx in &self.order {
+            let WireValue::Gate { inputs, .. } = &self.wires[wire_idx].value_start else {
+                unreachable!("the order only holds gate wires")
+            };
+            let lowest_output_bit = inputs
+                .iter()
+                .map(|&input_idx| self.wires[input_idx].wire_analytics.lowest_output_bit)
+                .max()
+                .unwrap_or_default();
+            self.wires[wire_idx].wire_analytics.lowest_output_bit = lowest_output_bit;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn get_gate_index(&self, wire_name: WireName) -> usize {
+        Self::find_gate_index(&self.gates, self.layout, wire_name)
     }
 
     // use a binary search to find the gate index in the sorted gates vector
-    fn get_gate_index(gates: &[WireNameValue<NO_CASES>], wire_name: WireName) -> usize {
+    fn find_gate_index(
+        gates: &[WireNameValue<NO_CASES>],
+        layout: Layout,
+        wire_name: WireName,
+    ) -> usize {
         match wire_name[0] {
-            b'x' => X_OFFSET + wire_name.bit_index().unwrap() as usize,
-            b'y' => Y_OFFSET + wire_name.bit_index().unwrap() as usize,
-            b'z' => Z_OFFSET + wire_name.bit_index().unwrap() as usize,
+            b'x' => layout.x_offset() + wire_name.bit_index().unwrap() as usize,
+            b'y' => layout.y_offset() + wire_name.bit_index().unwrap() as usize,
+            b'z' => layout.z_offset() + wire_name.bit_index().unwrap() as usize,
             _ => {
                 let mut low = 0;
                 let mut high = gates.len();
@@ -228,8 +467,9 @@ impl<const NO_CASES: usize> Machine<NO_CASES> {
     }
 
     // get a list of wirenames for the indexes set in the gate flags
+    #[cfg(test)]
     fn get_gates(&self, gate_idx: usize) -> Vec<WireName> {
-        let gate_flags = self.wires[gate_idx].wire_analytics.gate_array;
+        let gate_flags = &self.wires[gate_idx].wire_analytics.gate_array;
         let mut gates_list = Vec::new();
         for (idx, engine_wire) in self.wires.iter().enumerate() {
             if gate_flags.get(idx) {
@@ -239,39 +479,29 @@ impl<const NO_CASES: usize> Machine<NO_CASES> {
         gates_list
     }
 
-    fn eval(&mut self, wire_idx: usize, z_bit: u8) -> (BitFlags<u128>, WireAnalytics) {
-        let (new_value, new_analytics) = match self.wires[wire_idx].value_calc {
-            WireValue::Value(b) => {
-                if let WireType::Input(_) = self.wires[wire_idx].wire_analytics.wire_type {
-                    self.wires[wire_idx].wire_analytics.lowest_output_bit = z_bit
-                }
-                (b, self.wires[wire_idx].wire_analytics)
-            }
-            WireValue::Gate {
-                input1: input1_idx,
-                input2: input2_idx,
-                operation,
-            } => {
-                let (input1, wa1) = self.eval(input1_idx, z_bit);
-                let (input2, wa2) = self.eval(input2_idx, z_bit);
-
-                let output = match operation {
-                    Operation::And => input1 & input2,
-                    Operation::Or => input1 | input2,
-                    Operation::Xor => input1 ^ input2,
-                };
-                let mut wire_analytics = wa1.merge(&wa2);
-                wire_analytics.gate_array.set(input1_idx);
-                wire_analytics.gate_array.set(input2_idx);
-
-                (output, wire_analytics)
+    // evaluates every wire for all the cases at once, without recursion
+    fn evaluate(&mut self) {
+        for wire in self.wires.iter_mut() {
+            if let WireValue::Value(value) = &wire.value_start {
+                wire.value_calc.clone_from(value);
             }
-        };
-        self.wires[wire_idx].value_calc = WireValue::Value(new_value);
-
-        self.wires[wire_idx].wire_analytics = new_analytics;
+        }
+        for &wire_idx in &self.order {
+            let mut value = std::mem::take(&mut self.wires[wire_idx].value_calc);
+            let WireValue::Gate { inputs, operation } = &self.wires[wire_idx].value_start else {
+                unreachable!("the order only holds gate wires")
+            };
+            operation.apply_into(
+                &mut value,
+                inputs.iter().map(|&input_idx| self.value(input_idx)),
+            );
+            self.wires[wire_idx].value_calc = value;
+        }
+    }
 
-        (new_value, new_analytics)
+    // the value of a wire for each case, from the latest evaluation
+    fn value(&self, wire_idx: usize) -> &BitSet {
+        &self.wires[wire_idx].value_calc
     }
 
     pub fn validate_wire(&self, wire_idx: usize) -> Result<()> {
@@ -293,27 +523,15 @@ impl<const NO_CASES: usize> Machine<NO_CASES> {
         Ok(())
     }
 
-    fn set_highest_output_bit(&mut self, wire_idx: usize, bit: u8) {
-        let this_wire = &mut self.wires[wire_idx];
-        if this_wire.wire_analytics.lowest_output_bit < bit {
-            this_wire.wire_analytics.lowest_output_bit = bit;
-        }
-        match this_wire.value_start {
-            WireValue::Value(_) => {}
-            WireValue::Gate { input1, input2, .. } => {
-                self.set_highest_output_bit(input1, bit);
-                self.set_highest_output_bit(input2, bit);
-            }
-        }
-    }
-
     #[inline(always)]
     pub fn eval_output(&mut self) -> [TestCaseOutput; NO_CASES] {
+        let _span = span::enter("Machine::eval_output");
+        self.evaluate();
         let mut actual_by_case = [0_usize; NO_CASES];
         for bit_index in 0..=self.highest_z_bit {
             let z_wire_name = WireName::from_char_bit(b'z', bit_index);
-            let z_wire_idx = Self::get_gate_index(&self.gates, z_wire_name);
-            let (z_wire_value_by_case, _) = self.eval(z_wire_idx, z_wire_idx as u8);
+            let z_wire_idx = self.get_gate_index(z_wire_name);
+            let z_wire_value_by_case = self.value(z_wire_idx);
             for (case, answer) in actual_by_case.iter_mut().enumerate() {
                 *answer |= (z_wire_value_by_case.get(case) as usize) << bit_index;
             }
@@ -341,7 +559,55 @@ impl<const NO_CASES: usize> Machine<NO_CASES> {
     }
 }
 
-#[derive(Clone, Copy, Default)]
+// the gate wires in an order where each comes after the gates driving its inputs, found by a
+// depth first search without recursion
+fn evaluation_order<const N: usize>(wires: &[wire::Wire<N>]) -> Result<Vec<usize>> {
+    // 0 is unvisited, 1 is on the current path and 2 is finished
+    let mut state = vec![0_u8; wires.len()];
+    let mut order = Vec::new();
+    for start in 0..wires.len() {
+        if state[start] != 0 {
+            continue;
+        }
+        state[start] = 1;
+        // each wire on the path, with the next of its inputs to visit
+        let mut path = vec![(start, 0)];
+        while let Some(&(wire_idx, next)) = path.last() {
+            let inputs = match &wires[wire_idx].value_start {
+                WireValue::Gate { inputs, .. } => &inputs[..],
+                WireValue::Value(_) => &[],
+            };
+            let Some(&input_idx) = inputs.get(next) else {
+                state[wire_idx] = 2;
+                if !inputs.is_empty() {
+                    order.push(wire_idx);
+                }
+                path.pop();
+                continue;
+            };
+            path.last_mut().unwrap().1 += 1;
+            match state[input_idx] {
+                0 => {
+                    state[input_idx] = 1;
+                    path.push((input_idx, 0));
+                }
+                1 => {
+                    // the path from the input back to here, where each wire is driven by the next
+                    let position = path.iter().position(|&(idx, _)| idx == input_idx).unwrap();
+                    let cycle = std::iter::once(input_idx)
+                        .chain(path[position + 1..].iter().rev().map(|&(idx, _)| idx))
+                        .map(|idx| wires[idx].wire_name)
+                        .collect();
+                    return Err(MachineError::CycleError(cycle));
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(order)
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TestCaseOutput {
     pub x: usize,
     pub y: usize,
@@ -364,13 +630,15 @@ impl Debug for TestCaseOutput {
 mod tests {
     use super::*;
     use crate::*;
+    use aoc_common::rng::Rng;
+    use itertools::Itertools;
 
     fn test_wire_analytics(input: &str) {
         let mut lm = Machine::<1>::new(input);
         lm.eval_output();
         lm.wires
             .iter()
-            .filter(|ew| ew.wire_name[0] == b'z' && !ew.wire_analytics.gate_array.is_empty())
+            .filter(|ew| ew.wire_name[0] == b'z' && ew.wire_analytics.gate_array.any())
             .for_each(|engine_wire| {
                 println!(
                     "test_wire_analytics::{:?} {:?}",
@@ -378,7 +646,7 @@ mod tests {
                     engine_wire.wire_analytics.gate_array.as_binary_string()
                 );
                 // use get_gates to get the wire names for the gate indexes set in the gate flags
-                let gates = lm.get_gates(Machine::get_gate_index(&lm.gates, engine_wire.wire_name));
+                let gates = lm.get_gates(lm.get_gate_index(engine_wire.wire_name));
                 for gate in gates {
                     print!("  {:?}", gate);
                 }
@@ -409,10 +677,219 @@ mod tests {
         println!("\ntest_part1_2::ending");
     }
 
+    #[test]
+    fn test_layout() {
+        let layout = Layout::from_input(TESTINPUT2).unwrap();
+        assert_eq!(
+            layout,
+            Layout {
+                no_gates: 36,
+                input_bits: 5,
+                output_bits: 13
+            }
+        );
+        assert_eq!(layout.no_wires(), 36 + 5 * 2 + 13);
+        let error = |input| Layout::from_input(input).unwrap_err().to_string();
+        assert_eq!(
+            error("x00: 1\n\nx00 AND y00 -> x01"),
+            "Parse Error: line 3: the gate drives the input wire x01"
+        );
+        assert!(error("x63: 1\n\nx63 AND y00 -> z00").contains("input bits"));
+        assert!(Layout::from_input("x62: 1\n\nx62 AND y62 -> z63").is_ok());
+        assert_eq!(
+            Layout::from_input("x00 AND y00 -> z00").unwrap().no_gates,
+            1
+        );
+    }
+
+    #[test]
+    fn test_operations() {
+        let input = "\
+x00 NAND y00 -> z00
+x00 NOR y00 -> z01
+x00 XNOR y00 -> z02
+NOT x00 -> z03
+x00 AND y00 AND x01 -> z04
+x00 OR y00 OR x01 OR y01 -> z05
+x00 XOR y00 XOR x01 -> z06
+NOT z00 -> abc
+abc NOR abc -> z07";
+        for x in 0..4 {
+            for y in 0..4 {
+                let (x0, y0, x1, y1) = (x & 1, y & 1, x >> 1, y >> 1);
+                let expected = [
+                    1 - (x0 & y0),
+                    1 - (x0 | y0),
+                    1 - (x0 ^ y0),
+                    1 - x0,
+                    x0 & y0 & x1,
+                    x0 | y0 | x1 | y1,
+                    x0 ^ y0 ^ x1,
+                    1 - (x0 & y0),
+                ];
+                let expected = expected
+                    .iter()
+                    .enumerate()
+                    .fold(0, |z, (bit, value)| z | value << bit);
+                assert_eq!(Machine::calc(input, x, y), expected, "{x} {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_any_size() {
+        for bits in [1, 2, 8, 45, 50, MAX_INPUT_BITS] {
+            let mask = usize::MAX >> (usize::BITS as usize - bits);
+            for (x, y) in [
+                (0, 0),
+                (1, mask),
+                (mask, mask),
+                (0x5555_5555_5555_5555 & mask, 3 & mask),
+            ] {
+                let input = adder_input(bits, x, y);
+                let layout = Layout::from_input(&input).unwrap();
+                assert_eq!(layout.no_gates, 5 * bits - 3);
+                assert_eq!(layout.input_bits, bits);
+                let mut lm = Machine::new(&input);
+                assert_eq!(lm.eval_output()[0].actual, x + y, "{bits} bits, {x} + {y}");
+                assert_eq!(Machine::calc(&input, y, x), x + y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_many_cases() {
+        let mut rng = Rng::new(24);
+        let cases = std::array::from_fn(|_| InputPair {
+            x: rng.below(1 << 45) as usize,
+            y: rng.below(1 << 45) as usize,
+        });
+        let mut lm = Machine::<4096>::new_with_cases(&adder_input(45, 0, 0), cases);
+        let outputs = lm.eval_output();
+        assert!(
+            outputs
+                .iter()
+                .all(|output| output.actual == output.expected)
+        );
+        assert_eq!(outputs[4095].x, cases[4095].x);
+
+        let a10 = lm.get_gate_index(WireName(*b"a10"));
+        let s10 = lm.get_gate_index(WireName(*b"s10"));
+        lm.swap_outputs(a10, s10).unwrap();
+        let failures = lm
+            .eval_output()
+            .iter()
+            .filter(|output| output.z_wire_misses != 0)
+            .count();
+        assert!(failures > 1000 && failures < 4096, "{failures}");
+    }
+
+    #[test]
+    fn test_missing_z_bits() {
+        // no gate drives z01, which reads as 0
+        let mut machine =
+            Machine::try_new("x00: 1\ny00: 1\n\nx00 AND y00 -> z00\nx00 OR y00 -> z02").unwrap();
+        assert_eq!(machine.eval_output()[0].actual, 0b101);
+        let mut machine = Machine::try_new("x00: 1\ny00: 1\n\nx00 AND y00 -> abc").unwrap();
+        let output = machine.eval_output()[0];
+        assert_eq!((output.actual, output.expected), (0, 2));
+        for input in [
+            "x00: 1\ny00: 1\n\nx00 AND y00 -> z00\nx00 OR y00 -> z02",
+            "x00 AND y00 -> abc",
+        ] {
+            MachineFixer::try_new(input).unwrap().run_and_analyse();
+        }
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(Machine::try_new(TESTINPUT).is_ok());
+        let cycle = "x00: 1\ny00: 0\n\nx00 AND abc -> abc\nabc OR y00 -> z00";
+        assert_eq!(
+            Machine::try_new(cycle).unwrap_err().to_string(),
+            "Cycle Error: abc -> abc"
+        );
+        assert!(MachineFixer::try_new(cycle).is_err());
+        assert!(
+            Machine::<2>::try_new_with_cases("x00 FOO y00 -> z00", Default::default()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cycles() {
+        let error = |input: &str| {
+            let circuit = parser::parse(input).unwrap();
+            Machine::from_circuit(&circuit, [InputPair::default()])
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("x00 AND abc -> def\ndef OR y00 -> abc\nabc XOR x00 -> z00"),
+            "Cycle Error: abc -> def -> abc"
+        );
+        assert_eq!(
+            error("abc AND x00 -> abc\nabc OR y00 -> z00"),
+            "Cycle Error: abc -> abc"
+        );
+        assert_eq!(
+            error("x00 AND z01 -> z00\nNOT z02 -> z01\nz00 OR y00 -> z02"),
+            "Cycle Error: z00 -> z02 -> z01 -> z00"
+        );
+        assert!(crate::Day24::parse("x00 XOR z00 -> z00").is_err());
+    }
+
+    #[test]
+    fn test_swap_cycle() {
+        let input = adder_input(4, 7, 1);
+        let mut lm = Machine::new(&input);
+        let carry = lm.get_gate_index(WireName(*b"c01"));
+        let sum = lm.get_gate_index(WireName(*b"s03"));
+        // c01 would be driven by the XOR of s02 and c01 itself
+        let z02 = lm.get_gate_index(WireName(*b"z02"));
+        assert!(matches!(
+            lm.swap_outputs(carry, z02),
+            Err(MachineError::CycleError(_))
+        ));
+        assert_eq!(lm.eval_output()[0].actual, 8);
+        lm.swap_outputs(carry, sum).unwrap();
+        assert_ne!(lm.eval_output()[0].actual, 8);
+    }
+
+    #[test]
+    fn test_deep_chain() {
+        // far deeper than the stack would allow a recursive evaluation to go
+        let digit = |value: usize| char::from_digit(value as u32 % 36, 36).unwrap();
+        let names: Vec<_> = (0..9_999)
+            .map(|idx| {
+                format!(
+                    "{}{}{}",
+                    (b'a' + (idx / 1296) as u8) as char,
+                    digit(idx / 36),
+                    digit(idx)
+                )
+            })
+            .collect();
+        let chain = |first: &str| {
+            let mut input = format!("x00: 1\nNOT {first} -> {}\n", names[0]);
+            for (from, to) in names.iter().tuple_windows() {
+                input.push_str(&format!("NOT {from} -> {to}\n"));
+            }
+            input + &format!("NOT {} -> z00\n", names[names.len() - 1])
+        };
+        assert_eq!(Machine::new(&chain("x00")).eval_output()[0].actual, 1);
+        let circuit = parser::parse(&chain("z00")).unwrap();
+        let error = Machine::from_circuit(&circuit, [InputPair::default()]).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Cycle Error: a00 -> a01 -> a02")
+        );
+    }
+
     #[test]
     fn test_get_gate_index() {
         println!("\ntest_get_gate_index::starting");
-        let mut lm = Machine::<1>::new(TESTINPUT2);
+        let lm = Machine::<1>::new(TESTINPUT2);
 
         println!("\ngate array");
 
@@ -420,19 +897,9 @@ mod tests {
         // show the any wire name that does not index itself correctly. Show both the index and the wire name.
         // ignore wire names starting with 'w'.
         // print the wire name and the index if they do not match.
-        for (
-            idx,
-            wire::Wire {
-                wire_name,
-                wire_index,
-                value_start,
-                value_calc,
-                wire_analytics,
-            },
-        ) in lm.wires.iter().enumerate()
-        {
+        for (idx, wire::Wire { wire_name, .. }) in lm.wires.iter().enumerate() {
             if wire_name[0] != b'w' {
-                let found_idx = Machine::get_gate_index(&lm.gates, *wire_name);
+                let found_idx = lm.get_gate_index(*wire_name);
                 if found_idx != idx {
                     println!(
                         "wire name {:?} index {} found index {}",
@@ -447,7 +914,7 @@ mod tests {
         // run the same test for the engine array
         for (idx, engine_wire) in lm.wires.iter().enumerate() {
             if engine_wire.wire_name[0] != b'w' {
-                let found_idx = Machine::get_gate_index(&lm.gates, engine_wire.wire_name);
+                let found_idx = lm.get_gate_index(engine_wire.wire_name);
                 if found_idx != idx {
                     println!(
                         "wire name {:?} index {} found index {}",
@@ -459,42 +926,38 @@ mod tests {
 
         // Test x wire indices
         let x0 = WireName::from_char_bit(b'x', 0);
-        assert_eq!(Machine::get_gate_index(&lm.gates, x0), NO_GATES);
+        let no_gates = lm.layout().no_gates;
+        let input_bits = lm.layout().input_bits;
+        assert_eq!(lm.get_gate_index(x0), no_gates);
 
         let x1 = WireName::from_char_bit(b'x', 1);
-        assert_eq!(Machine::get_gate_index(&lm.gates, x1), NO_GATES + 1);
+        assert_eq!(lm.get_gate_index(x1), no_gates + 1);
 
         // Test y wire indices
         let y0 = WireName::from_char_bit(b'y', 0);
-        assert_eq!(
-            Machine::get_gate_index(&lm.gates, y0),
-            NO_GATES + INPUT_BITS
-        );
+        assert_eq!(lm.get_gate_index(y0), no_gates + input_bits);
 
         let y1 = WireName::from_char_bit(b'y', 1);
-        assert_eq!(
-            Machine::get_gate_index(&lm.gates, y1),
-            NO_GATES + INPUT_BITS + 1
-        );
+        assert_eq!(lm.get_gate_index(y1), no_gates + input_bits + 1);
 
         // Test gate wire indices (these should use binary search)
         // Note: Exact indices will depend on the test input gates
         let gate_wire = lm.gates[0].wire_name;
-        assert_eq!(Machine::get_gate_index(&lm.gates, gate_wire), 0);
+        assert_eq!(lm.get_gate_index(gate_wire), 0);
         println!("test_get_gate_index::ending");
     }
 
     #[test]
     fn test_engine_initialization() {
         println!("\ntest_engine_initialization::starting");
-        let mut lm = Machine::<1>::new(TESTINPUT);
+        let lm = Machine::<1>::new(TESTINPUT);
 
         // Check x wire initialization
-        let x0_idx = Machine::get_gate_index(&lm.gates, WireName::from_char_bit(b'x', 0));
+        let x0_idx = lm.get_gate_index(WireName::from_char_bit(b'x', 0));
         assert_eq!(lm.wires[x0_idx].wire_name[0], b'x');
 
         // Check y wire initialization
-        let y0_idx = Machine::get_gate_index(&lm.gates, WireName::from_char_bit(b'y', 0));
+        let y0_idx = lm.get_gate_index(WireName::from_char_bit(b'y', 0));
         assert_eq!(lm.wires[y0_idx].wire_name[0], b'y');
         println!("test_engine_initialization::ending");
     }
@@ -1,126 +1,302 @@
-#![allow(dead_code)]
-#![allow(unused_variables)]
+use std::{collections::HashSet, fmt::Display};
+
+use itertools::Itertools;
 
 use crate::{
-    bit_array::LargeBitFlags,
-    machine::{INPUT_BITS, InputPair, Machine, NO_WIRES, OUTPUT_BITS, Z_OFFSET},
+    analysis::{Analysis, ValidationFailure},
+    bit_array::BitSet,
+    errors::Result,
+    machine::{InputPair, Layout, MAX_INPUT_BITS, Machine, TestCaseOutput},
+    parser::{self, Circuit},
+    suite::Suite,
     wire::WireName,
 };
 
-const NO_CASES: usize = 1 + INPUT_BITS * 2;
-pub const TEST_CASES: [InputPair; NO_CASES] = {
-    const ALL_ONES: usize = (2 << INPUT_BITS) - 1;
-    let mut test_cases = [InputPair { x: 0, y: 0 }; 1 + INPUT_BITS * 2];
-    let mut case_idx = 0;
-    test_cases[case_idx] = InputPair { x: 0, y: 0 };
-    case_idx += 1;
-    let mut i = 0;
-    while i < INPUT_BITS {
-        test_cases[case_idx] = InputPair { x: 0, y: 1 << i };
-        case_idx += 1;
-        test_cases[case_idx] = InputPair {
+const NO_CASES: usize = 1 + MAX_INPUT_BITS * 2;
+
+// adds a single bit, and a single bit with a carry, at each bit position of the input. the
+// cases beyond the input width are left as 0 + 0.
+pub fn test_cases(input_bits: usize) -> [InputPair; NO_CASES] {
+    let all_ones = (1 << input_bits) - 1;
+    let mut test_cases = [InputPair { x: 0, y: 0 }; NO_CASES];
+    for i in 0..input_bits {
+        test_cases[1 + i * 2] = InputPair { x: 0, y: 1 << i };
+        test_cases[2 + i * 2] = InputPair {
             x: 1 << i,
-            y: 3 << i,
+            y: (3 << i) & all_ones,
         };
-
-        case_idx += 1;
-        i += 1;
     }
-
     test_cases
+}
 
-    // [
-    //     (0, 0),
-    //     (0, 1),
-    //     (1, 1),
-    //     (ALL_ONES, 0),
-    //     (1, ALL_ONES),
-    //     (ALL_ONES, ALL_ONES),
-    // ]
-};
-
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct MachineFixer {
     machine: Machine<NO_CASES>,
 }
 
+/// The test cases a machine got wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub cases: usize,
+    pub failures: Vec<TestCaseOutput>,
+    pub z_wire_misses: usize,
+}
+
+impl Verification {
+    fn new(machine: &mut Machine<NO_CASES>) -> Self {
+        let cases = 1 + machine.layout().input_bits * 2;
+        let failures: Vec<_> = machine.eval_output()[..cases]
+            .iter()
+            .filter(|output| output.z_wire_misses != 0)
+            .copied()
+            .collect();
+        let z_wire_misses = failures
+            .iter()
+            .fold(0, |acc, output| acc | output.z_wire_misses);
+        Self {
+            cases,
+            failures,
+            z_wire_misses,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn lowest_failing_bit(&self) -> Option<usize> {
+        (self.z_wire_misses != 0).then(|| self.z_wire_misses.trailing_zeros() as usize)
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of {} test cases failed",
+            self.failures.len(),
+            self.cases
+        )?;
+        if let Some(bit) = self.lowest_failing_bit() {
+            write!(
+                f,
+                ", z wire misses {:b}, lowest z{bit:02}",
+                self.z_wire_misses
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The output swaps which make the machine add, with the test results before and after.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+    pub swaps: Vec<(WireName, WireName)>,
+    pub before: Verification,
+    pub after: Verification,
+}
+
+impl Repair {
+    // the names of the swapped wires in order, as the puzzle asks for them
+    pub fn answer(&self) -> String {
+        self.swaps
+            .iter()
+            .flat_map(|&(wire1, wire2)| [wire1, wire2])
+            .sorted()
+            .map(|wire_name| wire_name.as_string())
+            .join(",")
+    }
+}
+
+impl Display for Repair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "before: {}", self.before)?;
+        for (wire1, wire2) in &self.swaps {
+            writeln!(f, "swap {} and {}", wire1.as_string(), wire2.as_string())?;
+        }
+        write!(f, "after: {}", self.after)
+    }
+}
+
 impl MachineFixer {
     pub fn new(input: &str) -> Self {
-        let mut machine = Machine::<NO_CASES>::new_with_cases(input, TEST_CASES);
-        Self { machine }
+        Self::try_new(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    pub fn try_new(input: &str) -> Result<Self> {
+        Self::from_circuit(&parser::parse(input)?)
     }
 
-    pub fn run_and_analyse(&mut self) {
-        let test_outputs_by_case = self.machine.eval_output();
+    pub fn from_circuit(circuit: &Circuit) -> Result<Self> {
+        let cases = test_cases(Layout::new(circuit).input_bits);
+        Ok(Self {
+            machine: Machine::from_circuit(circuit, cases)?,
+        })
+    }
 
-        // check that the gate analytics are valid
-        for wire in self.machine.wires.iter() {
-            match wire.wire_analytics.validate() {
-                Ok(_) => {}
-                Err(e) => {
-                    println!("Wire {}: {}", wire.wire_name.as_string(), e);
-                }
+    pub fn machine(&self) -> &Machine<NO_CASES> {
+        &self.machine
+    }
+
+    /// The gate wires which no correct z wire depends on, which is where the faults can be.
+    pub fn suspicious_wires(&mut self) -> Vec<WireName> {
+        let z_wire_misses = Verification::new(&mut self.machine).z_wire_misses;
+        self.suspicious_wires_for(z_wire_misses)
+    }
+
+    fn suspicious_wires_for(&self, z_wire_misses: usize) -> Vec<WireName> {
+        let layout = self.machine.layout();
+        let mut good_gates = BitSet::new(layout.no_wires());
+        for bit in 0..layout.output_bits {
+            if z_wire_misses & (1 << bit) == 0 {
+                let z_wire_idx = layout.z_offset() + bit;
+                good_gates |= &self.machine.wires[z_wire_idx].wire_analytics.gate_array;
+                good_gates.set(z_wire_idx);
             }
         }
+        self.machine
+            .gate_wires()
+            .filter(|&wire_idx| !good_gates.get(wire_idx))
+            .map(|wire_idx| self.machine.wires[wire_idx].wire_name)
+            .collect()
+    }
 
-        // print the test outputs.  x, y, actual and expected should be printed as integers.  z wire misses should be printed as binary.
-        for (i, test_output) in test_outputs_by_case.iter().enumerate() {
-            println!("Test case {:2}: {:?}", i, test_output);
-        }
+    /// Finds the fewest swaps of gate outputs, up to `max_swaps`, after which every test case
+    /// and every carry chain adds correctly. Each swap must fix the lowest failing z bit, so
+    /// only the wires near that bit are tried: those in the z wire's dependencies and the gates
+    /// whose highest input bit is within one of it.
+    pub fn repair(&self, max_swaps: usize) -> Option<Repair> {
+        let mut machine = self.machine.clone();
+        let before = Verification::new(&mut machine);
+        let mut swaps = Vec::new();
+        (0..=max_swaps).find(|&max_swaps| Self::search(&mut machine, max_swaps, &mut swaps))?;
+        let after = Verification::new(&mut machine);
+        let swaps = swaps
+            .into_iter()
+            .map(|(wire_idx1, wire_idx2)| {
+                let wire_name1 = machine.wires[wire_idx1].wire_name;
+                let wire_name2 = machine.wires[wire_idx2].wire_name;
+                (wire_name1.min(wire_name2), wire_name1.max(wire_name2))
+            })
+            .sorted()
+            .collect();
+        Some(Repair {
+            swaps,
+            before,
+            after,
+        })
+    }
 
-        let z_wire_any_miss = test_outputs_by_case
-            .iter()
-            .fold(0, |acc, &x| acc | x.z_wire_misses);
-
-        // any gate which is a dependency for a good z wire should be marked as good
-        let mut good_gates = LargeBitFlags::default();
-        for i in 0..OUTPUT_BITS {
-            if z_wire_any_miss & (1 << i) == 0 {
-                // This bit is not set in z_wire_any_miss, so we can find the gates that are dependencies for this bit and mark them as good.
-                let gate_dependencies = self.machine.wires[Z_OFFSET + i].wire_analytics.gate_array;
-                good_gates |= gate_dependencies;
-            }
+    // leaves the machine with the swaps applied when it finds them
+    fn search(
+        machine: &mut Machine<NO_CASES>,
+        max_swaps: usize,
+        swaps: &mut Vec<(usize, usize)>,
+    ) -> bool {
+        let Some(bit) = Self::lowest_failing_bit(machine) else {
+            return true;
+        };
+        if max_swaps == 0 {
+            return false;
         }
+        let layout = machine.layout();
+        let swapped: HashSet<usize> = swaps.iter().flat_map(|&(a, b)| [a, b]).collect();
+        let near_bit = |wire_idx: &usize| {
+            let highest_input_bit =
+                machine.wires[*wire_idx].wire_analytics.highest_input_bit as usize;
+            !swapped.contains(wire_idx)
+                && highest_input_bit + 1 >= bit
+                && highest_input_bit <= bit + 1
+        };
+        let z_wire_idx = layout.z_offset() + bit;
+        let dependencies = &machine.wires[z_wire_idx].wire_analytics.gate_array;
+        let candidates: Vec<usize> = machine
+            .gate_wires()
+            .filter(|&wire_idx| wire_idx == z_wire_idx || dependencies.get(wire_idx))
+            .filter(near_bit)
+            .collect();
+        let partners: Vec<usize> = machine.gate_wires().filter(near_bit).collect();
 
-        for i in 0..OUTPUT_BITS {
-            // if z wire is good then print the gate dependencies
-            if z_wire_any_miss & (1 << i) == 0 {
-                let gate_dependencies = self.machine.wires[Z_OFFSET + i].wire_analytics.gate_array;
-                let good_gate_list: Vec<String> = gate_dependencies
-                    .0
-                    .iter()
-                    .map(|x| format!("{:#?}", x))
-                    .collect();
-                println!(
-                    "Good gate dependencies for z{}: {}",
-                    i,
-                    good_gate_list.join(", ")
-                );
+        let mut tried = HashSet::new();
+        for &wire_idx1 in &candidates {
+            for &wire_idx2 in &partners {
+                if wire_idx1 == wire_idx2
+                    || !tried.insert((wire_idx1.min(wire_idx2), wire_idx1.max(wire_idx2)))
+                {
+                    continue;
+                }
+                if machine.swap_outputs(wire_idx1, wire_idx2).is_err() {
+                    continue;
+                }
+                let next_bit = Self::lowest_failing_bit(machine);
+                if next_bit.is_none_or(|next_bit| next_bit > bit) {
+                    swaps.push((wire_idx1, wire_idx2));
+                    if Self::search(machine, max_swaps - 1, swaps) {
+                        return true;
+                    }
+                    swaps.pop();
+                }
+                machine
+                    .swap_outputs(wire_idx1, wire_idx2)
+                    .expect("swapping back restores a machine without loops");
             }
         }
+        false
+    }
 
-        println!("Good gates: ");
-        self.machine
-            .wires
-            .iter()
-            .take_while(|x| x.wire_name < WireName::default())
-            .filter(|x| good_gates.get(x.wire_index))
-            .for_each(|x| print!(" {:#?}", x.wire_name.as_string()));
-        println!();
+    // the fixed cases catch most faults quickly, and the carry suite then checks the long chains
+    fn lowest_failing_bit(machine: &mut Machine<NO_CASES>) -> Option<usize> {
+        Verification::new(machine).lowest_failing_bit().or_else(|| {
+            machine
+                .verify(&Suite::Carries)
+                .expect("the carry suite suits any width")
+                .lowest_failing_bit()
+        })
+    }
 
-        println!("Possibly bad gates: ");
-        self.machine
-            .wires
-            .iter()
-            .take_while(|x| x.wire_name < WireName::default())
-            .filter(|x| !good_gates.get(x.wire_index))
-            .for_each(|x| print!(" {:#?}", x.wire_name.as_string()));
-        println!();
-
-        for wire_idx in 0..NO_WIRES {
-            if let Err(e) = self.machine.validate_wire(wire_idx) {
-                println!("error on wire {}, {}", wire_idx, e);
-            }
+    /// Tests the machine and checks the analytics of every wire.
+    pub fn run_and_analyse(&mut self) -> Analysis {
+        let verification = Verification::new(&mut self.machine);
+        let layout = self.machine.layout();
+        let validation_failures = (0..layout.no_wires())
+            .filter(|&wire_idx| self.machine.wires[wire_idx].wire_name != WireName::default())
+            .filter_map(|wire_idx| {
+                self.machine
+                    .validate_wire(wire_idx)
+                    .err()
+                    .map(|error| ValidationFailure {
+                        wire: self.machine.wires[wire_idx].wire_name,
+                        error,
+                    })
+            })
+            .sorted_by_key(|failure| failure.wire)
+            .collect();
+        let cones = (0..layout.output_bits)
+            .map(|bit| {
+                let z_wire = &self.machine.wires[layout.z_offset() + bit];
+                let cone = z_wire
+                    .wire_analytics
+                    .gate_array
+                    .iter_ones()
+                    .map(|wire_idx| self.machine.wires[wire_idx].wire_name)
+                    .sorted()
+                    .collect();
+                (z_wire.wire_name, cone)
+            })
+            .collect();
+        Analysis {
+            cases: verification.cases,
+            failing_z_bits: (0..layout.output_bits)
+                .filter(|bit| verification.z_wire_misses >> bit & 1 == 1)
+                .collect(),
+            validation_failures,
+            suspicious_gates: self
+                .suspicious_wires_for(verification.z_wire_misses)
+                .into_iter()
+                .sorted()
+                .collect(),
+            cones,
+            failures: verification.failures,
         }
     }
 }
@@ -129,13 +305,131 @@ impl MachineFixer {
 mod tests {
 
     use super::*;
-    use crate::INPUT;
-    use stephen_morris_utils::timer::time;
+    use crate::{Day24, adder_input};
+    use aoc_common::Solution;
 
     #[test]
+    fn test_test_cases() {
+        for bits in [3, 45, MAX_INPUT_BITS] {
+            let mut machine =
+                Machine::<NO_CASES>::new_with_cases(&adder_input(bits, 0, 0), test_cases(bits));
+            let outputs = machine.eval_output();
+            assert!(outputs.iter().all(|output| output.z_wire_misses == 0));
+            let limit = 2u128 << bits;
+            assert!(
+                outputs
+                    .iter()
+                    .all(|output| (output.expected as u128) < limit)
+            );
+        }
+    }
+
+    // exchanges the gates driving each pair of wires in the input text
+    fn swap_outputs(input: &str, swaps: &[(&str, &str)]) -> String {
+        let mut input = input.to_string();
+        for (wire1, wire2) in swaps {
+            input = input
+                .replace(&format!("-> {wire1}"), "-> ???")
+                .replace(&format!("-> {wire2}"), &format!("-> {wire1}"))
+                .replace("-> ???", &format!("-> {wire2}"));
+        }
+        input
+    }
+
+    #[test]
+    fn test_repair() {
+        let swaps = [
+            ("a30", "s30"),
+            ("b12", "z12"),
+            ("c20", "z20"),
+            ("a05", "z05"),
+        ];
+        let input = swap_outputs(&adder_input(45, 0, 0), &swaps);
+        let fixer = MachineFixer::new(&input);
+        let repair = fixer.repair(4).unwrap();
+        let expected: Vec<_> = swaps
+            .iter()
+            .map(|(wire1, wire2)| {
+                let name = |wire: &str| WireName::from_slice(wire.as_bytes());
+                (name(wire1), name(wire2))
+            })
+            .sorted()
+            .collect();
+        assert_eq!(repair.swaps, expected);
+        assert_eq!(repair.answer(), "a05,a30,b12,c20,s30,z05,z12,z20");
+        assert_eq!(repair.before.lowest_failing_bit(), Some(5));
+        assert!(repair.after.passed());
+        assert_eq!(repair.after.cases, 91);
+
+        assert_eq!(fixer.repair(3), None);
+
+        let parsed = Day24::parse(&input).unwrap();
+        assert_eq!(Day24::part2(&parsed).unwrap(), repair.answer());
+        let input = swap_outputs(&input, &[("a40", "s40")]);
+        let parsed = Day24::parse(&input).unwrap();
+        assert_eq!(
+            Day24::part2(&parsed).unwrap_err().to_string(),
+            "Repair Error: no 4 swaps of outputs repair the adder"
+        );
+    }
+
+    #[test]
+    fn test_suspicious_wires() {
+        let input = swap_outputs(&adder_input(20, 0, 0), &[("a10", "s10")]);
+        let mut fixer = MachineFixer::new(&input);
+        let suspicious: Vec<_> = fixer
+            .suspicious_wires()
+            .iter()
+            .map(|wire_name| wire_name.as_string())
+            .collect();
+        // the swapped wires also feed the carry into the correct higher bits
+        assert_eq!(suspicious, ["z10", "z11", "z12"]);
+        assert!(
+            MachineFixer::new(&adder_input(20, 0, 0))
+                .suspicious_wires()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_repair_correct_machine() {
+        let fixer = MachineFixer::new(&adder_input(10, 0, 0));
+        let repair = fixer.repair(4).unwrap();
+        assert!(repair.swaps.is_empty() && repair.before.passed());
+        assert_eq!(repair.answer(), "");
+    }
+
+    #[test]
+    fn test_repair_long_carry_chain() {
+        // a fault the fixed cases miss, which no swap can mend
+        let input = adder_input(20, 0, 0).replace(
+            "s11 AND c10 -> b11",
+            "s11 AND c10 AND d11 -> b11\nb10 NAND b09 -> d11",
+        );
+        let fixer = MachineFixer::new(&input);
+        assert_eq!(fixer.repair(0), None);
+        assert!(fixer.repair(1).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-input")]
+    fn test_repair_input() {
+        let repair = MachineFixer::new(crate::INPUT).repair(4).unwrap();
+        assert_eq!(repair.swaps.len(), 4);
+        assert!(repair.after.passed());
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-input")]
     fn test_logic_tester() {
-        let input = INPUT;
+        let input = crate::INPUT;
         let mut machine_fixer = MachineFixer::new(input);
-        machine_fixer.run_and_analyse();
+        let analysis = machine_fixer.run_and_analyse();
+        assert!(analysis.validation_failures.is_empty());
+        for bit in [18, 22, 27] {
+            assert!(analysis.failing_z_bits.contains(&bit));
+            let z_wire = WireName::from_char_bit(b'z', bit as u8);
+            assert!(analysis.suspicious_gates.contains(&z_wire));
+        }
     }
 }
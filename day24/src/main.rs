@@ -1,80 +1,45 @@
-#![allow(dead_code, unused)]
-
-use machine::Machine;
+use aoc_common::{input, span};
+use day24::EMBEDDED_INPUT;
+use day24::machine::Machine;
+use day24::machine_fixer::MachineFixer;
+use day24::netlist;
 use stephen_morris_utils::timer::time;
-const INPUT: &str = include_str!("input.txt");
-
-#[cfg(test)]
-const TESTINPUT: &str = "x00: 1
-x01: 1
-x02: 1
-y00: 0
-y01: 1
-y02: 0
-
-x00 AND y00 -> z00
-x01 XOR y01 -> z01
-x02 OR y02 -> z02";
-#[cfg(test)]
-const TESTINPUT2: &str = "x00: 1
-x01: 0
-x02: 1
-x03: 1
-x04: 0
-y00: 1
-y01: 1
-y02: 1
-y03: 1
-y04: 1
-
-ntg XOR fgs -> mjb
-y02 OR x01 -> tnw
-kwq OR kpj -> z05
-x00 OR x03 -> fst
-tgd XOR rvg -> z01
-vdt OR tnw -> bfw
-bfw AND frj -> z10
-ffh OR nrd -> bqk
-y00 AND y03 -> djm
-y03 OR y00 -> psh
-bqk OR frj -> z08
-tnw OR fst -> frj
-gnj AND tgd -> z11
-bfw XOR mjb -> z00
-x03 OR x00 -> vdt
-gnj AND wpb -> z02
-x04 AND y00 -> kjc
-djm OR pbm -> qhw
-nrd AND vdt -> hwm
-kjc AND fst -> rvg
-y04 OR y02 -> fgs
-y01 AND x02 -> pbm
-ntg OR kjc -> kwq
-psh XOR fgs -> tgd
-qhw XOR tgd -> z09
-pbm OR djm -> kpj
-x03 XOR y03 -> ffh
-x00 XOR y04 -> ntg
-bfw OR bqk -> z06
-nrd XOR fgs -> wpb
-frj XOR qhw -> z04
-bqk OR frj -> z07
-y03 OR x01 -> nrd
-hwm AND bqk -> z03
-tgd XOR rvg -> z12
-tnw OR pbm -> gnj";
-
-mod bit_array;
-mod errors;
-mod machine;
-mod machine_fixer;
-mod wire;
-mod wire_analytics;
 
 fn main() {
-    let mut lm = time(|| Machine::new(INPUT), "Logic");
+    let input = input::load_from_args(24, EMBEDDED_INPUT);
+    if std::env::args().any(|arg| arg == "--dot") {
+        let mut fixer = input::or_exit(MachineFixer::try_new(&input));
+        let suspicious = fixer.suspicious_wires();
+        print!("{}", netlist::to_dot(fixer.machine(), &suspicious));
+        return;
+    }
+    if std::env::args().any(|arg| arg == "--analyse") {
+        let analysis = input::or_exit(MachineFixer::try_new(&input)).run_and_analyse();
+        match std::env::args().any(|arg| arg == "--json") {
+            true => println!("{}", analysis.to_json()),
+            false => print!("{analysis}"),
+        }
+        return;
+    }
+    if std::env::args().any(|arg| arg == "--verilog") {
+        let machine = input::or_exit(Machine::try_new(&input));
+        print!("{}", netlist::to_verilog(&machine, "day24"));
+        return;
+    }
+    span::enable();
+    let mut lm = time(|| input::or_exit(Machine::try_new(&input)), "Logic");
     let output = time(|| lm.eval_output(), "eval_all");
 
     lm.print_duration();
     output.print_all();
+
+    let fixer = input::or_exit(MachineFixer::try_new(&input));
+    let repair = time(|| fixer.repair(4), "repair");
+    repair.print_duration();
+    match &*repair {
+        Some(repair) => println!("{repair}\n{}", repair.answer()),
+        None => println!("no repair with up to 4 swaps"),
+    }
+
+    print!("\n{}", span::take());
 }
@@ -0,0 +1,451 @@
+//! Writes a machine's gates as a Graphviz DOT graph or a structural Verilog module, and reads
+//! such a module back into the puzzle's input format.
+//!
+//! The Verilog subset is one module with `input`, `output` and `wire` declarations and gate
+//! primitives (`and`, `or`, `xor`, `nand`, `nor`, `xnor` with any number of inputs, and `not`
+//! with one), each with an optional instance name and the output first:
+//!
+//! ```text
+//! module adder(x00, y00, z00, z01);
+//!     input x00, y00;
+//!     output z00, z01;
+//!     and g_z01 (z01, x00, y00);
+//!     xor g_z00 (z00, x00, y00);
+//! endmodule
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::{
+    errors::{MachineError, Result},
+    machine::{Machine, Operation},
+    parser::{self, Gate},
+    wire::{WireName, WireValue},
+};
+
+/// The machine's gates by wire names, with any swaps made to the machine applied.
+pub fn gates<const N: usize>(machine: &Machine<N>) -> Vec<Gate> {
+    machine
+        .gate_wires()
+        .map(|wire_idx| {
+            let wire = &machine.wires[wire_idx];
+            let WireValue::Gate { inputs, operation } = &wire.value_start else {
+                unreachable!()
+            };
+            Gate {
+                output: wire.wire_name,
+                operation: *operation,
+                inputs: inputs
+                    .iter()
+                    .map(|&input| machine.wires[input].wire_name)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+fn input_names<const N: usize>(machine: &Machine<N>) -> Vec<WireName> {
+    let layout = machine.layout();
+    (layout.x_offset()..layout.z_offset())
+        .map(|wire_idx| machine.wires[wire_idx].wire_name)
+        .collect()
+}
+
+/// The circuit as a left-to-right graph, with an edge from each gate input to its output. The
+/// `highlighted` wires, such as `MachineFixer::suspicious_wires`, are drawn in red.
+pub fn to_dot<const N: usize>(machine: &Machine<N>, highlighted: &[WireName]) -> String {
+    let highlighted: HashSet<_> = highlighted.iter().collect();
+    let style = |wire_name: &WireName| match highlighted.contains(wire_name) {
+        true => ", color=red, fontcolor=red",
+        false => "",
+    };
+    let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n    node [shape=box];\n");
+    for wire_name in input_names(machine) {
+        let name = wire_name.as_string();
+        dot.push_str(&format!(
+            "    {name} [shape=ellipse{}];\n",
+            style(&wire_name)
+        ));
+    }
+    let gates = gates(machine);
+    for gate in &gates {
+        let name = gate.output.as_string();
+        let peripheries = if name.starts_with('z') {
+            ", peripheries=2"
+        } else {
+            ""
+        };
+        dot.push_str(&format!(
+            "    {name} [label=\"{name}\\n{}\"{peripheries}{}];\n",
+            gate.operation.name(),
+            style(&gate.output)
+        ));
+    }
+    for gate in &gates {
+        for input in &gate.inputs {
+            dot.push_str(&format!(
+                "    {} -> {};\n",
+                input.as_string(),
+                gate.output.as_string()
+            ));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// The circuit as a structural Verilog module with the x and y wires as inputs and the z wires
+/// as outputs.
+pub fn to_verilog<const N: usize>(machine: &Machine<N>, module_name: &str) -> String {
+    let gates = gates(machine);
+    let names = |wire_names: &mut dyn Iterator<Item = WireName>| {
+        wire_names.map(|wire_name| wire_name.as_string()).join(", ")
+    };
+    let inputs = names(&mut input_names(machine).into_iter());
+    let outputs = names(
+        &mut gates
+            .iter()
+            .map(|gate| gate.output)
+            .filter(|name| name[0] == b'z')
+            .sorted(),
+    );
+    let wires = names(
+        &mut gates
+            .iter()
+            .map(|gate| gate.output)
+            .filter(|name| name[0] != b'z')
+            .sorted(),
+    );
+
+    let mut verilog = format!("module {module_name}({inputs}, {outputs});\n");
+    verilog.push_str(&format!("    input {inputs};\n    output {outputs};\n"));
+    if !wires.is_empty() {
+        verilog.push_str(&format!("    wire {wires};\n"));
+    }
+    verilog.push('\n');
+    for gate in gates {
+        let output = gate.output.as_string();
+        verilog.push_str(&format!(
+            "    {} g_{output} ({output}, {});\n",
+            gate.operation.name().to_lowercase(),
+            gate.inputs.iter().map(WireName::as_string).join(", ")
+        ));
+    }
+    verilog.push_str("endmodule\n");
+    verilog
+}
+
+/// Reads a module in the subset `to_verilog` writes, and returns it in the puzzle's input
+/// format with every input set to 0. Errors give the line of the Verilog statement.
+pub fn input_from_verilog(verilog: &str) -> Result<String> {
+    let statements = statements(verilog);
+    let mut statements = statements.iter().peekable();
+
+    let (line_no, module) = statements.next().unwrap();
+    if !module.starts_with("module ") {
+        return Err(MachineError::ParseError {
+            line_no: *line_no,
+            message: format!("expected a module, found '{module}'"),
+        });
+    }
+    let mut declared: HashSet<&str> = HashSet::new();
+    let mut inputs = Vec::new();
+    let mut gates = Vec::new();
+    let mut gate_lines = HashMap::new();
+    while let Some((line_no, statement)) = statements.next() {
+        let line_no = *line_no;
+        let error = |message: String| MachineError::ParseError { line_no, message };
+        let words: Vec<&str> = statement
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|word| !word.is_empty())
+            .collect();
+        match words[..] {
+            ["endmodule"] if statements.peek().is_none() => {
+                return finish(inputs, gates, &gate_lines);
+            }
+            [] => {}
+            ["input", ref names @ ..] => {
+                for &name in names {
+                    let wire_name = parser::wire_name(name).map_err(error)?;
+                    if wire_name[0] != b'x' && wire_name[0] != b'y' {
+                        return Err(error(format!("input {name} is not an x or y wire")));
+                    }
+                    inputs.push(wire_name);
+                }
+                declared.extend(names);
+            }
+            ["output" | "wire", ref names @ ..] => declared.extend(names),
+            _ => {
+                let primitive = primitive(statement)
+                    .ok_or_else(|| error(format!("unsupported statement '{statement}'")))?;
+                if let Some(name) = std::iter::once(&primitive.output)
+                    .chain(&primitive.inputs)
+                    .find(|name| !declared.contains(**name))
+                {
+                    return Err(error(format!("undeclared wire {name} in '{statement}'")));
+                }
+                let gate = Gate {
+                    output: parser::wire_name(primitive.output).map_err(error)?,
+                    operation: primitive.operation,
+                    inputs: primitive
+                        .inputs
+                        .into_iter()
+                        .map(parser::wire_name)
+                        .collect::<std::result::Result<_, _>>()
+                        .map_err(error)?,
+                };
+                if gate.output[0] == b'x' || gate.output[0] == b'y' {
+                    return Err(error(format!(
+                        "the gate drives the input wire {}",
+                        gate.output.as_string()
+                    )));
+                }
+                if let Some(first) = gate_lines.insert(gate.output, line_no) {
+                    return Err(error(format!(
+                        "{} is already driven by the gate on line {first}",
+                        gate.output.as_string()
+                    )));
+                }
+                gates.push((line_no, gate));
+            }
+        }
+    }
+    Err(MachineError::ParseError {
+        line_no: verilog.lines().count().max(1),
+        message: "expected endmodule at the end".to_string(),
+    })
+}
+
+// the text between semicolons without comments, each with the line its statement starts on
+fn statements(verilog: &str) -> Vec<(usize, String)> {
+    let last_line_no = verilog.lines().count().max(1);
+    let mut statements = Vec::new();
+    let mut statement = String::new();
+    let mut start = None;
+    for (idx, line) in verilog.lines().enumerate() {
+        let line_no = idx + 1;
+        let code = line.split("//").next().unwrap();
+        for (piece_idx, piece) in code.split(';').enumerate() {
+            if piece_idx > 0 {
+                statements.push((
+                    start.take().unwrap_or(line_no),
+                    statement.trim().to_string(),
+                ));
+                statement.clear();
+            }
+            if !piece.trim().is_empty() {
+                start.get_or_insert(line_no);
+            }
+            statement.push_str(piece);
+        }
+        statement.push('\n');
+    }
+    statements.push((start.unwrap_or(last_line_no), statement.trim().to_string()));
+    statements
+}
+
+// a gate primitive such as `and g1 (out, in1, in2)`, by the names of its wires
+struct Primitive<'a> {
+    operation: Operation,
+    output: &'a str,
+    inputs: Vec<&'a str>,
+}
+
+fn primitive(statement: &str) -> Option<Primitive<'_>> {
+    let (head, ports) = statement.split_once('(')?;
+    let ports: Vec<&str> = ports.strip_suffix(')')?.split(',').map(str::trim).collect();
+    let (keyword, instance) = match head.split_whitespace().collect::<Vec<_>>()[..] {
+        [keyword] => (keyword, None),
+        [keyword, instance] => (keyword, Some(instance)),
+        _ => return None,
+    };
+    let operation = Operation::ALL
+        .into_iter()
+        .find(|operation| operation.name().to_lowercase() == keyword)?;
+    let expected_inputs = match operation {
+        Operation::Not => 1..=1,
+        _ => 2..=usize::MAX,
+    };
+    let (output, inputs) = ports.split_first()?;
+    if instance.is_some_and(|instance| instance.contains(['(', ')']))
+        || !expected_inputs.contains(&inputs.len())
+        || ports
+            .iter()
+            .any(|port| port.is_empty() || port.contains(char::is_whitespace))
+    {
+        return None;
+    }
+    Some(Primitive {
+        operation,
+        output,
+        inputs: inputs.to_vec(),
+    })
+}
+
+fn finish(
+    inputs: Vec<WireName>,
+    gates: Vec<(usize, Gate)>,
+    gate_lines: &HashMap<WireName, usize>,
+) -> Result<String> {
+    for (line_no, gate) in &gates {
+        if let Some(input) = gate
+            .inputs
+            .iter()
+            .find(|input| input[0] != b'x' && input[0] != b'y' && !gate_lines.contains_key(*input))
+        {
+            return Err(MachineError::ParseError {
+                line_no: *line_no,
+                message: format!("{} is not driven by any gate", input.as_string()),
+            });
+        }
+    }
+    let values = inputs
+        .iter()
+        .sorted()
+        .map(|name| format!("{}: 0", name.as_string()))
+        .join("\n");
+    Ok(format!(
+        "{values}\n\n{}",
+        gates.iter().map(|(_, gate)| gate).join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TESTINPUT, adder_input};
+
+    #[test]
+    fn test_to_verilog() {
+        let machine = Machine::new(TESTINPUT);
+        assert_eq!(
+            to_verilog(&machine, "example"),
+            "\
+module example(x00, x01, x02, y00, y01, y02, z00, z01, z02);
+    input x00, x01, x02, y00, y01, y02;
+    output z00, z01, z02;
+
+    and g_z00 (z00, x00, y00);
+    xor g_z01 (z01, x01, y01);
+    or g_z02 (z02, x02, y02);
+endmodule
+"
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let machine = Machine::new(TESTINPUT);
+        let z01 = WireName::from_char_bit(b'z', 1);
+        let dot = to_dot(&machine, &[z01]);
+        assert!(dot.starts_with("digraph circuit {"));
+        assert!(dot.contains("    x00 [shape=ellipse];"));
+        assert!(dot.contains("    z00 [label=\"z00\\nAND\", peripheries=2];"));
+        assert!(
+            dot.contains("    z01 [label=\"z01\\nXOR\", peripheries=2, color=red, fontcolor=red];")
+        );
+        assert!(dot.contains("    y02 -> z02;"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = adder_input(12, 0, 0);
+        let machine = Machine::new(&input);
+        let imported = input_from_verilog(&to_verilog(&machine, "adder")).unwrap();
+        assert_eq!(gates(&Machine::new(&imported)), gates(&machine));
+        for (x, y) in [(0, 0), (4095, 1), (1234, 2345)] {
+            assert_eq!(Machine::calc(&imported, x, y), x + y);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_operations() {
+        let input =
+            "x00: 1\ny00: 0\n\nx00 NAND y00 NAND abc -> z00\nNOT x00 -> abc\nabc XNOR y00 -> z01";
+        let machine = Machine::new(input);
+        let verilog = to_verilog(&machine, "gates");
+        assert!(verilog.contains("    nand g_z00 (z00, x00, y00, abc);"));
+        assert!(verilog.contains("    not g_abc (abc, x00);"));
+        let imported = input_from_verilog(&verilog).unwrap();
+        assert_eq!(gates(&Machine::new(&imported)), gates(&machine));
+    }
+
+    #[test]
+    fn test_input_from_verilog() {
+        let verilog = "\
+// a half adder
+module half(x00, y00, z00, z01);
+    input x00, y00;
+    output z00,
+           z01;
+    xor (z00, x00, y00);   // without an instance name
+    and carry(z01, x00, y00);
+endmodule";
+        assert_eq!(
+            input_from_verilog(verilog).unwrap(),
+            "x00: 0\ny00: 0\n\nx00 XOR y00 -> z00\nx00 AND y00 -> z01"
+        );
+
+        let error = |verilog: &str| input_from_verilog(verilog).unwrap_err().to_string();
+        assert!(error("input x00;").contains("expected a module"));
+        assert!(error("module m(); input a00; endmodule").contains("not an x or y wire"));
+        assert!(
+            error("module m(); input x00; output z00; and (z00, x00, y00); endmodule")
+                .contains("undeclared wire y00")
+        );
+        assert!(error("module m(); assign z00 = x00; endmodule").contains("unsupported statement"));
+        assert!(error("module m(); input x00;").contains("expected endmodule"));
+        assert!(
+            error("module m(); input x00; output z00; not (z00, x00, x00); endmodule")
+                .contains("unsupported statement")
+        );
+        assert!(
+            error("module m(); input x00; output z00; buf (z00, x00); endmodule")
+                .contains("unsupported statement")
+        );
+    }
+
+    #[test]
+    fn test_input_from_verilog_error_lines() {
+        let error = |body: &str| {
+            let verilog = format!(
+                "// header\nmodule m(x00, y00, z00);\n    input x00, y00;\n    output z00;\n{body}\nendmodule"
+            );
+            input_from_verilog(&verilog).unwrap_err().to_string()
+        };
+        assert_eq!(
+            error("    wire abc;\n    and (z00, x00, abc);"),
+            "Parse Error: line 6: abc is not driven by any gate"
+        );
+        assert_eq!(
+            error("    and (z00, x00, y00);\n\n    or (z00,\n        x00, y00);"),
+            "Parse Error: line 7: z00 is already driven by the gate on line 5"
+        );
+        assert_eq!(
+            error("    wire x01;\n    not (x01, x00);"),
+            "Parse Error: line 6: the gate drives the input wire x01"
+        );
+        assert_eq!(
+            error("    wire abcd;\n    and (z00, x00, y00); not (abcd, x00);"),
+            "Parse Error: line 6: invalid wire name 'abcd'"
+        );
+        assert_eq!(
+            error("    // a comment; not a statement\n    assign z00 = x00;"),
+            "Parse Error: line 6: unsupported statement 'assign z00 = x00'"
+        );
+        assert_eq!(
+            input_from_verilog("\n\n  input x00;")
+                .unwrap_err()
+                .to_string(),
+            "Parse Error: line 3: expected a module, found 'input x00'"
+        );
+        assert_eq!(
+            input_from_verilog("module m(x00);\n  input x00;\n")
+                .unwrap_err()
+                .to_string(),
+            "Parse Error: line 2: expected endmodule at the end"
+        );
+    }
+}
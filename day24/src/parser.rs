@@ -0,0 +1,277 @@
+//! Reads the puzzle's input format, one input value or gate per line:
+//!
+//! ```text
+//! x00: 1
+//! x00 AND y00 -> abc
+//! abc NOR x01 NOR y01 -> z00
+//! NOT abc -> z01
+//! ```
+//!
+//! Gates take two or more inputs joined by the same operation, or a single input for `NOT`.
+//! Tokens may be separated by any whitespace, and blank lines are ignored.
+
+use std::{collections::HashMap, fmt::Display};
+
+use itertools::Itertools;
+
+use crate::{
+    errors::{MachineError, Result},
+    machine::{MAX_INPUT_BITS, MAX_OUTPUT_BITS, Operation},
+    wire::WireName,
+};
+
+/// A gate by wire names.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Gate {
+    pub output: WireName,
+    pub operation: Operation,
+    pub inputs: Vec<WireName>,
+}
+
+impl Display for Gate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inputs = self.inputs.iter().map(WireName::as_string);
+        match self.operation {
+            Operation::Not => write!(f, "NOT {}", inputs.format(" ")),
+            operation => write!(f, "{}", inputs.format(&format!(" {} ", operation.name()))),
+        }?;
+        write!(f, " -> {}", self.output.as_string())
+    }
+}
+
+/// The input values and gates of a circuit, checked to be consistent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Circuit {
+    pub values: Vec<(WireName, bool)>,
+    pub gates: Vec<Gate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Word(&'a str),
+    Colon,
+    Arrow,
+}
+
+fn tokenize(line: &str) -> std::result::Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = line.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let len = if rest.starts_with("->") {
+            tokens.push(Token::Arrow);
+            2
+        } else if c == ':' {
+            tokens.push(Token::Colon);
+            1
+        } else {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            if len == 0 {
+                return Err(format!("unexpected character '{c}'"));
+            }
+            tokens.push(Token::Word(&rest[..len]));
+            len
+        };
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+// the letter and bit of an x, y or z wire name
+pub(crate) fn numbered_wire(name: &[u8]) -> Option<(u8, usize)> {
+    match name {
+        [letter @ (b'x' | b'y' | b'z'), tens, units]
+            if tens.is_ascii_digit() && units.is_ascii_digit() =>
+        {
+            Some((*letter, ((tens - b'0') * 10 + (units - b'0')) as usize))
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn wire_name(name: &str) -> std::result::Result<WireName, String> {
+    match numbered_wire(name.as_bytes()) {
+        Some((b'z', bit)) if bit >= MAX_OUTPUT_BITS => Err(format!(
+            "{name} is beyond the {MAX_OUTPUT_BITS} supported output bits"
+        )),
+        Some((b'x' | b'y', bit)) if bit >= MAX_INPUT_BITS => Err(format!(
+            "{name} is beyond the {MAX_INPUT_BITS} supported input bits"
+        )),
+        Some(_) => Ok(WireName::from_slice(name.as_bytes())),
+        None if name.len() == 3 && !name.starts_with(['x', 'y', 'z']) => {
+            Ok(WireName::from_slice(name.as_bytes()))
+        }
+        None => Err(format!("invalid wire name '{name}'")),
+    }
+}
+
+fn operation(word: &str) -> std::result::Result<Operation, String> {
+    Operation::ALL
+        .into_iter()
+        .find(|operation| operation.name() == word)
+        .ok_or_else(|| format!("unknown operation '{word}'"))
+}
+
+fn parse_gate(tokens: &[Token]) -> std::result::Result<Gate, String> {
+    let (operands, [Token::Arrow, Token::Word(output)]) =
+        tokens.split_at(tokens.len().saturating_sub(2))
+    else {
+        return Err("expected '-> <wire>' at the end of the gate".to_string());
+    };
+    let words = operands
+        .iter()
+        .map(|token| match token {
+            Token::Word(word) => Ok(*word),
+            _ => Err("unexpected ':' or '->' in the gate inputs".to_string()),
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let (operation, inputs) = match words[..] {
+        ["NOT", input] => (Operation::Not, vec![input]),
+        ["NOT", ..] => return Err("NOT takes a single input".to_string()),
+        [_, word, _, ..] if words.len() % 2 == 1 => {
+            let operation = operation(word)?;
+            if operation == Operation::Not {
+                return Err("NOT takes a single input".to_string());
+            }
+            if let Some(other) = words
+                .iter()
+                .skip(1)
+                .step_by(2)
+                .find(|&&other| other != word)
+            {
+                return Err(format!("can't mix {word} and {other} in one gate"));
+            }
+            (operation, words.iter().step_by(2).copied().collect())
+        }
+        _ => return Err("expected inputs joined by an operation, or NOT and an input".to_string()),
+    };
+    let output = wire_name(output)?;
+    if output[0] == b'x' || output[0] == b'y' {
+        return Err(format!(
+            "the gate drives the input wire {}",
+            output.as_string()
+        ));
+    }
+    Ok(Gate {
+        output,
+        operation,
+        inputs: inputs
+            .into_iter()
+            .map(wire_name)
+            .collect::<std::result::Result<_, _>>()?,
+    })
+}
+
+pub fn parse(input: &str) -> Result<Circuit> {
+    let mut circuit = Circuit::default();
+    let mut gate_lines = HashMap::new();
+    let mut line_nos = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        let error = |message: String| MachineError::ParseError { line_no, message };
+        let tokens = tokenize(line).map_err(error)?;
+        match tokens[..] {
+            [] => {}
+            [
+                Token::Word(name),
+                Token::Colon,
+                Token::Word(value @ ("0" | "1")),
+            ] => {
+                let name = wire_name(name).map_err(error)?;
+                if name[0] != b'x' && name[0] != b'y' {
+                    return Err(error(format!(
+                        "input values are for x and y wires, not {}",
+                        name.as_string()
+                    )));
+                }
+                circuit.values.push((name, value == "1"));
+            }
+            [_, Token::Colon, ..] => return Err(error("expected '<wire>: 0' or 1".to_string())),
+            _ => {
+                let gate = parse_gate(&tokens).map_err(error)?;
+                if let Some(first) = gate_lines.insert(gate.output, line_no) {
+                    return Err(error(format!(
+                        "{} is already driven by the gate on line {first}",
+                        gate.output.as_string()
+                    )));
+                }
+                circuit.gates.push(gate);
+                line_nos.push(line_no);
+            }
+        }
+    }
+    for (gate, &line_no) in circuit.gates.iter().zip(&line_nos) {
+        if let Some(input) = gate
+            .inputs
+            .iter()
+            .find(|input| input[0] != b'x' && input[0] != b'y' && !gate_lines.contains_key(*input))
+        {
+            return Err(MachineError::ParseError {
+                line_no,
+                message: format!("{} is not driven by any gate", input.as_string()),
+            });
+        }
+    }
+    Ok(circuit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TESTINPUT2;
+
+    fn error(input: &str) -> String {
+        parse(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse() {
+        let circuit = parse(TESTINPUT2).unwrap();
+        assert_eq!(circuit.values.len(), 10);
+        assert_eq!(circuit.gates.len(), 36);
+        assert_eq!(circuit.gates[0].to_string(), "ntg XOR fgs -> mjb");
+
+        let circuit =
+            parse("  x00 :1\r\n\n\ty00: 0\nx00  NAND\ty00->abc  \n\nNOT abc -> z00\n").unwrap();
+        assert_eq!(circuit.values.len(), 2);
+        assert_eq!(
+            circuit
+                .gates
+                .iter()
+                .map(Gate::to_string)
+                .collect::<Vec<_>>(),
+            ["x00 NAND y00 -> abc", "NOT abc -> z00"]
+        );
+        let circuit = parse("x00 XNOR y00 XNOR abc -> z00\nx01 NOR y01 -> abc").unwrap();
+        assert_eq!(circuit.gates[0].operation, Operation::Xnor);
+        assert_eq!(circuit.gates[0].inputs.len(), 3);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            error("x00: 1\nx00 AND y00 => z00"),
+            "Parse Error: line 2: unexpected character '='"
+        );
+        assert!(error("x00: 2").contains("line 1: expected '<wire>: 0' or 1"));
+        assert!(error("z00: 1").contains("not z00"));
+        assert!(error("x00 AND y00").contains("expected '-> <wire>'"));
+        assert!(error("x00 FOO y00 -> z00").contains("unknown operation 'FOO'"));
+        assert!(error("x00 AND y00 OR x01 -> z00").contains("can't mix AND and OR"));
+        assert!(error("x00 AND -> z00").contains("expected inputs joined"));
+        assert!(error("NOT x00 y00 -> z00").contains("NOT takes a single input"));
+        assert!(error("x00 NOT y00 -> z00").contains("NOT takes a single input"));
+        assert!(error("x00 AND abcd -> z00").contains("invalid wire name 'abcd'"));
+        assert!(error("x00 AND y00 -> x01").contains("drives the input wire x01"));
+        assert!(error("x63 AND y00 -> z00").contains("supported input bits"));
+        assert_eq!(
+            error("x00 AND y00 -> z00\n\nx00 OR y00 -> z00"),
+            "Parse Error: line 3: z00 is already driven by the gate on line 1"
+        );
+        assert_eq!(
+            error("x00 AND abc -> z00"),
+            "Parse Error: line 1: abc is not driven by any gate"
+        );
+    }
+}
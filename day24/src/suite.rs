@@ -0,0 +1,251 @@
+//! Test suites which check that a machine adds, in batches of as many cases as it holds.
+//!
+//! A report counts the cases each z bit got wrong, split by the value the bit should have had,
+//! and keeps the simplest failing case for each bit: the one with the fewest input bits set.
+
+use std::fmt::Display;
+
+use aoc_common::rng::Rng;
+
+use crate::{
+    errors::{MachineError, Result},
+    machine::{InputPair, Machine, TestCaseOutput},
+};
+
+/// Exhaustive checks run every pair of inputs, so they are limited to small adders.
+pub const MAX_EXHAUSTIVE_BITS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suite {
+    /// `count` pairs of inputs, the same for the same seed.
+    Random { seed: u64, count: usize },
+    /// A carry made at each bit and carried through each run of the bits above it, with either
+    /// input holding the run.
+    Carries,
+    /// Every pair of inputs.
+    Exhaustive,
+}
+
+impl Suite {
+    pub fn cases(&self, input_bits: usize) -> Result<Vec<InputPair>> {
+        let mask = (1 << input_bits) - 1;
+        match *self {
+            Suite::Random { seed, count } => {
+                let mut rng = Rng::new(seed);
+                Ok((0..count)
+                    .map(|_| InputPair {
+                        x: rng.next_u64() as usize & mask,
+                        y: rng.next_u64() as usize & mask,
+                    })
+                    .collect())
+            }
+            Suite::Carries => {
+                let mut cases = vec![InputPair::default()];
+                for start in 0..input_bits {
+                    for end in start..input_bits {
+                        let run = (2 << end) - (1 << start);
+                        cases.push(InputPair {
+                            x: run,
+                            y: 1 << start,
+                        });
+                        cases.push(InputPair {
+                            x: 1 << start,
+                            y: run,
+                        });
+                    }
+                }
+                Ok(cases)
+            }
+            Suite::Exhaustive if input_bits > MAX_EXHAUSTIVE_BITS => {
+                Err(MachineError::LogicError(format!(
+                    "exhaustive checks are limited to {MAX_EXHAUSTIVE_BITS} input bits, not {input_bits}"
+                )))
+            }
+            Suite::Exhaustive => Ok((0..=mask)
+                .flat_map(|x| (0..=mask).map(move |y| InputPair { x, y }))
+                .collect()),
+        }
+    }
+}
+
+/// The failures of a suite by z bit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuiteReport {
+    pub cases: usize,
+    pub failed_cases: usize,
+    // for each z bit, the cases it got wrong where it should have been 0, and where 1
+    pub bit_failures: Vec<[usize; 2]>,
+    pub examples: Vec<Option<TestCaseOutput>>,
+}
+
+impl SuiteReport {
+    fn new(output_bits: usize) -> Self {
+        Self {
+            cases: 0,
+            failed_cases: 0,
+            bit_failures: vec![[0; 2]; output_bits],
+            examples: vec![None; output_bits],
+        }
+    }
+
+    fn add(&mut self, output: &TestCaseOutput) {
+        self.cases += 1;
+        if output.z_wire_misses == 0 {
+            return;
+        }
+        self.failed_cases += 1;
+        let simplicity = |output: &TestCaseOutput| {
+            let ones = output.x.count_ones() + output.y.count_ones();
+            (ones, output.x | output.y, output.x, output.y)
+        };
+        for bit in (0..self.bit_failures.len()).filter(|bit| output.z_wire_misses >> bit & 1 == 1) {
+            self.bit_failures[bit][output.expected >> bit & 1] += 1;
+            let example = &mut self.examples[bit];
+            if example.is_none_or(|example| simplicity(output) < simplicity(&example)) {
+                *example = Some(*output);
+            }
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.failed_cases == 0
+    }
+
+    pub fn failing_bits(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.bit_failures.len()).filter(|&bit| self.examples[bit].is_some())
+    }
+
+    pub fn lowest_failing_bit(&self) -> Option<usize> {
+        self.failing_bits().next()
+    }
+}
+
+impl Display for SuiteReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} of {} cases failed", self.failed_cases, self.cases)?;
+        for bit in self.failing_bits() {
+            let [should_be_0, should_be_1] = self.bit_failures[bit];
+            let example = self.examples[bit].unwrap();
+            write!(
+                f,
+                "\nz{bit:02}: {should_be_0} wrong where 0 was expected, {should_be_1} where 1 was, e.g. {} + {} gave {} instead of {}",
+                example.x, example.y, example.actual, example.expected
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<const NO_CASES: usize> Machine<NO_CASES> {
+    /// Runs every case of the suite, and leaves the machine with its own cases again.
+    pub fn verify(&mut self, suite: &Suite) -> Result<SuiteReport> {
+        let layout = self.layout();
+        let suite_cases = suite.cases(layout.input_bits)?;
+        let own_cases = *self.cases();
+        let mut report = SuiteReport::new(layout.output_bits);
+        for batch in suite_cases.chunks(NO_CASES) {
+            let mut cases = [InputPair::default(); NO_CASES];
+            cases[..batch.len()].copy_from_slice(batch);
+            self.set_cases(cases);
+            for output in &self.eval_output()[..batch.len()] {
+                report.add(output);
+            }
+        }
+        self.set_cases(own_cases);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{adder_input, machine::MAX_INPUT_BITS, machine_fixer::test_cases, wire::WireName};
+
+    const SUITES: [Suite; 3] = [
+        Suite::Random {
+            seed: 24,
+            count: 1000,
+        },
+        Suite::Carries,
+        Suite::Exhaustive,
+    ];
+
+    #[test]
+    fn test_suite_cases() {
+        assert_eq!(Suite::Carries.cases(45).unwrap().len(), 1 + 45 * 46);
+        assert_eq!(Suite::Exhaustive.cases(3).unwrap().len(), 64);
+        let random = Suite::Random { seed: 0, count: 50 }.cases(10).unwrap();
+        assert!(random.iter().all(|case| case.x < 1024 && case.y < 1024));
+        assert_ne!(random[0], random[1]);
+        assert_eq!(
+            Suite::Random { seed: 0, count: 50 }.cases(10).unwrap(),
+            random
+        );
+        assert!(
+            Suite::Exhaustive
+                .cases(MAX_EXHAUSTIVE_BITS + 1)
+                .unwrap_err()
+                .to_string()
+                .contains("limited to 8 input bits")
+        );
+        assert!(Suite::Carries.cases(MAX_INPUT_BITS).is_ok());
+    }
+
+    #[test]
+    fn test_correct_adder() {
+        let mut machine =
+            Machine::<64>::new_with_cases(&adder_input(6, 0, 0), [InputPair::default(); 64]);
+        for suite in SUITES {
+            let report = machine.verify(&suite).unwrap();
+            assert!(report.passed(), "{suite:?} {report}");
+        }
+        assert_eq!(machine.verify(&Suite::Exhaustive).unwrap().cases, 4096);
+        let mut machine = Machine::<1>::new(&adder_input(45, 3, 5));
+        assert!(machine.verify(&Suite::Carries).unwrap().passed());
+        assert!(machine.verify(&Suite::Exhaustive).is_err());
+        assert_eq!(machine.eval_output()[0].actual, 8);
+    }
+
+    #[test]
+    fn test_swapped_adder() {
+        let mut machine =
+            Machine::<100>::new_with_cases(&adder_input(8, 0, 0), [InputPair::default(); 100]);
+        let a3 = machine.get_gate_index(WireName(*b"a03"));
+        let s3 = machine.get_gate_index(WireName(*b"s03"));
+        machine.swap_outputs(a3, s3).unwrap();
+        for suite in SUITES {
+            let report = machine.verify(&suite).unwrap();
+            assert_eq!(report.lowest_failing_bit(), Some(3), "{suite:?}");
+        }
+        // the sum and carry of bit 3 are only right when neither input has it set
+        let report = machine.verify(&Suite::Exhaustive).unwrap();
+        assert!(
+            report
+                .to_string()
+                .starts_with("49152 of 65536 cases failed\nz03:")
+        );
+        assert!(report.bit_failures[3].iter().all(|&count| count > 0));
+        let example = report.examples[3].unwrap();
+        assert_eq!((example.x, example.y, example.actual), (0, 8, 16));
+    }
+
+    #[test]
+    fn test_long_carry_chain() {
+        // the carry out of bit 31 is lost only when it has come through bits 29 and 30 as well
+        let input = adder_input(45, 0, 0).replace(
+            "s31 AND c30 -> b31",
+            "s31 AND c30 AND d31 -> b31\nb30 NAND b29 -> d31",
+        );
+        let mut machine = Machine::new_with_cases(&input, test_cases(45));
+        assert!(
+            machine
+                .eval_output()
+                .iter()
+                .all(|output| output.z_wire_misses == 0)
+        );
+        let report = machine.verify(&Suite::Carries).unwrap();
+        assert_eq!(report.lowest_failing_bit(), Some(32));
+        let example = report.examples[32].unwrap();
+        assert_eq!((example.x | example.y).count_ones(), 4);
+    }
+}
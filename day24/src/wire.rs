@@ -2,26 +2,14 @@ use crate::errors::MachineError;
 
 use crate::errors::Result;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Wire<const N: usize> {
     pub wire_name: WireName,
     pub wire_index: usize,
     pub(crate) value_start: WireValue<usize, N>,
-    pub(crate) value_calc: WireValue<usize, N>,
-    pub wire_analytics: WireAnalytics,
-}
-
-impl<const N: usize> PartialOrd for Wire<N> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        debug_assert!(!(self.depends_on(other) && other.depends_on(self)));
-        if self.depends_on(other) {
-            Some(std::cmp::Ordering::Greater)
-        } else if other.depends_on(self) {
-            Some(std::cmp::Ordering::Less)
-        } else {
-            None
-        }
-    }
+    // the value found by the latest evaluation, one bit for each case
+    pub(crate) value_calc: BitSet,
+    pub(crate) wire_analytics: WireAnalytics,
 }
 
 impl<const N: usize> Wire<N> {
@@ -42,13 +30,15 @@ impl<const N: usize> Wire<N> {
     }
 }
 
-use crate::bit_array::BitFlags;
+use crate::bit_array::BitSet;
 use crate::machine::Operation;
 use crate::wire_analytics::WireAnalytics;
 
 use std::fmt::Debug;
 
-use std::ops::{BitAndAssign, BitOrAssign, Deref, Not};
+use std::ops::Deref;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(PartialEq, Copy, Clone, Hash, Eq, PartialOrd, Ord)]
 pub struct WireName(pub [u8; 3]);
@@ -59,6 +49,25 @@ impl Default for WireName {
     }
 }
 
+// a wire name is written as its string, in JSON and elsewhere
+impl Serialize for WireName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.as_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for WireName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        match name.as_bytes() {
+            bytes @ [_, _, _] => Ok(Self::from_slice(bytes)),
+            _ => Err(serde::de::Error::custom(format!(
+                "invalid wire name '{name}'"
+            ))),
+        }
+    }
+}
+
 impl Deref for WireName {
     type Target = [u8; 3];
 
@@ -95,18 +104,17 @@ impl WireName {
     }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord)]
 pub enum WireValue<T, const NO_CASES: usize> {
-    Value(BitFlags<u128>),
+    Value(BitSet),
     Gate {
-        input1: T,
-        input2: T,
+        inputs: Vec<T>,
         operation: Operation,
     },
 }
 
 impl<T, const NO_CASES: usize> Default for WireValue<T, NO_CASES> {
     fn default() -> Self {
-        Self::Value(BitFlags::new())
+        Self::Value(BitSet::default())
     }
 }
@@ -1,13 +1,13 @@
-use crate::{bit_array::LargeBitFlags, errors::MachineError};
+use crate::{bit_array::BitSet, errors::MachineError};
 
 use super::wire::WireName;
 
 use crate::bit_array::BitFlags;
 
-#[derive(Clone, PartialEq, Eq, Debug, Default, Copy, PartialOrd, Ord)]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
 pub(crate) struct WireAnalytics {
     pub(crate) wire_type: WireType,
-    pub(crate) gate_array: LargeBitFlags,
+    pub(crate) gate_array: BitSet,
     pub(crate) x_bits_used: BitFlags<u64>,
     pub(crate) y_bits_used: BitFlags<u64>,
     pub(crate) generation: u8,
@@ -30,18 +30,20 @@ pub enum WireType {
 }
 
 impl WireAnalytics {
-    pub fn merge(&self, other: &Self) -> Self {
-        Self {
-            wire_type: WireType::GateOutput,
-            gate_array: self.gate_array.merge(&other.gate_array),
-            x_bits_used: self.x_bits_used | other.x_bits_used,
-            y_bits_used: self.y_bits_used | other.y_bits_used,
-            generation: self.generation.max(other.generation) + 1,
-            highest_input_bit: self.highest_input_bit.max(other.highest_input_bit),
-            lowest_output_bit: self.lowest_output_bit.max(other.lowest_output_bit),
+    // the analytics of a gate output, from those of its inputs
+    pub fn merge(inputs: &[Self]) -> Self {
+        let mut merged = Self::default();
+        for input in inputs {
+            merged.gate_array |= &input.gate_array;
+            merged.x_bits_used |= input.x_bits_used;
+            merged.y_bits_used |= input.y_bits_used;
+            merged.generation = merged.generation.max(input.generation.saturating_add(1));
+            merged.highest_input_bit = merged.highest_input_bit.max(input.highest_input_bit);
+            merged.lowest_output_bit = merged.lowest_output_bit.max(input.lowest_output_bit);
         }
+        merged
     }
-    pub fn new_input_wire(wire_type: WireType, bit: usize) -> Self {
+    pub fn new_input_wire(wire_type: WireType, bit: usize, no_wires: usize) -> Self {
         let mut x_bits_used = BitFlags::new();
         let mut y_bits_used = BitFlags::new();
         match wire_type {
@@ -51,7 +53,7 @@ impl WireAnalytics {
         }
         Self {
             wire_type,
-            gate_array: LargeBitFlags::default(),
+            gate_array: BitSet::new(no_wires),
             x_bits_used,
             y_bits_used,
             generation: 0,
@@ -59,7 +61,7 @@ impl WireAnalytics {
             lowest_output_bit: u8::MAX,
         }
     }
-    pub fn new_gate_wire(wire_name: WireName) -> Self {
+    pub fn new_gate_wire(wire_name: WireName, no_wires: usize) -> Self {
         let wire_type = match wire_name[0] {
             b'x' | b'y' => unreachable!(),
             b'z' => WireType::Z,
@@ -68,7 +70,7 @@ impl WireAnalytics {
 
         Self {
             wire_type,
-            gate_array: LargeBitFlags::default(),
+            gate_array: BitSet::new(no_wires),
             x_bits_used: BitFlags::new(),
             y_bits_used: BitFlags::new(),
             generation: 0,
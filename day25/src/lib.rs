@@ -0,0 +1,82 @@
+use aoc_common::{Result, Solution, Unsolved};
+
+#[cfg(feature = "embedded-input")]
+pub const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(INPUT);
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[cfg(test)]
+const TESTINPUT: &str = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####";
+
+pub mod locks;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Parsed = locks::Schematics;
+    type Part1 = usize;
+    type Part2 = Unsolved;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        locks::Schematics::parse_input(input)
+    }
+
+    fn part1(schematics: &Self::Parsed) -> Result<usize> {
+        Ok(schematics.count_matches())
+    }
+
+    fn part2(_: &Self::Parsed) -> Result<Unsolved> {
+        Ok(Unsolved)
+    }
+}
+
+#[cfg(all(test, feature = "embedded-input"))]
+mod tests {
+    use crate::{Day25, INPUT};
+    use aoc_common::Solution;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day25::solve_part1(INPUT).unwrap(), 3690);
+    }
+}
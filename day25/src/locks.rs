@@ -1,5 +1,8 @@
+use aoc_common::{
+    ParseError, Result,
+    grid::{Grid, Point},
+};
 use stephen_morris_utils::bit_array::BitFlags;
-use stephen_morris_utils::grid::{Grid, Point};
 
 #[derive(Debug)]
 struct Key(BitFlags<u32>);
@@ -13,13 +16,33 @@ pub struct Schematics {
 }
 
 impl Schematics {
-    pub fn parse_input(input: &str) -> Schematics {
+    /// Reads schematics of 5 columns and 7 rows, separated by blank lines. A lock has a full top
+    /// row and a key a full bottom row.
+    pub fn parse_input(input: &str) -> Result<Schematics> {
         let mut keys = Vec::new();
         let mut locks = Vec::new();
 
-        let blocks = input.split("\n\n");
-        for block in blocks {
-            let grid = Grid::from(block);
+        let lines: Vec<&str> = input.lines().collect();
+        let mut offset = 0;
+        for block in lines.split(|line| line.trim().is_empty()) {
+            let start = offset;
+            offset += block.len() + 1;
+            if block.is_empty() {
+                continue;
+            }
+            let grid = Grid::parse(&block.join("\n"), |byte| byte == b'#' || byte == b'.')
+                .map_err(|error| error.offset(start))?;
+            let error = |message| Err(ParseError::at_line(start + 1, message));
+            if (grid.width, grid.height) != (5, 7) {
+                return error("expected a schematic of 5 columns and 7 rows");
+            }
+            let rows = grid.as_slice();
+            let (top, bottom) = (&rows[..5], &rows[30..]);
+            if !matches!((top, bottom), (b"#####", b".....") | (b".....", b"#####")) {
+                return error(
+                    "expected a lock with a full top row or a key with a full bottom row",
+                );
+            }
 
             let mut bits: BitFlags<u32> = BitFlags::new();
             // We only care about the middle rows, so take a sub-slice.
@@ -38,7 +61,7 @@ impl Schematics {
             }
         }
 
-        Schematics { keys, locks }
+        Ok(Schematics { keys, locks })
     }
 
     pub fn count_matches(&self) -> usize {
@@ -58,9 +81,27 @@ impl Schematics {
 mod tests {
     use crate::{TESTINPUT, locks::Schematics};
 
+    #[test]
+    fn test_parse_errors() {
+        let error = |input: String| Schematics::parse_input(&input).unwrap_err().to_string();
+        let lock = "#####\n.####\n.####\n.####\n.#.#.\n.#...\n.....";
+        assert_eq!(
+            error(format!("{lock}\n\n#####\n.....")),
+            "line 9: expected a schematic of 5 columns and 7 rows"
+        );
+        assert_eq!(
+            error(format!("{lock}\n\n{}", lock.replace("#####", "##.##"))),
+            "line 9: expected a lock with a full top row or a key with a full bottom row"
+        );
+        assert_eq!(
+            error(format!("{lock}\n\n\n{}", lock.replace(".#...", ".#x.."))),
+            "line 15: unexpected character 'x'"
+        );
+    }
+
     #[test]
     fn test_part1() {
-        let count = Schematics::parse_input(TESTINPUT).count_matches();
+        let count = Schematics::parse_input(TESTINPUT).unwrap().count_matches();
         assert_eq!(count, 3);
     }
 }
@@ -1,67 +1,14 @@
+use aoc_common::input;
+use day25::{EMBEDDED_INPUT, locks};
 use stephen_morris_utils::timer::time;
-const INPUT: &str = include_str!("input.txt");
-
-#[cfg(test)]
-const TESTINPUT: &str = "#####
-.####
-.####
-.####
-.#.#.
-.#...
-.....
-
-#####
-##.##
-.#.##
-...##
-...#.
-...#.
-.....
-
-.....
-#....
-#....
-#...#
-#.#.#
-#.###
-#####
-
-.....
-.....
-#.#..
-###..
-###.#
-###.#
-#####
-
-.....
-.....
-.....
-#....
-#.#..
-#.#.#
-#####";
-
-mod locks;
 
 fn main() {
-    part1();
-}
-
-fn part1() -> usize {
-    let schem = time(|| locks::Schematics::parse_input(INPUT), "parse_input");
+    let input = input::load_from_args(25, EMBEDDED_INPUT);
+    let schem = time(
+        || input::or_exit(locks::Schematics::parse_input(&input)),
+        "parse_input",
+    );
     let count = time(|| schem.count_matches(), "count_matches");
     schem.print_duration();
     count.print_all();
-    *count
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::part1;
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(), 3690);
-    }
 }